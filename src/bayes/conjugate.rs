@@ -0,0 +1,270 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Bernoulli, Beta, Categorical, Gamma, Gaussian, NegativeBinomial};
+use source::Source;
+
+/// A beta prior for the success probability of a Bernoulli distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BetaBernoulli {
+    alpha: f64,
+    beta: f64,
+}
+
+/// A gamma prior for the rate of a Poisson distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaPoisson {
+    k: f64,
+    theta: f64,
+}
+
+/// A Gaussian prior for the mean of a Gaussian distribution with a known
+/// standard deviation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalNormal {
+    mu: f64,
+    tau: f64,
+    sigma: f64,
+}
+
+/// A Dirichlet prior for the probabilities of a categorical distribution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirichletCategorical {
+    alpha: Vec<f64>,
+}
+
+impl BetaBernoulli {
+    /// Create a model with prior shape parameters `alpha` and `beta`.
+    ///
+    /// It should hold that `alpha > 0` and `beta > 0`.
+    #[inline]
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        should!(alpha > 0.0 && beta > 0.0);
+        BetaBernoulli { alpha, beta }
+    }
+
+    /// Update the model with observations, which should be zeros and ones.
+    pub fn update(&mut self, data: &[u8]) {
+        for &x in data {
+            should!(x <= 1);
+            if x == 1 {
+                self.alpha += 1.0;
+            } else {
+                self.beta += 1.0;
+            }
+        }
+    }
+
+    /// Return the posterior distribution of the success probability.
+    #[inline]
+    pub fn posterior(&self) -> Beta {
+        Beta::new(self.alpha, self.beta, 0.0, 1.0)
+    }
+
+    /// Return the posterior predictive distribution of an observation.
+    #[inline]
+    pub fn predictive(&self) -> Bernoulli {
+        Bernoulli::new(self.alpha / (self.alpha + self.beta))
+    }
+}
+
+impl GammaPoisson {
+    /// Create a model with prior shape parameter `k` and scale parameter
+    /// `theta`.
+    ///
+    /// It should hold that `k > 0` and `theta > 0`.
+    #[inline]
+    pub fn new(k: f64, theta: f64) -> Self {
+        should!(k > 0.0 && theta > 0.0);
+        GammaPoisson { k, theta }
+    }
+
+    /// Update the model with observations.
+    pub fn update(&mut self, data: &[usize]) {
+        let sum = data.iter().fold(0.0, |sum, &x| sum + x as f64);
+        self.k += sum;
+        self.theta /= 1.0 + data.len() as f64 * self.theta;
+    }
+
+    /// Return the posterior distribution of the rate.
+    #[inline]
+    pub fn posterior(&self) -> Gamma {
+        Gamma::new(self.k, self.theta)
+    }
+
+    /// Return the posterior predictive distribution of an observation.
+    #[inline]
+    pub fn predictive(&self) -> NegativeBinomial {
+        NegativeBinomial::new(self.k, 1.0 / (1.0 + self.theta))
+    }
+}
+
+impl NormalNormal {
+    /// Create a model with prior mean `mu` and prior standard deviation `tau`
+    /// for observations with standard deviation `sigma`.
+    ///
+    /// It should hold that `tau > 0` and `sigma > 0`.
+    #[inline]
+    pub fn new(mu: f64, tau: f64, sigma: f64) -> Self {
+        should!(tau > 0.0 && sigma > 0.0);
+        NormalNormal { mu, tau, sigma }
+    }
+
+    /// Update the model with observations.
+    pub fn update(&mut self, data: &[f64]) {
+        if data.is_empty() {
+            return;
+        }
+        let prior = (self.tau * self.tau).recip();
+        let likelihood = data.len() as f64 / (self.sigma * self.sigma);
+        let sum = data.iter().fold(0.0, |sum, &x| sum + x);
+        let precision = prior + likelihood;
+        self.mu = (self.mu * prior + sum / (self.sigma * self.sigma)) / precision;
+        self.tau = precision.recip().sqrt();
+    }
+
+    /// Return the posterior distribution of the mean.
+    #[inline]
+    pub fn posterior(&self) -> Gaussian {
+        Gaussian::new(self.mu, self.tau)
+    }
+
+    /// Return the posterior predictive distribution of an observation.
+    #[inline]
+    pub fn predictive(&self) -> Gaussian {
+        Gaussian::new(self.mu, libm::hypot(self.tau, self.sigma))
+    }
+}
+
+impl DirichletCategorical {
+    /// Create a model with prior concentration parameters `alpha`.
+    ///
+    /// It should hold that `alpha[i] > 0`.
+    #[inline]
+    pub fn new(alpha: &[f64]) -> Self {
+        should!(!alpha.is_empty() && alpha.iter().all(|&alpha| alpha > 0.0));
+        DirichletCategorical {
+            alpha: alpha.to_vec(),
+        }
+    }
+
+    /// Update the model with observations, which should be category indices.
+    pub fn update(&mut self, data: &[usize]) {
+        for &x in data {
+            should!(x < self.alpha.len());
+            self.alpha[x] += 1.0;
+        }
+    }
+
+    /// Return the concentration parameters of the posterior distribution of
+    /// the probabilities, which is a Dirichlet distribution.
+    #[inline(always)]
+    pub fn posterior(&self) -> &[f64] {
+        &self.alpha
+    }
+
+    /// Draw the probabilities from the posterior distribution.
+    pub fn sample_posterior<S>(&self, source: &mut S) -> Vec<f64>
+    where
+        S: Source,
+    {
+        use distribution::Sample;
+        let mut p = self
+            .alpha
+            .iter()
+            .map(|&alpha| Gamma::new(alpha, 1.0).sample(source))
+            .collect::<Vec<_>>();
+        let sum = p.iter().fold(0.0, |sum, &p| sum + p);
+        for p in p.iter_mut() {
+            *p /= sum;
+        }
+        p
+    }
+
+    /// Return the posterior predictive distribution of an observation.
+    pub fn predictive(&self) -> Categorical {
+        let sum = self.alpha.iter().fold(0.0, |sum, &alpha| sum + alpha);
+        let p = self
+            .alpha
+            .iter()
+            .map(|&alpha| alpha / sum)
+            .collect::<Vec<_>>();
+        Categorical::new(&p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::{BetaBernoulli, DirichletCategorical, GammaPoisson, NormalNormal};
+
+    #[test]
+    fn beta_bernoulli() {
+        let mut model = BetaBernoulli::new(2.0, 3.0);
+        model.update(&[1, 1, 0, 1]);
+        assert_eq!(model.posterior(), Beta::new(5.0, 4.0, 0.0, 1.0));
+        assert::close(model.predictive().p(), 5.0 / 9.0, 1e-15);
+    }
+
+    #[test]
+    fn gamma_poisson() {
+        let mut model = GammaPoisson::new(2.0, 0.5);
+        model.update(&[3, 1, 4]);
+        let posterior = model.posterior();
+        assert_eq!(posterior.k(), 10.0);
+        assert::close(posterior.theta(), 0.2, 1e-15);
+        let predictive = model.predictive();
+        assert::close(predictive.mean(), posterior.mean(), 1e-14);
+        assert::close(
+            predictive.variance(),
+            posterior.mean() + posterior.variance(),
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn normal_normal() {
+        let mut model = NormalNormal::new(0.0, 2.0, 1.0);
+        model.update(&[1.0, 2.0, 3.0]);
+        let posterior = model.posterior();
+        assert::close(posterior.mu(), 6.0 / 3.25, 1e-14);
+        assert::close(posterior.sigma(), 3.25f64.recip().sqrt(), 1e-14);
+        assert::close(
+            model.predictive().sigma(),
+            (3.25f64.recip() + 1.0).sqrt(),
+            1e-14,
+        );
+
+        let mut sequential = NormalNormal::new(0.0, 2.0, 1.0);
+        for &x in &[1.0, 2.0, 3.0] {
+            sequential.update(&[x]);
+        }
+        assert::close(sequential.posterior().mu(), posterior.mu(), 1e-14);
+        assert::close(sequential.posterior().sigma(), posterior.sigma(), 1e-14);
+    }
+
+    #[test]
+    fn dirichlet_categorical() {
+        let mut model = DirichletCategorical::new(&[1.0, 1.0, 2.0]);
+        model.update(&[0, 2, 2, 1, 2]);
+        assert_eq!(model.posterior(), &[2.0, 2.0, 5.0]);
+        assert::close(
+            model.predictive().p(),
+            &[2.0 / 9.0, 2.0 / 9.0, 5.0 / 9.0],
+            1e-15,
+        );
+        let mut source = source::default(42);
+        let mut mean = [0.0; 3];
+        for _ in 0..10000 {
+            let p = model.sample_posterior(&mut source);
+            assert::close(p.iter().sum::<f64>(), 1.0, 1e-12);
+            for (mean, p) in mean.iter_mut().zip(p) {
+                *mean += p / 10000.0;
+            }
+        }
+        assert::close(&mean, model.predictive().p(), 0.01);
+    }
+}
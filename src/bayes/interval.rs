@@ -0,0 +1,98 @@
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::Inverse;
+
+/// Compute the equal-tailed credible interval with credibility `level`.
+///
+/// The interval leaves the probability of `(1 - level) / 2` in each tail. It
+/// should hold that `level > 0` and `level < 1`.
+pub fn credible_interval<D>(distribution: &D, level: f64) -> (f64, f64)
+where
+    D: Inverse<Value = f64>,
+{
+    should!(level > 0.0 && level < 1.0);
+    (
+        distribution.inverse(0.5 * (1.0 - level)),
+        distribution.inverse(0.5 * (1.0 + level)),
+    )
+}
+
+/// Compute the highest-density interval with credibility `level`.
+///
+/// The interval is the shortest one containing the probability of `level`,
+/// which is found by golden-section search over the probability left in the
+/// lower tail. For a unimodal distribution, it is the interval whose points
+/// have a higher density than those outside. It should hold that
+/// `level > 0` and `level < 1`.
+pub fn hpd_interval<D>(distribution: &D, level: f64) -> (f64, f64)
+where
+    D: Inverse<Value = f64>,
+{
+    const ITERATIONS: usize = 200;
+    const TOLERANCE: f64 = 1e-12;
+
+    should!(level > 0.0 && level < 1.0);
+    let ratio = 0.5 * (5f64.sqrt() - 1.0);
+    let width = |p: f64| distribution.inverse(p + level) - distribution.inverse(p);
+    let (mut a, mut b) = (0.0, 1.0 - level);
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut width_c, mut width_d) = (width(c), width(d));
+    for _ in 0..ITERATIONS {
+        if b - a <= TOLERANCE {
+            break;
+        }
+        if width_c < width_d {
+            b = d;
+            d = c;
+            width_d = width_c;
+            c = b - ratio * (b - a);
+            width_c = width(c);
+        } else {
+            a = c;
+            c = d;
+            width_c = width_d;
+            d = a + ratio * (b - a);
+            width_d = width(d);
+        }
+    }
+    let p = 0.5 * (a + b);
+    (distribution.inverse(p), distribution.inverse(p + level))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::{credible_interval, hpd_interval};
+
+    #[test]
+    fn credible() {
+        let d = Gaussian::new(1.0, 2.0);
+        let (lower, upper) = credible_interval(&d, 0.95);
+        assert::close(lower, 1.0 - 2.0 * 1.959963984540054, 1e-8);
+        assert::close(upper, 1.0 + 2.0 * 1.959963984540054, 1e-8);
+    }
+
+    #[test]
+    fn hpd() {
+        let d = Gaussian::new(1.0, 2.0);
+        let (lower, upper) = hpd_interval(&d, 0.95);
+        assert::close(lower, 1.0 - 2.0 * 1.959963984540054, 1e-5);
+        assert::close(upper, 1.0 + 2.0 * 1.959963984540054, 1e-5);
+
+        let d = Exponential::new(2.0);
+        let (lower, upper) = hpd_interval(&d, 0.9);
+        assert::close(lower, 0.0, 1e-6);
+        assert::close(upper, d.inverse(0.9), 1e-6);
+
+        let d = Gamma::new(3.0, 1.0);
+        let (lower, upper) = hpd_interval(&d, 0.9);
+        assert::close(d.distribution(upper) - d.distribution(lower), 0.9, 1e-10);
+        assert::close(d.density(lower), d.density(upper), 1e-6);
+        let (one, other) = credible_interval(&d, 0.9);
+        assert!(upper - lower < other - one);
+    }
+}
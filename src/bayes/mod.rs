@@ -0,0 +1,20 @@
+//! Bayesian inference.
+//!
+//! ## Example
+//!
+//! ```
+//! use probability::bayes::BetaBernoulli;
+//! use probability::prelude::*;
+//!
+//! let mut model = BetaBernoulli::new(1.0, 1.0);
+//! model.update(&[1, 0, 1, 1]);
+//! let posterior = model.posterior();
+//! assert_eq!((posterior.alpha(), posterior.beta()), (4.0, 2.0));
+//! let p = model.predictive().p();
+//! ```
+
+mod conjugate;
+mod interval;
+
+pub use self::conjugate::{BetaBernoulli, DirichletCategorical, GammaPoisson, NormalNormal};
+pub use self::interval::{credible_interval, hpd_interval};
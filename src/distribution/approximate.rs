@@ -0,0 +1,92 @@
+//! Approximate equality of distributions.
+//!
+//! Two distributions are approximately equal when their parameters are. The
+//! parameters that are integers, such as the number of trials, are compared
+//! exactly.
+
+use approx::{AbsDiffEq, RelativeEq};
+
+use distribution::*;
+
+macro_rules! implement(
+    ($($kind:ident => [$($exact:ident),*] [$($field:ident),+],)*) => ($(
+        impl AbsDiffEq for $kind {
+            type Epsilon = f64;
+
+            #[inline]
+            fn default_epsilon() -> f64 {
+                f64::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+                $(self.$exact() == other.$exact() &&)*
+                $(self.$field().abs_diff_eq(&other.$field(), epsilon))&&+
+            }
+        }
+
+        impl RelativeEq for $kind {
+            #[inline]
+            fn default_max_relative() -> f64 {
+                f64::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+                $(self.$exact() == other.$exact() &&)*
+                $(self.$field().relative_eq(&other.$field(), epsilon, max_relative))&&+
+            }
+        }
+    )*);
+);
+
+implement!(
+    Bernoulli => [] [p],
+    Beta => [] [alpha, beta, a, b],
+    Binomial => [n] [p],
+    Categorical => [] [p],
+    Cauchy => [] [x_0, gamma],
+    Exponential => [] [lambda],
+    Gamma => [] [k, theta],
+    Gaussian => [] [mu, sigma],
+    Laplace => [] [mu, b],
+    Logistic => [] [mu, s],
+    Lognormal => [] [mu, sigma],
+    Metalog => [] [a],
+    NegativeBinomial => [] [r, p],
+    Pert => [] [a, b, c],
+    Poisson => [] [lambda],
+    Triangular => [] [a, b, c],
+    Uniform => [] [a, b],
+);
+
+#[cfg(test)]
+mod tests {
+    use approx::{AbsDiffEq, RelativeEq};
+    use prelude::*;
+
+    #[test]
+    fn abs_diff_eq() {
+        let one = Gaussian::new(1.0, 2.0);
+        let other = Gaussian::new(1.0 + 1e-12, 2.0);
+        assert!(one != other);
+        assert!(one.abs_diff_eq(&other, 1e-10));
+        assert!(!one.abs_diff_eq(&other, 1e-14));
+
+        let one = Binomial::new(10, 0.5);
+        assert!(one.abs_diff_eq(&Binomial::new(10, 0.5 + 1e-12), 1e-10));
+        assert!(!one.abs_diff_eq(&Binomial::new(11, 0.5), 1e-10));
+
+        let one = Categorical::new(&[0.25, 0.75]);
+        assert!(one.abs_diff_eq(&Categorical::new(&[0.25 + 1e-12, 0.75 - 1e-12]), 1e-10));
+        assert!(!one.abs_diff_eq(&Categorical::new(&[0.25, 0.25, 0.5]), 1e-10));
+    }
+
+    #[test]
+    fn relative_eq() {
+        let one = Gamma::new(1e6, 2.0);
+        let other = Gamma::new(1e6 + 1e-3, 2.0);
+        assert!(one.relative_eq(&other, 0.0, 1e-8));
+        assert!(!one.relative_eq(&other, 0.0, 1e-10));
+    }
+}
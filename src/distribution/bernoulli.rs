@@ -1,12 +1,15 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// A Bernoulli distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Bernoulli {
     p: f64,
     q: f64,
@@ -18,7 +21,7 @@ impl Bernoulli {
     ///
     /// It should hold that `p > 0` and `p < 1`.
     #[inline]
-    pub fn new(p: f64) -> Self {
+    pub const fn new(p: f64) -> Self {
         should!(p > 0.0 && p < 1.0);
         Bernoulli {
             p,
@@ -27,12 +30,21 @@ impl Bernoulli {
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(p: f64) -> Result<Self, Error> {
+        error::probability("p", p)?;
+        Ok(Bernoulli::new(p))
+    }
+
     /// Create a Bernoulli distribution with failure probability `q`.
     ///
     /// It should hold that `q > 0` and `q < 1`. This constructor is preferable
     /// when `q` is very small.
     #[inline]
-    pub fn with_failure(q: f64) -> Self {
+    pub const fn with_failure(q: f64) -> Self {
         should!(q > 0.0 && q < 1.0);
         Bernoulli {
             p: 1.0 - q,
@@ -54,6 +66,13 @@ impl Bernoulli {
     }
 }
 
+impl distribution::CharacteristicFunction for Bernoulli {
+    #[inline]
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        (self.q + self.p * libm::cos(t), self.p * libm::sin(t))
+    }
+}
+
 impl distribution::Discrete for Bernoulli {
     #[inline]
     fn mass(&self, x: u8) -> f64 {
@@ -80,11 +99,16 @@ impl distribution::Distribution for Bernoulli {
             1.0
         }
     }
+
+    #[inline]
+    fn probability(&self, interval: core::ops::Range<f64>) -> f64 {
+        distribution::integer_probability(self, interval)
+    }
 }
 
 impl distribution::Entropy for Bernoulli {
     fn entropy(&self) -> f64 {
-        -self.q * self.q.ln() - self.p * self.p.ln()
+        -self.q * math::ln(self.q) - self.p * math::ln(self.p)
     }
 }
 
@@ -100,6 +124,17 @@ impl distribution::Inverse for Bernoulli {
     }
 }
 
+impl distribution::InverseBracket for Bernoulli {
+    #[inline]
+    fn inverse_bracket(&self, p: f64) -> (u8, f64, f64) {
+        use distribution::Inverse;
+        match self.inverse(p) {
+            0 => (0, 0.0, self.q),
+            _ => (1, self.q, 1.0),
+        }
+    }
+}
+
 impl distribution::Kurtosis for Bernoulli {
     #[inline]
     fn kurtosis(&self) -> f64 {
@@ -126,18 +161,49 @@ impl distribution::Median for Bernoulli {
     }
 }
 
-impl distribution::Modes for Bernoulli {
-    fn modes(&self) -> Vec<u8> {
+impl distribution::Mgf for Bernoulli {
+    #[inline]
+    fn mgf(&self, t: f64) -> f64 {
+        self.q + self.p * math::exp(t)
+    }
+
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        math::ln(self.q + self.p * math::exp(t))
+    }
+}
+
+impl distribution::ModesIter for Bernoulli {
+    type Iter<'l> = distribution::ModePair<u8>;
+
+    fn modes_iter(&self) -> Self::Iter<'_> {
         use core::cmp::Ordering::*;
+        use distribution::ModePair;
         match self.p.partial_cmp(&self.q) {
-            Some(Less) => vec![0],
-            Some(Equal) => vec![0, 1],
-            Some(Greater) => vec![1],
+            Some(Less) => ModePair::one(0),
+            Some(Equal) => ModePair::two(0, 1),
+            Some(Greater) => ModePair::one(1),
             None => unreachable!(),
         }
     }
 }
 
+impl distribution::Pgf for Bernoulli {
+    #[inline]
+    fn pgf(&self, z: f64) -> f64 {
+        self.q + self.p * z
+    }
+
+    #[inline]
+    fn factorial_moment(&self, k: usize) -> f64 {
+        match k {
+            0 => 1.0,
+            1 => self.p,
+            _ => 0.0,
+        }
+    }
+}
+
 impl distribution::Sample for Bernoulli {
     #[inline]
     fn sample<S>(&self, source: &mut S) -> u8
@@ -159,6 +225,13 @@ impl distribution::Skewness for Bernoulli {
     }
 }
 
+impl distribution::Support for Bernoulli {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
 impl distribution::Variance for Bernoulli {
     #[inline]
     fn variance(&self) -> f64 {
@@ -166,10 +239,32 @@ impl distribution::Variance for Bernoulli {
     }
 }
 
+impl estimate::MaximumLikelihood for Bernoulli {
+    fn maximum_likelihood(data: &[u8]) -> Option<Estimate<Self>> {
+        if data.iter().any(|&x| x > 1) {
+            return None;
+        }
+        let n = data.len() as f64;
+        let successes = data.iter().filter(|&&x| x == 1).count() as f64;
+        if successes == 0.0 || successes == n {
+            return None;
+        }
+        let p = successes / n;
+        let q = (n - successes) / n;
+        Some(Estimate {
+            distribution: Bernoulli::new(p),
+            parameters: vec![p],
+            errors: vec![(p * q / n).sqrt()],
+            report: Report::closed(n * (p * math::ln(p) + q * math::ln(q))),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
@@ -177,6 +272,15 @@ mod tests {
         ($p:expr) => (Bernoulli::new($p));
     );
 
+    #[test]
+    fn characteristic() {
+        use core::f64::consts::PI;
+        let d = new!(0.25);
+        assert_eq!(d.characteristic(0.0), (1.0, 0.0));
+        assert::close(d.characteristic(PI).0, 0.5, 1e-15);
+        assert::close(d.characteristic(PI / 2.0).1, 0.25, 1e-15);
+    }
+
     #[test]
     fn distribution() {
         let d = new!(0.25);
@@ -198,6 +302,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entropy_bits() {
+        let d = new!(0.5);
+        assert_eq!(d.entropy_bits(), 1.0);
+        assert::close(d.entropy_base(4.0), 0.5, 1e-15);
+    }
+
+    #[test]
+    fn interval_mass() {
+        let d = new!(0.25);
+        assert_eq!(d.interval_mass(0..1), 0.75);
+        assert_eq!(d.interval_mass(0..2), 1.0);
+        assert_eq!(d.interval_mass(1..1), 0.0);
+    }
+
     #[test]
     fn inverse() {
         let d = new!(0.25);
@@ -206,6 +325,14 @@ mod tests {
         assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
     }
 
+    #[test]
+    fn inverse_bracket() {
+        let d = new!(0.25);
+        assert_eq!(d.inverse_bracket(0.0), (0, 0.0, 0.75));
+        assert_eq!(d.inverse_bracket(0.75), (0, 0.0, 0.75));
+        assert_eq!(d.inverse_bracket(0.8), (1, 0.75, 1.0));
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.5).kurtosis(), -2.0);
@@ -220,6 +347,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn maximum_likelihood() {
+        let estimate = estimate::maximum_likelihood::<Bernoulli>(&[1, 0, 0, 1, 1]).unwrap();
+        assert::close(&estimate.parameters, &[0.6], 1e-15);
+        assert::close(&estimate.errors, &[(0.24f64 / 5.0).sqrt()], 1e-15);
+        assert::close(
+            estimate.report.log_likelihood,
+            3.0 * 0.6f64.ln() + 2.0 * 0.4f64.ln(),
+            1e-14,
+        );
+        assert!(estimate::maximum_likelihood::<Bernoulli>(&[1, 1]).is_none());
+        assert!(estimate::maximum_likelihood::<Bernoulli>(&[0, 2]).is_none());
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.5).mean(), 0.5);
@@ -232,6 +373,15 @@ mod tests {
         assert_eq!(new!(0.75).median(), 1.0);
     }
 
+    #[test]
+    fn mgf() {
+        use core::f64::consts::LN_2;
+        let d = new!(0.25);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert_eq!(d.mgf(LN_2), 1.25);
+        assert::close(d.log_mgf(LN_2), 1.25f64.ln(), 1e-15);
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(0.25).modes(), vec![0]);
@@ -239,6 +389,44 @@ mod tests {
         assert_eq!(new!(0.75).modes(), vec![1]);
     }
 
+    #[test]
+    fn outcomes() {
+        let d = new!(0.25);
+        assert_eq!(
+            d.outcomes(0.0).collect::<Vec<_>>(),
+            vec![(0, 0.75), (1, 0.25)]
+        );
+    }
+
+    #[test]
+    fn pgf() {
+        let d = new!(0.25);
+        assert_eq!(d.pgf(0.0), 0.75);
+        assert_eq!(d.pgf(1.0), 1.0);
+        assert_eq!(d.pgf(2.0), 1.25);
+        assert_eq!(
+            (0..4).map(|k| d.factorial_moment(k)).collect::<Vec<_>>(),
+            vec![1.0, 0.25, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn probability() {
+        let d = new!(0.25);
+        assert_eq!(d.probability(-1.0..0.0), 0.0);
+        assert_eq!(d.probability(0.0..1.0), 0.75);
+        assert_eq!(d.probability(1.0..2.0), 0.25);
+        assert_eq!(d.probability(-1.0..2.0), 1.0);
+        assert_eq!(d.probability(0.5..1.0), 0.0);
+        assert_eq!(d.probability(0.5..0.5), 0.0);
+        for (start, end) in [(0, 1), (0, 2), (1, 2)] {
+            assert_eq!(
+                d.probability(start as f64..end as f64),
+                d.interval_mass(start..end)
+            );
+        }
+    }
+
     #[test]
     fn sample() {
         assert!(
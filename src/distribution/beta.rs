@@ -1,12 +1,14 @@
-use alloc::{vec, vec::Vec};
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate;
+use math;
 use source::Source;
 
 /// A beta distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Beta {
     alpha: f64,
     beta: f64,
@@ -33,6 +35,19 @@ impl Beta {
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(alpha: f64, beta: f64, a: f64, b: f64) -> Result<Self, Error> {
+        error::positive("alpha", alpha)?;
+        error::positive("beta", beta)?;
+        error::finite("a", a)?;
+        error::finite("b", b)?;
+        error::require("a < b", a < b)?;
+        Ok(Beta::new(alpha, beta, a, b))
+    }
+
     /// Return the first shape parameter.
     #[inline(always)]
     pub fn alpha(&self) -> f64 {
@@ -65,8 +80,10 @@ impl distribution::Continuous for Beta {
         } else {
             let scale = self.b - self.a;
             let x = (x - self.a) / scale;
-            ((self.alpha - 1.0) * x.ln() + (self.beta - 1.0) * (-x).ln_1p() - self.ln_beta).exp()
-                / scale
+            math::exp(
+                (self.alpha - 1.0) * math::ln(x) + (self.beta - 1.0) * math::ln_1p(-x)
+                    - self.ln_beta,
+            ) / scale
         }
     }
 }
@@ -84,13 +101,24 @@ impl distribution::Distribution for Beta {
             ((x - self.a) / (self.b - self.a)).inc_beta(self.alpha, self.beta, self.ln_beta)
         }
     }
+
+    fn log_distribution(&self, x: f64) -> f64 {
+        if x <= self.a {
+            f64::NEG_INFINITY
+        } else if x >= self.b {
+            0.0
+        } else {
+            let x = (x - self.a) / (self.b - self.a);
+            ln_inc_beta(x, self.alpha, self.beta, self.ln_beta)
+        }
+    }
 }
 
 impl distribution::Entropy for Beta {
     fn entropy(&self) -> f64 {
         use special::Gamma;
         let sum = self.alpha + self.beta;
-        (self.b - self.a).ln() + self.ln_beta
+        math::ln(self.b - self.a) + self.ln_beta
             - (self.alpha - 1.0) * self.alpha.digamma()
             - (self.beta - 1.0) * self.beta.digamma()
             + (sum - 2.0) * sum.digamma()
@@ -98,11 +126,30 @@ impl distribution::Entropy for Beta {
 }
 
 impl distribution::Inverse for Beta {
-    #[inline]
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// Far in the lower tail, Newton’s method is applied to the logarithm of
+    /// the function started from the leading term of its series expansion,
+    /// which retains the relative accuracy down to the smallest positive `f64`.
+    /// No extended-precision arithmetic is involved, and the upper tail is
+    /// limited by the resolution of `p` near one.
     fn inverse(&self, p: f64) -> f64 {
+        use distribution;
         use special::Beta;
         should!((0.0..=1.0).contains(&p));
-        self.a + (self.b - self.a) * p.inv_inc_beta(self.alpha, self.beta, self.ln_beta)
+        let (alpha, beta, ln_beta) = (self.alpha, self.beta, self.ln_beta);
+        let x = if p > 0.0 && p < TAIL {
+            let ln_p = math::ln(p);
+            let x = math::exp((ln_p + math::ln(alpha) + ln_beta) / alpha);
+            distribution::invert_lower_tail(ln_p, x, alpha / (alpha + beta), |x| {
+                let ln_distribution = ln_inc_beta(x, alpha, beta, ln_beta);
+                let ln_density = alpha * math::ln(x) + (beta - 1.0) * math::ln_1p(-x) - ln_beta;
+                (ln_distribution, ln_density)
+            })
+        } else {
+            p.inv_inc_beta(alpha, beta, ln_beta)
+        };
+        self.a + (self.b - self.a) * x
     }
 }
 
@@ -138,36 +185,58 @@ impl distribution::Median for Beta {
     }
 }
 
-impl distribution::Modes for Beta {
-    fn modes(&self) -> Vec<f64> {
+impl distribution::ModesIter for Beta {
+    type Iter<'l> = distribution::ModePair<f64>;
+
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        use distribution::ModePair;
         if self.alpha == 1.0 && self.beta == 1.0 {
-            vec![]
+            ModePair::none()
         } else if self.alpha == 1.0 && self.beta > 1.0 {
-            vec![self.a]
+            ModePair::one(self.a)
         } else if self.alpha > 1.0 && self.beta == 1.0 {
-            vec![self.b]
+            ModePair::one(self.b)
         } else if self.alpha < 1.0 && self.beta < 1.0 {
-            vec![self.a, self.b]
+            ModePair::two(self.a, self.b)
         } else if self.alpha < 1.0 && self.beta >= 1.0 {
-            vec![self.a]
+            ModePair::one(self.a)
         } else if self.alpha >= 1.0 && self.beta < 1.0 {
-            vec![self.b]
+            ModePair::one(self.b)
         } else {
-            vec![self.a + (self.b - self.a) * (self.alpha - 1.0) / (self.alpha + self.beta - 2.0)]
+            ModePair::one(
+                self.a + (self.b - self.a) * (self.alpha - 1.0) / (self.alpha + self.beta - 2.0),
+            )
         }
     }
 }
 
 impl distribution::Sample for Beta {
-    #[inline]
+    /// Draw a sample.
+    ///
+    /// If both shape parameters are greater than one, algorithm BB of Cheng
+    /// is used; if only one of them is, algorithm BC is used. Otherwise, the
+    /// sample is obtained as a ratio of two gamma variates.
+    ///
+    /// ## References
+    ///
+    /// 1. R. C. H. Cheng, “Generating beta variates with nonintegral shape
+    ///    parameters,” Communications of the ACM, vol. 21, no. 4, pp. 317–322,
+    ///    1978.
     fn sample<S>(&self, source: &mut S) -> f64
     where
         S: Source,
     {
         use distribution::gamma;
-        let x = gamma::sample(self.alpha, source);
-        let y = gamma::sample(self.beta, source);
-        self.a + (self.b - self.a) * x / (x + y)
+        let x = if self.alpha.min(self.beta) > 1.0 {
+            sample_bb(self.alpha, self.beta, source)
+        } else if self.alpha.max(self.beta) > 1.0 {
+            sample_bc(self.alpha, self.beta, source)
+        } else {
+            let x = gamma::sample(self.alpha, source);
+            let y = gamma::sample(self.beta, source);
+            x / (x + y)
+        };
+        self.a + (self.b - self.a) * x
     }
 }
 
@@ -179,6 +248,37 @@ impl distribution::Skewness for Beta {
     }
 }
 
+impl distribution::Support for Beta {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+}
+
+impl distribution::Survival for Beta {
+    fn survival(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x <= self.a {
+            1.0
+        } else if x >= self.b {
+            0.0
+        } else {
+            ((self.b - x) / (self.b - self.a)).inc_beta(self.beta, self.alpha, self.ln_beta)
+        }
+    }
+
+    fn log_survival(&self, x: f64) -> f64 {
+        if x <= self.a {
+            0.0
+        } else if x >= self.b {
+            f64::NEG_INFINITY
+        } else {
+            let x = (self.b - x) / (self.b - self.a);
+            ln_inc_beta(x, self.beta, self.alpha, self.ln_beta)
+        }
+    }
+}
+
 impl distribution::Variance for Beta {
     fn variance(&self) -> f64 {
         let scale = self.b - self.a;
@@ -187,11 +287,140 @@ impl distribution::Variance for Beta {
     }
 }
 
+impl estimate::MethodOfMoments for Beta {
+    /// Estimate the parameters from a sample by matching the sample moments.
+    ///
+    /// The sample is assumed to come from the unit interval.
+    fn moments(data: &[f64]) -> Option<Self> {
+        if data.len() < 2 || data.iter().any(|&x| !(0.0..=1.0).contains(&x)) {
+            return None;
+        }
+        let mean = estimate::mean(data);
+        let variance = estimate::variance(data, mean);
+        let common = mean * (1.0 - mean) / variance - 1.0;
+        if variance == 0.0 || common <= 0.0 {
+            return None;
+        }
+        Some(Beta::new(mean * common, (1.0 - mean) * common, 0.0, 1.0))
+    }
+}
+
+/// The probability below which quantiles are computed in the logarithmic
+/// domain.
+const TAIL: f64 = 1e-10;
+
+/// Compute the natural logarithm of the regularized incomplete beta function.
+///
+/// Below the mean, the series expansion is evaluated with the prefactor kept
+/// in the logarithmic domain, which avoids the underflow in the lower tail.
+/// Otherwise, the logarithm of the complement is taken.
+///
+/// ## References
+///
+/// 1. M. Abramowitz and I. A. Stegun, Handbook of Mathematical Functions,
+///    formula 26.5.4, 1964.
+fn ln_inc_beta(x: f64, p: f64, q: f64, ln_beta: f64) -> f64 {
+    use special::Beta;
+
+    if x >= (p + 1.0) / (p + q + 2.0) {
+        return math::ln_1p(-(1.0 - x).inc_beta(q, p, ln_beta));
+    }
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut n = 0.0;
+    while term > f64::EPSILON * sum {
+        term *= x * (p + q + n) / (p + 1.0 + n);
+        sum += term;
+        n += 1.0;
+    }
+    p * math::ln(x) + q * math::ln_1p(-x) - math::ln(p) - ln_beta + math::ln(sum)
+}
+
+/// Draw a sample from the standard beta distribution via algorithm BB of
+/// Cheng, which requires both shape parameters to be greater than one.
+fn sample_bb<S: Source>(alpha: f64, beta: f64, source: &mut S) -> f64 {
+    use core::f64::consts::LN_2;
+    const LN_5_P_1: f64 = 2.6094379124341003;
+
+    let (a, b) = (alpha.min(beta), alpha.max(beta));
+    let sum = a + b;
+    let scale = ((sum - 2.0) / (2.0 * a * b - sum)).sqrt();
+    let shift = a + 1.0 / scale;
+    loop {
+        let u1 = source.read::<f64>();
+        let u2 = source.read::<f64>();
+        let v = scale * math::ln(u1 / (1.0 - u1));
+        let w = a * math::exp(v);
+        let z = u1 * u1 * u2;
+        let r = shift * v - 2.0 * LN_2;
+        let s = a + r - w;
+        if s + LN_5_P_1 >= 5.0 * z
+            || s >= math::ln(z)
+            || r + sum * math::ln(sum / (b + w)) >= math::ln(z)
+        {
+            return finish(alpha == a, b, w);
+        }
+    }
+}
+
+/// Draw a sample from the standard beta distribution via algorithm BC of
+/// Cheng, which requires one of the shape parameters to be greater than one.
+fn sample_bc<S: Source>(alpha: f64, beta: f64, source: &mut S) -> f64 {
+    use core::f64::consts::LN_2;
+
+    let (a, b) = (alpha.max(beta), alpha.min(beta));
+    let sum = a + b;
+    let scale = 1.0 / b;
+    let delta = 1.0 + a - b;
+    let k1 = delta * (1.0 / 72.0 + b / 24.0) / (a * scale - 7.0 / 9.0);
+    let k2 = 0.25 + (0.5 + 0.25 / delta) * b;
+    loop {
+        let u1 = source.read::<f64>();
+        let u2 = source.read::<f64>();
+        let z = if u1 < 0.5 {
+            let y = u1 * u2;
+            let z = u1 * y;
+            if 0.25 * u2 + z - y >= k1 {
+                continue;
+            }
+            z
+        } else {
+            let z = u1 * u1 * u2;
+            if z <= 0.25 {
+                let w = a * math::exp(scale * math::ln(u1 / (1.0 - u1)));
+                return finish(alpha == a, b, w);
+            }
+            if z >= k2 {
+                continue;
+            }
+            z
+        };
+        let v = scale * math::ln(u1 / (1.0 - u1));
+        let w = a * math::exp(v);
+        if sum * (math::ln(sum / (b + w)) + v) - 2.0 * LN_2 >= math::ln(z) {
+            return finish(alpha == a, b, w);
+        }
+    }
+}
+
+/// Compute the variate of Cheng’s algorithms given the accepted `w`.
+#[inline]
+fn finish(first: bool, b: f64, w: f64) -> f64 {
+    let x = if w.is_infinite() { 1.0 } else { w / (b + w) };
+    if first {
+        x
+    } else {
+        1.0 - x
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
+    use test;
 
     macro_rules! new(
         ($alpha:expr, $beta:expr, $a:expr, $b:expr) => (Beta::new($alpha, $beta, $a, $b));
@@ -328,6 +557,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_tail() {
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.inverse(1e-20) / 4.082482904749741e-11, 1.0, 1e-13);
+        assert::close(d.inverse(1e-100) / 4.08248290463863e-51, 1.0, 1e-13);
+        assert::close(d.log_distribution(d.inverse(1e-300)), 1e-300f64.ln(), 1e-10);
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        assert::close(d.inverse(1e-100), -1.0 + 3.0 * 4.08248290463863e-51, 1e-15);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).kurtosis(), -6.0 / 5.0);
@@ -335,6 +574,24 @@ mod tests {
         assert_eq!(new!(3.0, 2.0, -1.0, 2.0).kurtosis(), -0.6428571428571429);
     }
 
+    #[test]
+    fn log_distribution() {
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.log_distribution(1e-60), -274.5184516900574, 1e-10);
+        assert::close(d.log_distribution(0.1), -2.95075890791126, 1e-14);
+        assert::close(d.log_distribution(0.9), d.distribution(0.9).ln(), 1e-14);
+        assert_eq!(d.log_distribution(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_survival() {
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        let x = 1.0 - 2f64.powi(-50);
+        assert::close(d.log_survival(x), -102.58578272287191, 1e-10);
+        assert::close(d.log_survival(0.5), d.survival(0.5).ln(), 1e-14);
+        assert_eq!(d.log_survival(1.0), f64::NEG_INFINITY);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.5, 0.5, 0.0, 1.0).mean(), 0.5);
@@ -377,6 +634,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn moments() {
+        let d = estimate::moments::<Beta>(&[0.2, 0.4, 0.6, 0.8]).unwrap();
+        assert::close(d.alpha(), 2.0, 1e-14);
+        assert::close(d.beta(), 2.0, 1e-14);
+        assert!(estimate::moments::<Beta>(&[0.0, 1.0]).is_none());
+    }
+
     #[test]
     fn sample() {
         for x in Independent(&new!(1.0, 2.0, 7.0, 42.0), &mut source::default(42)).take(100) {
@@ -384,6 +649,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sample_algorithms() {
+        let mut source = source::default(42);
+        for &(alpha, beta) in &[(2.0, 5.0), (7.5, 1.5), (0.5, 3.0), (4.0, 0.2), (0.3, 0.4)] {
+            let d = new!(alpha, beta, -1.0, 2.0);
+            let data = Independent(&d, &mut source).take(20000).collect::<Vec<_>>();
+            assert!(data.iter().all(|&x| (-1.0..=2.0).contains(&x)));
+            let mean = data.iter().sum::<f64>() / data.len() as f64;
+            let variance =
+                data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64;
+            assert::close(mean, d.mean(), 0.02);
+            assert::close(variance, d.variance(), 0.02);
+            assert!(test::ks_one_sample(&data, &d).p > 1e-3);
+        }
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).skewness(), 0.0);
@@ -391,6 +672,28 @@ mod tests {
         assert_eq!(new!(3.0, 2.0, -1.0, 2.0).skewness(), -0.28571428571428575);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let x = vec![-1.0, 0.0, 1.0, 1.9, 1.999];
+        let p = vec![
+            1.000000000000000e+00,
+            5.925925925925926e-01,
+            1.111111111111111e-01,
+            1.444444444444448e-04,
+            1.481111111110622e-10,
+        ];
+
+        assert::close(
+            &x.iter()
+                .zip(&p)
+                .map(|(&x, &p)| d.survival(x) / p)
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-12,
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).variance(), 1.0 / 12.0);
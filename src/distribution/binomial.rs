@@ -1,27 +1,36 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate;
+use math;
 use source::Source;
 
 /// A binomial distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Binomial {
-    n: usize,
+    n: u64,
     p: f64,
     q: f64,
     np: f64,
     nq: f64,
     npq: f64,
+    sampler: Sampler,
 }
 
 impl Binomial {
-    /// Create a binomial distribution with `n` trails and success probability
+    /// Create a binomial distribution with `n` trials and success probability
     /// `p`.
     ///
+    /// The number of trials and the outcomes are represented by `u64`, which
+    /// retains large counts on 32-bit targets; the probabilities involving
+    /// powers of `p` and `q` are computed in the logarithmic domain.
+    ///
     /// It should hold that `p >= 0` and `p <= 1`.
-    pub fn new(n: usize, p: f64) -> Self {
+    pub fn new(n: u64, p: f64) -> Self {
         should!(0.0 < p && p < 1.0);
         let q = 1.0 - p;
         let np = n as f64 * p;
@@ -33,15 +42,25 @@ impl Binomial {
             np,
             nq,
             npq: np * q,
+            sampler: Sampler::new(n, p.min(q)),
         }
     }
 
-    /// Create a binomial distribution with `n` trails and failure probability
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(n: u64, p: f64) -> Result<Self, Error> {
+        error::probability("p", p)?;
+        Ok(Binomial::new(n, p))
+    }
+
+    /// Create a binomial distribution with `n` trials and failure probability
     /// `q`.
     ///
     /// It should hold that if `q >= 0` or `q <= 1`. This constructor is
     /// preferable when `q` is very small.
-    pub fn with_failure(n: usize, q: f64) -> Self {
+    pub fn with_failure(n: u64, q: f64) -> Self {
         should!(0.0 < q && q < 1.0);
         let p = 1.0 - q;
         let np = n as f64 * p;
@@ -53,12 +72,13 @@ impl Binomial {
             np,
             nq,
             npq: np * q,
+            sampler: Sampler::new(n, p.min(q)),
         }
     }
 
     /// Return the number of trials.
     #[inline(always)]
-    pub fn n(&self) -> usize {
+    pub fn n(&self) -> u64 {
         self.n
     }
 
@@ -73,33 +93,57 @@ impl Binomial {
     pub fn q(&self) -> f64 {
         self.q
     }
-}
 
-impl distribution::Discrete for Binomial {
-    /// Compute the probability mass function.
+    /// Compute `ln(p)` using whichever of `p` and `q` is given more
+    /// accurately.
+    #[inline]
+    fn ln_p(&self) -> f64 {
+        if self.q < self.p {
+            math::ln_1p(-self.q)
+        } else {
+            math::ln(self.p)
+        }
+    }
+
+    /// Compute `ln(q)` using whichever of `p` and `q` is given more
+    /// accurately.
+    #[inline]
+    fn ln_q(&self) -> f64 {
+        if self.p < self.q {
+            math::ln_1p(-self.p)
+        } else {
+            math::ln(self.q)
+        }
+    }
+
+    /// Compute the natural logarithm of the probability mass function.
     ///
-    /// For large `n`, a saddle-point expansion is used for more accurate
-    /// computation.
+    /// The saddle-point expansion used for `mass` is evaluated without leaving
+    /// the logarithmic domain, which retains the accuracy for large `n` where
+    /// the mass itself underflows.
     ///
     /// ## References
     ///
     /// 1. C. Loader, “Fast and Accurate Computation of Binomial Probabilities,”
     ///    2000.
-    fn mass(&self, x: usize) -> f64 {
+    pub fn log_mass(&self, x: u64) -> f64 {
         use core::f64::consts::PI;
 
+        if x > self.n {
+            return f64::NEG_INFINITY;
+        }
         if self.p == 0.0 {
-            return if x == 0 { 1.0 } else { 0.0 };
+            return if x == 0 { 0.0 } else { f64::NEG_INFINITY };
         }
         if self.p == 1.0 {
-            return if x == self.n { 1.0 } else { 0.0 };
+            return if x == self.n { 0.0 } else { f64::NEG_INFINITY };
         }
 
         let n = self.n as f64;
         if x == 0 {
-            (n * self.q.ln()).exp()
+            n * self.ln_q()
         } else if x == self.n {
-            (n * self.p.ln()).exp()
+            n * self.ln_p()
         } else {
             let x = x as f64;
             let n_m_x = n - x;
@@ -108,13 +152,58 @@ impl distribution::Discrete for Binomial {
                 - stirlerr(n_m_x)
                 - ln_d0(x, self.np)
                 - ln_d0(n_m_x, self.nq);
-            ln_c.exp() * (n / (2.0 * PI * x * (n_m_x))).sqrt()
+            ln_c + 0.5 * math::ln(n / (2.0 * PI * x * n_m_x))
+        }
+    }
+
+    /// Iterate over the probability mass function at `0, 1, …, n`.
+    ///
+    /// The masses are computed in a single pass via the recurrence
+    /// `f(x + 1) = f(x) (n - x) p / ((x + 1) q)`. The recurrence is restarted
+    /// from `log_mass` periodically and whenever the running value leaves a
+    /// safe range, which bounds the accumulation of rounding errors and
+    /// prevents underflow.
+    #[inline]
+    pub fn mass_iter(&self) -> BinomialMasses {
+        BinomialMasses {
+            distribution: *self,
+            x: 0,
+            mass: 0.0,
+            scale: 0.0,
+            ratio: self.p / self.q,
+            steps: 0,
         }
     }
 }
 
+impl distribution::CharacteristicFunction for Binomial {
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let (re, im) = (self.q + self.p * libm::cos(t), self.p * libm::sin(t));
+        let n = self.n as f64;
+        let modulus = math::powf(libm::hypot(re, im), n);
+        let argument = n * libm::atan2(im, re);
+        (modulus * libm::cos(argument), modulus * libm::sin(argument))
+    }
+}
+
+impl distribution::Discrete for Binomial {
+    /// Compute the probability mass function.
+    ///
+    /// For large `n`, a saddle-point expansion is used for more accurate
+    /// computation.
+    ///
+    /// ## References
+    ///
+    /// 1. C. Loader, “Fast and Accurate Computation of Binomial Probabilities,”
+    ///    2000.
+    #[inline]
+    fn mass(&self, x: u64) -> f64 {
+        math::exp(self.log_mass(x))
+    }
+}
+
 impl distribution::Distribution for Binomial {
-    type Value = usize;
+    type Value = u64;
 
     /// Compute the cumulative distribution function.
     ///
@@ -124,9 +213,9 @@ impl distribution::Distribution for Binomial {
         if x < 0.0 {
             return 0.0;
         }
-        let x = x as usize;
+        let x = x as u64;
         if x == 0 {
-            return self.q.powi(self.n as i32);
+            return math::exp(self.log_mass(0));
         }
         if x >= self.n {
             return 1.0;
@@ -134,19 +223,95 @@ impl distribution::Distribution for Binomial {
         let (p, q) = ((self.n - x) as f64, (x + 1) as f64);
         self.q.inc_beta(p, q, p.ln_beta(q))
     }
+
+    /// Compute the natural logarithm of the cumulative distribution function.
+    ///
+    /// Above the median, the logarithm is computed from the complement via
+    /// `ln_1p`, which retains the accuracy close to one. Far in the lower
+    /// tail, where the function underflows, the masses given by `log_mass`
+    /// are summed relative to the largest one, which is the last.
+    fn log_distribution(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let x = x as u64;
+        if x == 0 {
+            return self.log_mass(0);
+        }
+        if x >= self.n {
+            return 0.0;
+        }
+        let value = self.distribution(x as f64);
+        if value < 1e-280 && (x as f64) < self.np {
+            let mut term = 1.0;
+            let mut sum = 1.0;
+            for k in (1..=x).rev() {
+                term *= k as f64 * self.q / ((self.n - k + 1) as f64 * self.p);
+                sum += term;
+                if term < f64::EPSILON * sum {
+                    break;
+                }
+            }
+            return self.log_mass(x) + math::ln(sum);
+        }
+        if value < 0.5 {
+            return math::ln(value);
+        }
+        let (p, q) = ((x + 1) as f64, (self.n - x) as f64);
+        math::ln_1p(-self.p.inc_beta(p, q, p.ln_beta(q)))
+    }
+
+    #[inline]
+    fn probability(&self, interval: core::ops::Range<f64>) -> f64 {
+        distribution::integer_probability(self, interval)
+    }
 }
 
 impl distribution::Entropy for Binomial {
+    /// Compute the differential entropy.
+    ///
+    /// For large `n` and large variances, a normal asymptotic approximation is
+    /// used. Otherwise, the summation starts at the mode and proceeds outward
+    /// via the recurrence of the probability mass function until the masses
+    /// become negligible, which requires a number of terms proportional to the
+    /// standard deviation rather than to `n`.
     fn entropy(&self) -> f64 {
         use core::f64::consts::PI;
-        use distribution::Discrete;
+        use distribution::ModesIter;
+
+        const EPSILON: f64 = 1e-20;
 
         if self.n > 10000 && self.npq > 80.0 {
             // Use a normal approximation.
-            0.5 * ((2.0 * PI * self.npq).ln() + 1.0)
-        } else {
-            -(0..(self.n + 1)).fold(0.0, |sum, i| sum + self.mass(i) * self.mass(i).ln())
+            return 0.5 * (math::ln(2.0 * PI * self.npq) + 1.0);
+        }
+        let n = self.n as f64;
+        let ln_ratio = self.ln_p() - self.ln_q();
+        let mode = self.modes_iter().next().unwrap();
+        let ln_mass = self.log_mass(mode);
+        let mut sum = -math::exp(ln_mass) * ln_mass;
+        let (mut k, mut ln_mass_k) = (mode, ln_mass);
+        while k < self.n {
+            ln_mass_k += math::ln((n - k as f64) / (k + 1) as f64) + ln_ratio;
+            k += 1;
+            let mass = math::exp(ln_mass_k);
+            if mass < EPSILON {
+                break;
+            }
+            sum -= mass * ln_mass_k;
+        }
+        let (mut k, mut ln_mass_k) = (mode, ln_mass);
+        while k > 0 {
+            ln_mass_k += math::ln(k as f64 / (n - k as f64 + 1.0)) - ln_ratio;
+            k -= 1;
+            let mass = math::exp(ln_mass_k);
+            if mass < EPSILON {
+                break;
+            }
+            sum -= mass * ln_mass_k;
         }
+        sum
     }
 }
 
@@ -162,15 +327,15 @@ impl distribution::Inverse for Binomial {
     /// 1. S. Moorhead, “Efficient evaluation of the inverse binomial cumulative
     ///    distribution function where the number of trials is large,” Oxford
     ///    University, 2013.
-    fn inverse(&self, p: f64) -> usize {
-        use distribution::{Discrete, Distribution, Modes};
+    fn inverse(&self, p: f64) -> u64 {
+        use distribution::{Discrete, Distribution, ModesIter};
 
         should!((0.0..=1.0).contains(&p));
 
         macro_rules! sum_bottom_up(
             ($prod_term: expr) => ({
                 let mut k = 1;
-                let mut a = self.q.powi(self.n as i32);
+                let mut a = self.mass(0);
                 let mut sum = a - p;
                 while sum < 0.0 {
                     a *= $prod_term(k);
@@ -183,7 +348,7 @@ impl distribution::Inverse for Binomial {
         macro_rules! sum_top_down(
             ($prod_term: expr) => ({
                 let mut k = 1;
-                let mut a = self.p.powi(self.n as i32);
+                let mut a = self.mass(self.n);
                 let mut sum = (1.0 - p) - a;
                 while sum >= 0.0 {
                     a *= $prod_term(k);
@@ -207,16 +372,16 @@ impl distribution::Inverse for Binomial {
             }
         } else if self.npq > 80.0 {
             // Use a normal approximation.
-            inverse_normal(self.p, self.np, self.npq, p).floor() as usize
+            inverse_normal(self.p, self.np, self.npq, p).floor() as u64
         } else {
             // Use Newton’s method starting at the mode.
             const ALPHA: f64 = 0.999;
-            let mut q = self.modes()[0] as f64;
+            let mut q = self.modes_iter().next().unwrap() as f64;
             let mut alpha = 1.0;
             loop {
-                let delta = alpha * (p - self.distribution(q)) / self.mass(q as usize);
+                let delta = alpha * (p - self.distribution(q)) / self.mass(q as u64);
                 if delta.abs() < 0.5 {
-                    return q as usize;
+                    return q as u64;
                 }
                 q += delta;
                 alpha *= ALPHA;
@@ -225,6 +390,19 @@ impl distribution::Inverse for Binomial {
     }
 }
 
+impl distribution::InverseBracket for Binomial {
+    fn inverse_bracket(&self, p: f64) -> (u64, f64, f64) {
+        use distribution::{Distribution, Inverse};
+        let k = self.inverse(p);
+        let lower = if k == 0 {
+            0.0
+        } else {
+            self.distribution((k - 1) as f64)
+        };
+        (k, lower, self.distribution(k as f64))
+    }
+}
+
 impl distribution::Kurtosis for Binomial {
     #[inline]
     fn kurtosis(&self) -> f64 {
@@ -260,29 +438,79 @@ impl distribution::Median for Binomial {
     }
 }
 
-impl distribution::Modes for Binomial {
-    fn modes(&self) -> Vec<usize> {
+impl distribution::Mgf for Binomial {
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        self.n as f64 * math::ln(self.q + self.p * math::exp(t))
+    }
+}
+
+impl distribution::ModesIter for Binomial {
+    type Iter<'l> = distribution::ModePair<u64>;
+
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        use distribution::ModePair;
         let r = self.p * (self.n + 1) as f64;
         if r == 0.0 {
-            vec![0]
+            ModePair::one(0)
         } else if self.p == 1.0 {
-            vec![self.n]
+            ModePair::one(self.n)
         } else if (r - r.trunc()) != 0.0 {
-            vec![r.floor() as usize]
+            ModePair::one(r.floor() as u64)
         } else {
-            vec![r as usize - 1, r as usize]
+            ModePair::two(r as u64 - 1, r as u64)
         }
     }
 }
 
+impl distribution::Pgf for Binomial {
+    /// Compute the probability-generating function.
+    ///
+    /// The power is computed in the logarithmic domain via `ln_1p` unless the
+    /// base is negative.
+    fn pgf(&self, z: f64) -> f64 {
+        let x = -self.p * (1.0 - z);
+        if x >= -1.0 {
+            math::exp(self.n as f64 * math::ln_1p(x))
+        } else {
+            math::powf(self.q + self.p * z, self.n as f64)
+        }
+    }
+
+    fn factorial_moment(&self, k: usize) -> f64 {
+        let k = k as u64;
+        if k > self.n {
+            return 0.0;
+        }
+        (0..k).fold(1.0, |product, i| product * (self.n - i) as f64 * self.p)
+    }
+}
+
 impl distribution::Sample for Binomial {
-    #[inline]
-    fn sample<S>(&self, source: &mut S) -> usize
+    /// Draw a sample.
+    ///
+    /// When the expected number of the less likely outcome is small, the
+    /// sample is drawn by sequential inversion; otherwise, the BTPE algorithm
+    /// is used.
+    ///
+    /// ## References
+    ///
+    /// 1. V. Kachitvichyanukul and B. W. Schmeiser, “Binomial random variate
+    ///    generation,” Communications of the ACM, vol. 31, no. 2, pp.
+    ///    216–222, 1988.
+    fn sample<S>(&self, source: &mut S) -> u64
     where
         S: Source,
     {
-        use distribution::Inverse;
-        self.inverse(source.read::<f64>())
+        let x = match self.sampler {
+            Sampler::Btpe(ref sampler) => sampler.sample(source),
+            Sampler::Inversion(ref sampler) => sampler.sample(source),
+        };
+        if self.p > 0.5 {
+            self.n - x
+        } else {
+            x
+        }
     }
 }
 
@@ -293,6 +521,13 @@ impl distribution::Skewness for Binomial {
     }
 }
 
+impl distribution::Support for Binomial {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, self.n as f64)
+    }
+}
+
 impl distribution::Variance for Binomial {
     #[inline]
     fn variance(&self) -> f64 {
@@ -300,8 +535,302 @@ impl distribution::Variance for Binomial {
     }
 }
 
-// See [Moorhead, 2013, pp. 7].
-#[rustfmt::skip]
+impl estimate::MethodOfMoments for Binomial {
+    /// Estimate the parameters from a sample by matching the sample moments.
+    ///
+    /// The number of trials is rounded to the nearest integer, which is not
+    /// smaller than the largest observation, and the success probability is
+    /// then matched to the sample mean.
+    fn moments(data: &[u64]) -> Option<Self> {
+        if data.len() < 2 {
+            return None;
+        }
+        let data = data.iter().map(|&x| x as f64).collect::<Vec<_>>();
+        let mean = estimate::mean(&data);
+        let variance = estimate::variance(&data, mean);
+        if variance == 0.0 || variance >= mean {
+            return None;
+        }
+        let largest = data.iter().fold(0.0f64, |largest, &x| largest.max(x));
+        let n = (mean * mean / (mean - variance)).round().max(largest);
+        Some(Binomial::new(n as u64, mean / n))
+    }
+}
+
+/// An iterator over the probability mass function of a binomial distribution.
+#[derive(Clone, Copy, Debug)]
+pub struct BinomialMasses {
+    distribution: Binomial,
+    x: u64,
+    mass: f64,
+    scale: f64,
+    ratio: f64,
+    steps: usize,
+}
+
+impl Iterator for BinomialMasses {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        const PERIOD: usize = 64;
+        const RANGE: core::ops::Range<f64> = 1e-100..1e100;
+
+        let n = self.distribution.n;
+        if self.x > n {
+            return None;
+        }
+        if self.steps == 0 || !RANGE.contains(&self.mass) {
+            self.scale = math::exp(self.distribution.log_mass(self.x));
+            self.mass = 1.0;
+            self.steps = PERIOD;
+        }
+        let value = self.mass * self.scale;
+        self.mass *= (n - self.x) as f64 / (self.x + 1) as f64 * self.ratio;
+        self.x += 1;
+        self.steps -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.distribution.n;
+        let remaining = if self.x > n {
+            0
+        } else {
+            (n - self.x).saturating_add(1)
+        };
+        match usize::try_from(remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl ExactSizeIterator for BinomialMasses {}
+
+/// A sampler chosen according to the expected number of the less likely
+/// outcome.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Sampler {
+    Btpe(Btpe),
+    Inversion(Inversion),
+}
+
+impl Sampler {
+    fn new(n: u64, r: f64) -> Self {
+        if n as f64 * r < 30.0 {
+            Sampler::Inversion(Inversion::new(n, r))
+        } else {
+            Sampler::Btpe(Btpe::new(n, r))
+        }
+    }
+}
+
+/// A sampler based on the BTPE algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Btpe {
+    n: f64,
+    r: f64,
+    q: f64,
+    nrq: f64,
+    m: f64,
+    p1: f64,
+    p2: f64,
+    p3: f64,
+    p4: f64,
+    xm: f64,
+    xl: f64,
+    xr: f64,
+    c: f64,
+    lambda_l: f64,
+    lambda_r: f64,
+}
+
+impl Btpe {
+    fn new(n: u64, r: f64) -> Self {
+        let n = n as f64;
+        let q = 1.0 - r;
+        let nrq = n * r * q;
+        let fm = n * r + r;
+        let m = libm::floor(fm);
+        let p1 = libm::floor(2.195 * nrq.sqrt() - 4.6 * q) + 0.5;
+        let xm = m + 0.5;
+        let xl = xm - p1;
+        let xr = xm + p1;
+        let c = 0.134 + 20.5 / (15.3 + m);
+        let a = (fm - xl) / (fm - xl * r);
+        let lambda_l = a * (1.0 + 0.5 * a);
+        let a = (xr - fm) / (xr * q);
+        let lambda_r = a * (1.0 + 0.5 * a);
+        let p2 = p1 * (1.0 + 2.0 * c);
+        let p3 = p2 + c / lambda_l;
+        let p4 = p3 + c / lambda_r;
+        Btpe {
+            n,
+            r,
+            q,
+            nrq,
+            m,
+            p1,
+            p2,
+            p3,
+            p4,
+            xm,
+            xl,
+            xr,
+            c,
+            lambda_l,
+            lambda_r,
+        }
+    }
+
+    // See [Kachitvichyanukul and Schmeiser, 1988].
+    fn sample<S: Source>(&self, source: &mut S) -> u64 {
+        let &Btpe {
+            n,
+            r,
+            q,
+            nrq,
+            m,
+            p1,
+            p2,
+            p3,
+            p4,
+            xm,
+            xl,
+            xr,
+            c,
+            lambda_l,
+            lambda_r,
+        } = self;
+        loop {
+            let u = source.read::<f64>() * p4;
+            let mut v = source.read::<f64>();
+            let y;
+            if u <= p1 {
+                return libm::floor(xm - p1 * v + u) as u64;
+            } else if u <= p2 {
+                let x = xl + (u - p1) / c;
+                v = v * c + 1.0 - (m - x + 0.5).abs() / p1;
+                if v > 1.0 {
+                    continue;
+                }
+                y = libm::floor(x);
+            } else if u <= p3 {
+                y = libm::floor(xl + math::ln(v) / lambda_l);
+                if y < 0.0 {
+                    continue;
+                }
+                v *= (u - p2) * lambda_l;
+            } else {
+                y = libm::floor(xr - math::ln(v) / lambda_r);
+                if y > n {
+                    continue;
+                }
+                v *= (u - p3) * lambda_r;
+            }
+
+            let k = (y - m).abs();
+            if k <= 20.0 || k >= 0.5 * nrq - 1.0 {
+                let s = r / q;
+                let a = s * (n + 1.0);
+                let mut f = 1.0;
+                if m < y {
+                    let mut i = m + 1.0;
+                    while i <= y {
+                        f *= a / i - s;
+                        i += 1.0;
+                    }
+                } else if m > y {
+                    let mut i = y + 1.0;
+                    while i <= m {
+                        f /= a / i - s;
+                        i += 1.0;
+                    }
+                }
+                if v <= f {
+                    return y as u64;
+                }
+                continue;
+            }
+
+            let rho = (k / nrq) * ((k * (k / 3.0 + 0.625) + 1.0 / 6.0) / nrq + 0.5);
+            let t = -k * k / (2.0 * nrq);
+            let a = math::ln(v);
+            if a < t - rho {
+                return y as u64;
+            }
+            if a > t + rho {
+                continue;
+            }
+            let x1 = y + 1.0;
+            let f1 = m + 1.0;
+            let z = n + 1.0 - m;
+            let w = n - y + 1.0;
+            let bound = xm * math::ln(f1 / x1)
+                + (n - m + 0.5) * math::ln(z / w)
+                + (y - m) * math::ln(w * r / (x1 * q))
+                + stirling(f1)
+                + stirling(z)
+                + stirling(x1)
+                + stirling(w);
+            if a <= bound {
+                return y as u64;
+            }
+        }
+    }
+}
+
+/// A sampler based on sequential inversion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Inversion {
+    n: u64,
+    r: f64,
+    q: f64,
+    qn: f64,
+    bound: f64,
+}
+
+impl Inversion {
+    fn new(n: u64, r: f64) -> Self {
+        let q = 1.0 - r;
+        let np = n as f64 * r;
+        Inversion {
+            n,
+            r,
+            q,
+            qn: math::exp(n as f64 * math::ln_1p(-r)),
+            bound: (n as f64).min(np + 10.0 * (np * q + 1.0).sqrt()),
+        }
+    }
+
+    fn sample<S: Source>(&self, source: &mut S) -> u64 {
+        let &Inversion { n, r, q, qn, bound } = self;
+        let mut x = 0;
+        let mut px = qn;
+        let mut u = source.read::<f64>();
+        while u > px {
+            x += 1;
+            if x as f64 > bound {
+                x = 0;
+                px = qn;
+                u = source.read::<f64>();
+            } else {
+                u -= px;
+                px = ((n - x + 1) as f64 * r * px) / (x as f64 * q);
+            }
+        }
+        x
+    }
+}
+
+/// Compute the correction term of the Stirling approximation.
+#[inline]
+fn stirling(x: f64) -> f64 {
+    let x2 = x * x;
+    (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / x2) / x2) / x2) / x2) / x / 166320.0
+}
+
 fn inverse_normal(p: f64, np: f64, v: f64, u: f64) -> f64 {
     use distribution::gaussian;
 
@@ -320,34 +849,31 @@ fn inverse_normal(p: f64, np: f64, v: f64, u: f64) -> f64 {
     let p3 = p2 * p;
     let p4 = p2 * p2;
 
-    np +
-    sd * w +
-    (p + 1.0) / 3.0 -
-    (2.0 * p - 1.0) * w2 / 6.0 +
-    sd_em1 * w3 * (2.0 * p2 - 2.0 * p - 1.0) / 72.0 -
-    w * (7.0 * p2 - 7.0 * p + 1.0) / 36.0 +
-    sd_em2 * (2.0 * p - 1.0) * (p + 1.0) * (p - 2.0) * (3.0 * w4 + 7.0 * w2 - 16.0 / 1620.0) +
-    sd_em3 * (
-        w5 * (4.0 * p4 - 8.0 * p3 - 48.0 * p2 + 52.0 * p - 23.0) / 17280.0 +
-        w3 * (256.0 * p4 - 512.0 * p3 - 147.0 * p2 + 403.0 * p - 137.0) / 38880.0 -
-        w * (433.0 * p4 - 866.0 * p3 - 921.0 * p2 + 1354.0 * p - 671.0) / 38880.0
-    ) +
-    sd_em4 * (
-        w6 * (2.0 * p - 1.0) * (p2 - p + 1.0) * (p2 - p + 19.0) / 34020.0 +
-        w4 * (2.0 * p - 1.0) * (9.0 * p4 - 18.0 * p3 - 35.0 * p2 + 44.0 * p - 25.0) / 15120.0 +
-        w2 * (2.0 * p - 1.0) * (
-                923.0 * p4 - 1846.0 * p3 + 5271.0 * p2 - 4348.0 * p + 5189.0
-        ) / 408240.0 -
-        4.0 * (2.0 * p - 1.0) * (p + 1.0) * (p - 2.0) * (23.0 * p2 - 23.0 * p + 2.0) / 25515.0
-    )
+    np + sd * w + (p + 1.0) / 3.0 - (2.0 * p - 1.0) * w2 / 6.0
+        + sd_em1 * w3 * (2.0 * p2 - 2.0 * p - 1.0) / 72.0
+        - w * (7.0 * p2 - 7.0 * p + 1.0) / 36.0
+        + sd_em2 * (2.0 * p - 1.0) * (p + 1.0) * (p - 2.0) * (3.0 * w4 + 7.0 * w2 - 16.0 / 1620.0)
+        + sd_em3
+            * (w5 * (4.0 * p4 - 8.0 * p3 - 48.0 * p2 + 52.0 * p - 23.0) / 17280.0
+                + w3 * (256.0 * p4 - 512.0 * p3 - 147.0 * p2 + 403.0 * p - 137.0) / 38880.0
+                - w * (433.0 * p4 - 866.0 * p3 - 921.0 * p2 + 1354.0 * p - 671.0) / 38880.0)
+        + sd_em4
+            * (w6 * (2.0 * p - 1.0) * (p2 - p + 1.0) * (p2 - p + 19.0) / 34020.0
+                + w4 * (2.0 * p - 1.0) * (9.0 * p4 - 18.0 * p3 - 35.0 * p2 + 44.0 * p - 25.0)
+                    / 15120.0
+                + w2 * (2.0 * p - 1.0)
+                    * (923.0 * p4 - 1846.0 * p3 + 5271.0 * p2 - 4348.0 * p + 5189.0)
+                    / 408240.0
+                - 4.0 * (2.0 * p - 1.0) * (p + 1.0) * (p - 2.0) * (23.0 * p2 - 23.0 * p + 2.0)
+                    / 25515.0)
     // + O(v.powf(-2.5)), with probabilty of 1 - 2e-9
 }
 
 // ln(np * D₀) = x * ln(x / np) + np - x
-fn ln_d0(x: f64, np: f64) -> f64 {
+pub(crate) fn ln_d0(x: f64, np: f64) -> f64 {
     if (x - np).abs() < 0.1 * (x + np) {
         // ε = (n / np) is close to 1. Use a series expansion.
-        let mut s = (x - np).powi(2) / (x + np);
+        let mut s = math::powi(x - np, 2) / (x + np);
         let v = (x - np) / (x + np);
         let mut ej = 2.0 * x * v;
         let mut j = 1;
@@ -361,11 +887,11 @@ fn ln_d0(x: f64, np: f64) -> f64 {
             j += 1;
         }
     }
-    x * (x / np).ln() + np - x
+    x * math::ln(x / np) + np - x
 }
 
 // strilerr(n) = ln(n!) - ln(sqrt(2π * n) * (n / e)^n)
-fn stirlerr(n: f64) -> f64 {
+pub(crate) fn stirlerr(n: f64) -> f64 {
     const S0: f64 = 1.0 / 12.0;
     const S1: f64 = 1.0 / 360.0;
     const S2: f64 = 1.0 / 1260.0;
@@ -386,7 +912,7 @@ fn stirlerr(n: f64) -> f64 {
         1.041126526197209650e-02,
         9.255462182712732918e-03,
         8.330563433362871256e-03,
-        7.757367548795184079e-03,
+        7.573675487951840794e-03,
         6.942840107209529866e-03,
         6.408994188004207068e-03,
         5.951370112758847736e-03,
@@ -414,6 +940,7 @@ fn stirlerr(n: f64) -> f64 {
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new {
@@ -422,6 +949,25 @@ mod tests {
         };
     }
 
+    #[test]
+    fn characteristic() {
+        let d = new!(4, 0.25);
+        let t = vec![-1.0, 0.0, 0.5, 2.0];
+        let (re, im): (Vec<_>, Vec<_>) = t.iter().map(|&t| d.characteristic(t)).unzip();
+        let (expected_re, expected_im): (Vec<_>, Vec<_>) = t
+            .iter()
+            .map(|&t: &f64| {
+                (0..5).fold((0.0, 0.0), |(re, im), x| {
+                    let p = d.mass(x);
+                    let x = t * x as f64;
+                    (re + p * x.cos(), im + p * x.sin())
+                })
+            })
+            .unzip();
+        assert::close(&re, &expected_re, 1e-6);
+        assert::close(&im, &expected_im, 1e-6);
+    }
+
     #[test]
     fn distribution() {
         let d = new!(16, 0.75);
@@ -451,10 +997,34 @@ mod tests {
 
     #[test]
     fn entropy() {
-        assert_eq!(new!(16, 0.25).entropy(), 1.9588018945068573);
+        assert::close(new!(16, 0.25).entropy(), 1.9588258531430701, 1e-15);
+        assert::close(new!(5000, 0.3).entropy(), 4.8971985469807515, 1e-14);
+        assert::close(new!(9999, 0.001).entropy(), 2.5608847523459408, 1e-14);
+        assert::close(new!(200, 0.999).entropy(), 0.5353138203029356, 1e-14);
         assert_eq!(new!(10_000_000, 0.5).entropy(), 8.784839178123887);
     }
 
+    #[test]
+    fn expect() {
+        let d = new!(10, 0.25);
+        assert::close(d.expect(|x| x), 2.5, 1e-14);
+        assert::close(d.expect(|x| (x - 2.5).powi(2)), 1.875, 1e-14);
+    }
+
+    #[test]
+    fn interval_mass() {
+        let d = new!(10, 0.25);
+        assert_eq!(d.interval_mass(3..3), 0.0);
+        assert::close(d.interval_mass(0..11), 1.0, 1e-12);
+        assert::close(
+            d.interval_mass(3..8),
+            d.distribution(7.0) - d.distribution(2.0),
+            1e-12,
+        );
+        assert::close(d.interval_mass(3..8), d.probability(3.0..8.0), 1e-12);
+        assert::close(d.interval_mass(3..8), d.probability(2.5..7.5), 1e-12);
+    }
+
     #[test]
     fn inverse() {
         // Check edge cases.
@@ -487,11 +1057,69 @@ mod tests {
         assert_eq!(d.inverse(0.0033333333333332993), 3573);
     }
 
+    #[test]
+    fn inverse_bracket() {
+        let d = new!(250, 0.55);
+        for &p in &[0.0, 0.01, 0.1, 0.5, 0.9, 0.99] {
+            let (k, lower, upper) = d.inverse_bracket(p);
+            assert_eq!(k, d.inverse(p));
+            assert!(p == 0.0 || lower < p);
+            assert!(p <= upper);
+        }
+        assert_eq!(d.inverse_bracket(0.0).1, 0.0);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(16, 0.25).kurtosis(), -0.041666666666666664);
     }
 
+    #[test]
+    fn large_n() {
+        let d = new!(3_000_000_000, 1e-9);
+        assert::close(d.mass(0) / 0.04978706829318334, 1.0, 1e-14);
+        assert::close(d.distribution(0.0) / 0.04978706829318334, 1.0, 1e-14);
+        assert::close(d.pgf(0.5) / 0.22313016006475601887, 1.0, 1e-14);
+        assert_eq!(d.inverse(0.0), 0);
+        assert_eq!(d.inverse(0.3), 2);
+
+        let d = new!(10_000_000_000, 1e-10);
+        assert_eq!(d.n(), 10_000_000_000);
+        assert::close(d.mass(0) / 0.36787944115304834954, 1.0, 1e-14);
+        assert::close(d.pgf(0.5) / 0.60653065970505179036, 1.0, 1e-14);
+        assert_eq!(d.inverse(1.0), 10_000_000_000);
+    }
+
+    #[test]
+    fn log_distribution() {
+        let d = new!(1000, 0.3);
+        assert::close(d.log_distribution(10.0), -311.1961570224567, 1e-9);
+        assert::close(d.log_distribution(400.0), -7.03014753029159e-12, 1e-20);
+        assert_eq!(d.log_distribution(1000.0), 0.0);
+        assert_eq!(d.log_distribution(-1.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_distribution_large() {
+        let d = new!(1_000_000, 1e-3);
+        assert::close(d.log_distribution(5.0), -970.7390539230989, 1e-8);
+        assert::close(d.log_distribution(700.0), -53.37113142013082, 1e-9);
+    }
+
+    #[test]
+    fn log_mass() {
+        let d = new!(16, 0.25);
+        assert::close(d.log_mass(4), 2.251990651711821e-01f64.ln(), 1e-14);
+        assert_eq!(d.log_mass(17), f64::NEG_INFINITY);
+        assert_eq!(d.mass(17), 0.0);
+
+        let d = new!(1_000_000, 1e-3);
+        assert::close(d.log_mass(5), -970.7440564297519, 1e-8);
+        assert::close(d.log_mass(1000), -4.372399255942922, 1e-12);
+        assert::close(d.log_mass(3000), -1302.7608531898345, 1e-8);
+        assert_eq!(d.mass(3000), 0.0);
+    }
+
     #[test]
     fn mass() {
         let d = new!(16, 0.25);
@@ -512,6 +1140,30 @@ mod tests {
             &p,
             1e-14,
         );
+        assert::close(d.mass(5), 1.801592521369457e-01, 1e-14);
+        assert::close(d.mass(11), 2.471320331096649e-04, 1e-14);
+    }
+
+    #[test]
+    fn mass_iter() {
+        let d = new!(16, 0.25);
+        let masses = d.mass_iter();
+        assert_eq!(masses.len(), 17);
+        for (x, mass) in (0..).zip(masses) {
+            assert::close(mass, d.mass(x), 1e-15);
+        }
+
+        let d = new!(1_000_000, 0.3);
+        let masses = d.mass_iter().collect::<Vec<_>>();
+        assert_eq!(masses.len(), 1_000_001);
+        assert::close(masses.iter().sum::<f64>(), 1.0, 1e-12);
+        for &x in &[0, 280_000, 299_999, 300_000, 301_234, 320_000, 1_000_000] {
+            if masses[x as usize] == 0.0 {
+                assert!(d.log_mass(x) < -700.0);
+            } else {
+                assert::close(masses[x as usize] / d.mass(x), 1.0, 1e-12);
+            }
+        }
     }
 
     #[test]
@@ -527,6 +1179,14 @@ mod tests {
         assert_eq!(new!(39, 0.1).median(), 4.0);
     }
 
+    #[test]
+    fn mgf() {
+        let d = new!(4, 0.25);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert::close(d.mgf(3f64.ln()), 5.0625, 1e-14);
+        assert::close(d.log_mgf(-(3f64.ln())), 4.0 * (5.0f64 / 6.0).ln(), 1e-15);
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(16, 0.25).modes(), vec![4]);
@@ -535,6 +1195,100 @@ mod tests {
         assert_eq!(new!(39, 0.1).modes(), vec![3, 4]);
     }
 
+    #[test]
+    fn modes_iter() {
+        let mut modes = new!(3, 0.5).modes_iter();
+        assert_eq!(modes.len(), 2);
+        assert_eq!(modes.next(), Some(1));
+        assert_eq!(modes.len(), 1);
+        assert_eq!(modes.next(), Some(2));
+        assert_eq!(modes.next(), None);
+        assert_eq!(new!(16, 0.25).modes_iter().len(), 1);
+    }
+
+    #[test]
+    fn moments() {
+        let d = estimate::moments::<Binomial>(&[2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(d.n(), 8);
+        assert_eq!(d.p(), 0.5);
+        assert!(estimate::moments::<Binomial>(&[0, 10]).is_none());
+    }
+
+    #[test]
+    fn outcomes() {
+        let d = new!(10, 0.25);
+        let outcomes = d.outcomes(0.0).collect::<Vec<_>>();
+        assert_eq!(outcomes.len(), 11);
+        assert::close(
+            outcomes.iter().fold(0.0, |sum, &(_, p)| sum + p),
+            1.0,
+            1e-14,
+        );
+        for &(x, p) in outcomes.iter() {
+            assert_eq!(p, d.mass(x));
+        }
+        let outcomes = d.outcomes(1e-3).collect::<Vec<_>>();
+        assert_eq!(outcomes.len(), 8);
+        assert!(1.0 - d.distribution(7.0) <= 1e-3);
+    }
+
+    #[test]
+    fn pgf() {
+        let d = new!(16, 0.25);
+        assert_eq!(d.pgf(1.0), 1.0);
+        assert::close(d.pgf(0.0), 0.75f64.powi(16), 1e-15);
+        assert::close(d.pgf(0.5), 0.875f64.powi(16), 1e-15);
+
+        let (mean, variance) = (d.mean(), d.variance());
+        assert_eq!(d.factorial_moment(0), 1.0);
+        assert_eq!(d.factorial_moment(1), mean);
+        assert::close(d.factorial_moment(2), variance + mean * mean - mean, 1e-12);
+        assert_eq!(d.factorial_moment(17), 0.0);
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default(42);
+        for &(n, p) in [
+            (20, 0.1),
+            (20, 0.9),
+            (1000, 0.3),
+            (1000, 0.7),
+            (100000, 0.5),
+        ]
+        .iter()
+        {
+            let d = new!(n, p);
+            let samples = Independent(&d, &mut source)
+                .take(100000)
+                .collect::<Vec<_>>();
+            assert!(samples.iter().all(|&x| x <= n));
+            let mean = samples.iter().fold(0.0, |sum, &x| sum + x as f64) / 100000.0;
+            let variance = samples
+                .iter()
+                .fold(0.0, |sum, &x| sum + (x as f64 - mean).powi(2))
+                / 100000.0;
+            assert::close(mean / d.mean(), 1.0, 0.01);
+            assert::close(variance / d.variance(), 1.0, 0.05);
+            let x = d.median() as u64;
+            let count = samples.iter().filter(|&&y| y <= x).count();
+            assert::close(count as f64 / 100000.0, d.distribution(x as f64), 0.01);
+        }
+    }
+
+    #[test]
+    fn sample_into() {
+        let d = new!(1000, 0.7);
+        let mut buffer = vec![0; 100000];
+        d.sample_into(&mut buffer, &mut source::default(42));
+        let mean = buffer.iter().fold(0.0, |sum, &x| sum + x as f64) / 100000.0;
+        assert::close(mean / d.mean(), 1.0, 0.01);
+
+        let mut source = source::default(42);
+        let samples = Independent(&d, &mut source).take(10).collect::<Vec<_>>();
+        assert_eq!(&buffer[..10], &samples[..]);
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(16, 0.25).skewness(), 0.2886751345948129);
@@ -1,12 +1,16 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
+use core::iter::Enumerate;
+use core::slice;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use math;
 use source::Source;
 
 /// A categorical distribution.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Categorical {
     k: usize,
     p: Vec<f64>,
@@ -21,14 +25,18 @@ impl Categorical {
         should!(is_probability_vector(p), {
             const EPSILON: f64 = 1e-12;
             p.iter().all(|&p| (0.0..=1.0).contains(&p))
-                && (p.iter().fold(0.0, |sum, &p| sum + p) - 1.0).abs() < EPSILON
+                && (sum(p.iter().cloned()) - 1.0).abs() < EPSILON
         });
 
         let k = p.len();
-        let mut cumsum = p.to_vec();
-        for i in 1..(k - 1) {
-            cumsum[i] += cumsum[i - 1];
-        }
+        let mut total = Sum::default();
+        let mut cumsum = p
+            .iter()
+            .map(|&p| {
+                total.add(p);
+                total.value()
+            })
+            .collect::<Vec<_>>();
         cumsum[k - 1] = 1.0;
         Categorical {
             k,
@@ -37,6 +45,53 @@ impl Categorical {
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(p: &[f64]) -> Result<Self, Error> {
+        error::require("p.len() > 0", !p.is_empty())?;
+        if p.iter().any(|&p| !(0.0..=1.0).contains(&p)) {
+            return Err(Error::NotProbability("p"));
+        }
+        error::require("sum(p) == 1", (sum(p.iter().cloned()) - 1.0).abs() < 1e-12)?;
+        Ok(Categorical::new(p))
+    }
+
+    /// Create a distribution with probabilities proportional to counts.
+    ///
+    /// The counts should be finite and nonnegative, and at least one of them
+    /// should be positive.
+    #[inline]
+    pub fn from_counts(counts: &[f64]) -> Result<Self, Error> {
+        Categorical::from_counts_smoothed(counts, 0.0)
+    }
+
+    /// Create a distribution with probabilities proportional to counts with
+    /// additive smoothing.
+    ///
+    /// The pseudocount `alpha` is added to each count, which corresponds to
+    /// the posterior mean under a symmetric Dirichlet prior with concentration
+    /// `alpha`, and `alpha = 1` gives Laplace smoothing. The counts and the
+    /// pseudocount should be finite and nonnegative, and the total should be
+    /// positive.
+    pub fn from_counts_smoothed(counts: &[f64], alpha: f64) -> Result<Self, Error> {
+        error::require("counts.len() > 0", !counts.is_empty())?;
+        error::finite("alpha", alpha)?;
+        error::require("alpha >= 0", alpha >= 0.0)?;
+        for &count in counts {
+            error::finite("counts", count)?;
+            error::require("counts >= 0", count >= 0.0)?;
+        }
+        let total = counts.iter().fold(0.0, |sum, &count| sum + count + alpha);
+        error::require("sum(counts) > 0", total > 0.0)?;
+        let p = counts
+            .iter()
+            .map(|&count| (count + alpha) / total)
+            .collect::<Vec<_>>();
+        Ok(Categorical::new(&p))
+    }
+
     /// Return the number of categories.
     #[inline(always)]
     pub fn k(&self) -> usize {
@@ -50,6 +105,18 @@ impl Categorical {
     }
 }
 
+impl distribution::CharacteristicFunction for Categorical {
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        self.p
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, p)| {
+                let x = t * i as f64;
+                (re + p * libm::cos(x), im + p * libm::sin(x))
+            })
+    }
+}
+
 impl distribution::Discrete for Categorical {
     #[inline]
     fn mass(&self, x: usize) -> f64 {
@@ -71,11 +138,16 @@ impl distribution::Distribution for Categorical {
         }
         self.cumsum[x]
     }
+
+    #[inline]
+    fn probability(&self, interval: core::ops::Range<f64>) -> f64 {
+        distribution::integer_probability(self, interval)
+    }
 }
 
 impl distribution::Entropy for Categorical {
     fn entropy(&self) -> f64 {
-        -self.p.iter().fold(0.0, |sum, p| sum + p * p.ln())
+        -sum(self.p.iter().map(|&p| p * math::ln(p)))
     }
 }
 
@@ -89,25 +161,31 @@ impl distribution::Inverse for Categorical {
     }
 }
 
+impl distribution::InverseBracket for Categorical {
+    fn inverse_bracket(&self, p: f64) -> (usize, f64, f64) {
+        use distribution::Inverse;
+        let k = self.inverse(p);
+        let lower = if k == 0 { 0.0 } else { self.cumsum[k - 1] };
+        (k, lower, self.cumsum[k])
+    }
+}
+
 impl distribution::Kurtosis for Categorical {
     fn kurtosis(&self) -> f64 {
         use distribution::{Mean, Variance};
         let (mean, variance) = (self.mean(), self.variance());
-        let kurt = self
+        let kurt = sum(self
             .p
             .iter()
             .enumerate()
-            .fold(0.0, |sum, (i, p)| sum + (i as f64 - mean).powi(4) * p);
-        kurt / variance.powi(2) - 3.0
+            .map(|(i, p)| math::powi(i as f64 - mean, 4) * p));
+        kurt / math::powi(variance, 2) - 3.0
     }
 }
 
 impl distribution::Mean for Categorical {
     fn mean(&self) -> f64 {
-        self.p
-            .iter()
-            .enumerate()
-            .fold(0.0, |sum, (i, p)| sum + i as f64 * p)
+        sum(self.p.iter().enumerate().map(|(i, p)| i as f64 * p))
     }
 }
 
@@ -130,20 +208,40 @@ impl distribution::Median for Categorical {
     }
 }
 
-impl distribution::Modes for Categorical {
-    fn modes(&self) -> Vec<usize> {
-        let mut modes = Vec::new();
-        let mut max = 0.0;
-        for (i, &p) in self.p.iter().enumerate() {
-            if p == max {
-                modes.push(i);
-            }
-            if p > max {
-                max = p;
-                modes = vec![i];
-            }
+impl distribution::Mgf for Categorical {
+    fn mgf(&self, t: f64) -> f64 {
+        self.p
+            .iter()
+            .enumerate()
+            .fold(0.0, |sum, (i, p)| sum + p * math::exp(t * i as f64))
+    }
+
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        math::ln(self.mgf(t))
+    }
+}
+
+impl distribution::ModesIter for Categorical {
+    type Iter<'l> = CategoricalModes<'l>;
+
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        CategoricalModes {
+            p: self.p.iter().enumerate(),
+            max: self.p.iter().fold(0.0, |max, &p| max.max(p)),
         }
-        modes
+    }
+}
+
+impl distribution::Pgf for Categorical {
+    fn pgf(&self, z: f64) -> f64 {
+        self.p.iter().rev().fold(0.0, |sum, p| sum * z + p)
+    }
+
+    fn factorial_moment(&self, k: usize) -> f64 {
+        self.p.iter().enumerate().skip(k).fold(0.0, |sum, (i, p)| {
+            sum + p * ((i - k + 1)..(i + 1)).fold(1.0, |product, j| product * j as f64)
+        })
     }
 }
 
@@ -162,26 +260,93 @@ impl distribution::Skewness for Categorical {
     fn skewness(&self) -> f64 {
         use distribution::{Mean, Variance};
         let (mean, variance) = (self.mean(), self.variance());
-        let skew = self
+        let skew = sum(self
             .p
             .iter()
             .enumerate()
-            .fold(0.0, |sum, (i, p)| sum + (i as f64 - mean).powi(3) * p);
+            .map(|(i, p)| math::powi(i as f64 - mean, 3) * p));
         skew / (variance * variance.sqrt())
     }
 }
 
+impl distribution::Support for Categorical {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, (self.k - 1) as f64)
+    }
+}
+
 impl distribution::Variance for Categorical {
     fn variance(&self) -> f64 {
         use distribution::Mean;
         let mean = self.mean();
-        self.p
+        sum(self
+            .p
             .iter()
             .enumerate()
-            .fold(0.0, |sum, (i, p)| sum + (i as f64 - mean).powi(2) * p)
+            .map(|(i, p)| math::powi(i as f64 - mean, 2) * p))
     }
 }
 
+/// An iterator over the modes of a categorical distribution.
+#[derive(Clone, Debug)]
+pub struct CategoricalModes<'l> {
+    p: Enumerate<slice::Iter<'l, f64>>,
+    max: f64,
+}
+
+impl Iterator for CategoricalModes<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let max = self.max;
+        self.p.find(|&(_, &p)| p == max).map(|(i, _)| i)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.p.size_hint().1)
+    }
+}
+
+/// A sum with compensation for the rounding errors.
+///
+/// ## References
+///
+/// 1. A. Neumaier, “Rundungsfehleranalyse einiger Verfahren zur Summation
+///    endlicher Summen,” Zeitschrift für Angewandte Mathematik und Mechanik,
+///    vol. 54, no. 1, pp. 39–51, 1974.
+#[derive(Clone, Copy, Default)]
+struct Sum {
+    value: f64,
+    compensation: f64,
+}
+
+impl Sum {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        let value = self.value + x;
+        if self.value.abs() >= x.abs() {
+            self.compensation += (self.value - value) + x;
+        } else {
+            self.compensation += (x - value) + self.value;
+        }
+        self.value = value;
+    }
+
+    #[inline]
+    fn value(&self) -> f64 {
+        self.value + self.compensation
+    }
+}
+
+/// Sum terms with compensation for the rounding errors.
+fn sum<I: Iterator<Item = f64>>(terms: I) -> f64 {
+    let mut sum = Sum::default();
+    terms.for_each(|x| sum.add(x));
+    sum.value()
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
@@ -192,6 +357,16 @@ mod tests {
         ($p:expr) => { Categorical::new(&$p) };
     );
 
+    #[test]
+    fn characteristic() {
+        use core::f64::consts::PI;
+        let d = new!([0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(d.characteristic(0.0), (1.0, 0.0));
+        let (re, im) = d.characteristic(PI);
+        assert::close(re, -0.2, 1e-15);
+        assert::close(im, 0.0, 1e-15);
+    }
+
     #[test]
     fn distribution() {
         let d = new!([0.0, 0.75, 0.25, 0.0]);
@@ -228,6 +403,30 @@ mod tests {
         assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).entropy(), 1.2798542258336676);
     }
 
+    #[test]
+    fn entropy_bits() {
+        assert_eq!(new!(equal 4).entropy_bits(), 2.0);
+        assert::close(new!(equal 4).entropy_base(16.0), 0.5, 1e-15);
+    }
+
+    #[test]
+    fn from_counts() {
+        use Error;
+        let d = Categorical::from_counts(&[1.0, 0.0, 3.0]).unwrap();
+        assert_eq!(d.p(), &[0.25, 0.0, 0.75]);
+        let d = Categorical::from_counts_smoothed(&[1.0, 0.0, 3.0], 1.0).unwrap();
+        assert::close(d.p(), &[2.0 / 7.0, 1.0 / 7.0, 4.0 / 7.0], 1e-15);
+        assert_eq!(
+            Categorical::from_counts(&[0.0, 0.0]).unwrap_err(),
+            Error::Violated("sum(counts) > 0")
+        );
+        assert_eq!(
+            Categorical::from_counts(&[1.0, -1.0]).unwrap_err(),
+            Error::Violated("counts >= 0")
+        );
+        assert!(Categorical::from_counts_smoothed(&[0.0, 0.0], 0.5).is_ok());
+    }
+
     #[test]
     fn inverse() {
         let d = new!([0.0, 0.75, 0.25, 0.0]);
@@ -245,6 +444,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_bracket() {
+        let d = new!([0.0, 0.75, 0.25, 0.0]);
+        assert_eq!(d.inverse_bracket(0.5), (1, 0.0, 0.75));
+        assert_eq!(d.inverse_bracket(0.8), (2, 0.75, 1.0));
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(equal 2).kurtosis(), -2.0);
@@ -285,6 +491,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mgf() {
+        let d = new!(equal 2);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert_eq!(d.mgf(3f64.ln()), 2.0);
+        assert_eq!(d.log_mgf(3f64.ln()), 2f64.ln());
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!([0.6, 0.2, 0.2]).modes(), vec![0]);
@@ -297,6 +511,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn modes_iter() {
+        let d = new!([0.4, 0.2, 0.4]);
+        let mut modes = d.modes_iter();
+        assert_eq!(modes.next(), Some(0));
+        assert_eq!(modes.next(), Some(2));
+        assert_eq!(modes.next(), None);
+    }
+
+    #[test]
+    fn outcomes() {
+        let d = Categorical::new(&[0.5, 0.0, 0.25, 0.25]);
+        assert_eq!(
+            d.outcomes(0.0).collect::<Vec<_>>(),
+            vec![(0, 0.5), (1, 0.0), (2, 0.25), (3, 0.25)]
+        );
+    }
+
+    #[test]
+    fn partial_eq() {
+        assert_eq!(Categorical::new(&[0.5, 0.5]), Categorical::new(&[0.5, 0.5]));
+        assert_ne!(
+            Categorical::new(&[0.5, 0.5]),
+            Categorical::new(&[0.25, 0.75])
+        );
+    }
+
+    #[test]
+    fn pgf() {
+        let d = new!([0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(d.pgf(0.0), 0.1);
+        assert::close(d.pgf(1.0), 1.0, 1e-15);
+        assert::close(d.pgf(2.0), 0.1 + 0.4 + 1.2 + 3.2, 1e-14);
+
+        let (mean, variance) = (d.mean(), d.variance());
+        assert_eq!(d.factorial_moment(0), 1.0);
+        assert::close(d.factorial_moment(1), mean, 1e-15);
+        assert::close(d.factorial_moment(2), variance + mean * mean - mean, 1e-14);
+        assert::close(d.factorial_moment(3), 2.4, 1e-15);
+        assert_eq!(d.factorial_moment(4), 0.0);
+    }
+
     #[test]
     fn sample() {
         let mut source = source::default(42);
@@ -314,6 +570,17 @@ mod tests {
             .all(|x| x % 2 != 0));
     }
 
+    #[test]
+    fn small_probabilities() {
+        let k = 30000;
+        let mut p = vec![1e-6 / 3.0; k];
+        p[0] = 1.0 - (k - 1) as f64 * 1e-6 / 3.0;
+        let d = new!(p);
+        assert::close(d.distribution((k - 2) as f64), 1.0 - 1e-6 / 3.0, 1e-15);
+        let mean = (1..k).fold(0.0, |sum, i| sum + i as f64) * 1e-6 / 3.0;
+        assert::close(d.mean(), mean, 1e-12);
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(equal 6).skewness(), 0.0);
@@ -321,7 +588,25 @@ mod tests {
             new!([1.0 / 6.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 6.0]).skewness(),
             0.0
         );
-        assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).skewness(), -0.6);
+        assert::close(new!([0.1, 0.2, 0.3, 0.4]).skewness(), -0.6, 1e-15);
+    }
+
+    #[test]
+    fn try_new() {
+        use Error;
+        assert!(Categorical::try_new(&[0.25, 0.75]).is_ok());
+        assert_eq!(
+            Categorical::try_new(&[]).unwrap_err(),
+            Error::Violated("p.len() > 0")
+        );
+        assert_eq!(
+            Categorical::try_new(&[f64::NAN, 1.0]).unwrap_err(),
+            Error::NotProbability("p")
+        );
+        assert_eq!(
+            Categorical::try_new(&[0.5, 0.25]).unwrap_err(),
+            Error::Violated("sum(p) == 1")
+        );
     }
 
     #[test]
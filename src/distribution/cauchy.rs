@@ -1,8 +1,9 @@
-use alloc::{vec, vec::Vec};
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use math;
 use source::Source;
 
 /// A Cauchy distribution.
@@ -16,7 +17,7 @@ use source::Source;
 ///
 /// The distribution is long tailed and has no mean or variance. It is unimodal
 /// with the mode at `x_0`, around which it is symmetric.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Cauchy {
     x_0: f64,
     gamma: f64,
@@ -27,11 +28,21 @@ impl Cauchy {
     ///
     /// It should hold that `gamma > 0`.
     #[inline]
-    pub fn new(x_0: f64, gamma: f64) -> Self {
+    pub const fn new(x_0: f64, gamma: f64) -> Self {
         should!(gamma > 0.0);
         Cauchy { x_0, gamma }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(x_0: f64, gamma: f64) -> Result<Self, Error> {
+        error::finite("x_0", x_0)?;
+        error::positive("gamma", gamma)?;
+        Ok(Cauchy::new(x_0, gamma))
+    }
+
     /// Return the location parameter.
     #[inline(always)]
     pub fn x_0(&self) -> f64 {
@@ -45,6 +56,15 @@ impl Cauchy {
     }
 }
 
+impl distribution::CharacteristicFunction for Cauchy {
+    #[inline]
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let modulus = math::exp(-self.gamma * t.abs());
+        let x = self.x_0 * t;
+        (modulus * libm::cos(x), modulus * libm::sin(x))
+    }
+}
+
 impl distribution::Continuous for Cauchy {
     #[inline]
     fn density(&self, x: f64) -> f64 {
@@ -60,30 +80,30 @@ impl distribution::Distribution for Cauchy {
     #[inline]
     fn distribution(&self, x: f64) -> f64 {
         use core::f64::consts::FRAC_1_PI;
-        FRAC_1_PI * ((x - self.x_0) / self.gamma).atan() + 0.5
+        FRAC_1_PI * math::atan((x - self.x_0) / self.gamma) + 0.5
     }
 }
 
 impl distribution::Entropy for Cauchy {
     #[inline]
     fn entropy(&self) -> f64 {
-        (core::f64::consts::PI * 4.0 * self.gamma).ln()
+        math::ln(core::f64::consts::PI * 4.0 * self.gamma)
     }
 }
 
 impl distribution::Inverse for Cauchy {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
-        use core::f64::{consts::PI, INFINITY, NEG_INFINITY};
+        use core::f64::consts::PI;
 
         should!((0.0..=1.0).contains(&p));
 
         if p <= 0.0 {
-            NEG_INFINITY
+            f64::NEG_INFINITY
         } else if 1.0 <= p {
-            INFINITY
+            f64::INFINITY
         } else {
-            self.x_0 + self.gamma * (PI * (p - 0.5)).tan()
+            self.x_0 + self.gamma * math::tan(PI * (p - 0.5))
         }
     }
 }
@@ -95,10 +115,12 @@ impl distribution::Median for Cauchy {
     }
 }
 
-impl distribution::Modes for Cauchy {
+impl distribution::ModesIter for Cauchy {
+    type Iter<'l> = distribution::ModePair<f64>;
+
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![self.x_0]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(self.x_0)
     }
 }
 
@@ -115,6 +137,26 @@ impl distribution::Sample for Cauchy {
     }
 }
 
+impl distribution::Support for Cauchy {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Cauchy {
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        use core::f64::consts::FRAC_1_PI;
+        let z = (x - self.x_0) / self.gamma;
+        if z > 0.0 {
+            FRAC_1_PI * math::atan(z.recip())
+        } else {
+            0.5 - FRAC_1_PI * math::atan(z)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
@@ -125,6 +167,21 @@ mod tests {
         ($x_0:expr, $gamma:expr) => (Cauchy::new($x_0, $gamma));
     );
 
+    #[test]
+    fn characteristic() {
+        use distribution;
+
+        let d = new!(2.0, 8.0);
+        let x = vec![-100.0, -1.0, 0.0, 2.0, 5.0, 10.0, 100.0];
+        assert::close(
+            &x.iter()
+                .map(|&x| distribution::invert_characteristic(&d, x))
+                .collect::<Vec<_>>(),
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            1e-10,
+        );
+    }
+
     #[test]
     fn density() {
         let d = new!(2.0, 8.0);
@@ -188,17 +245,15 @@ mod tests {
 
     #[test]
     fn inverse() {
-        use core::f64::{INFINITY, NEG_INFINITY};
-
         let d = new!(2.0, 3.0);
         let x = vec![
-            NEG_INFINITY,
+            f64::NEG_INFINITY,
             -7.2330506115257585,
             -0.9999999999999996,
             2.0,
             5.0,
             11.233050611525758,
-            INFINITY,
+            f64::INFINITY,
         ];
         let p = vec![0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0];
 
@@ -234,4 +289,26 @@ mod tests {
             / n as f64;
         assert!((cross_entropy - d.entropy()).abs() < 0.01);
     }
+
+    #[test]
+    fn survival() {
+        let d = new!(2.0, 8.0);
+        let x = vec![-1.0, 2.0, 10.0, 1e3, 1e10];
+        let p = vec![
+            6.142002512199082e-01,
+            5.000000000000000e-01,
+            2.500000000000000e-01,
+            2.551527603940526e-03,
+            2.546479089979621e-10,
+        ];
+
+        assert::close(
+            &x.iter()
+                .zip(&p)
+                .map(|(&x, &p)| d.survival(x) / p)
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+    }
 }
@@ -1,12 +1,15 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// An exponential distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Exponential {
     lambda: f64,
 }
@@ -16,11 +19,20 @@ impl Exponential {
     ///
     /// It should hold that `lambda > 0`.
     #[inline]
-    pub fn new(lambda: f64) -> Self {
+    pub const fn new(lambda: f64) -> Self {
         should!(lambda > 0.0);
         Exponential { lambda }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(lambda: f64) -> Result<Self, Error> {
+        error::positive("lambda", lambda)?;
+        Ok(Exponential::new(lambda))
+    }
+
     /// Return the rate parameter.
     #[inline(always)]
     pub fn lambda(&self) -> f64 {
@@ -28,13 +40,39 @@ impl Exponential {
     }
 }
 
+impl distribution::CharacteristicFunction for Exponential {
+    #[inline]
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let norm = self.lambda * self.lambda + t * t;
+        (self.lambda * self.lambda / norm, self.lambda * t / norm)
+    }
+}
+
 impl distribution::Continuous for Exponential {
     #[inline]
     fn density(&self, x: f64) -> f64 {
         if x < 0.0 {
             0.0
         } else {
-            self.lambda * (-self.lambda * x).exp()
+            self.lambda * math::exp(-self.lambda * x)
+        }
+    }
+
+    #[inline]
+    fn log_density(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            math::ln(self.lambda) - self.lambda * x
+        }
+    }
+
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        let lambda = self.lambda;
+        for (value, &x) in out.iter_mut().zip(xs) {
+            let density = lambda * math::exp(-lambda * x);
+            *value = if x < 0.0 { 0.0 } else { density };
         }
     }
 }
@@ -47,7 +85,25 @@ impl distribution::Distribution for Exponential {
         if x <= 0.0 {
             0.0
         } else {
-            -(-self.lambda * x).exp_m1()
+            -math::exp_m1(-self.lambda * x)
+        }
+    }
+
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        let lambda = self.lambda;
+        for (value, &x) in out.iter_mut().zip(xs) {
+            let distribution = -math::exp_m1(-lambda * x);
+            *value = if x <= 0.0 { 0.0 } else { distribution };
+        }
+    }
+
+    #[inline]
+    fn log_distribution(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            math::ln(-math::exp_m1(-self.lambda * x))
         }
     }
 }
@@ -55,7 +111,7 @@ impl distribution::Distribution for Exponential {
 impl distribution::Entropy for Exponential {
     #[inline]
     fn entropy(&self) -> f64 {
-        1.0 - self.lambda.ln()
+        1.0 - math::ln(self.lambda)
     }
 }
 
@@ -63,7 +119,7 @@ impl distribution::Inverse for Exponential {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
         should!((0.0..=1.0).contains(&p));
-        -(-p).ln_1p() / self.lambda
+        -math::ln_1p(-p) / self.lambda
     }
 }
 
@@ -89,20 +145,49 @@ impl distribution::Median for Exponential {
     }
 }
 
-impl distribution::Modes for Exponential {
+impl distribution::Mgf for Exponential {
+    #[inline]
+    fn mgf(&self, t: f64) -> f64 {
+        if t < self.lambda {
+            self.lambda / (self.lambda - t)
+        } else {
+            f64::NAN
+        }
+    }
+
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        if t < self.lambda {
+            -math::ln_1p(-t / self.lambda)
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl distribution::ModesIter for Exponential {
+    type Iter<'l> = distribution::ModePair<f64>;
+
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![0.0]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(0.0)
     }
 }
 
 impl distribution::Sample for Exponential {
+    /// Draw a sample.
+    ///
+    /// ## References
+    ///
+    /// 1. G. Marsaglia and W. W. Tsang, “The ziggurat method for generating
+    ///    random variables,” Journal of Statistical Software, vol. 5, no. 8,
+    ///    pp. 1–7, 10 2000.
     #[inline]
     fn sample<S>(&self, source: &mut S) -> f64
     where
         S: Source,
     {
-        -source.read::<f64>().ln() / self.lambda
+        sample(source) / self.lambda
     }
 }
 
@@ -113,10 +198,55 @@ impl distribution::Skewness for Exponential {
     }
 }
 
+impl distribution::Support for Exponential {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Exponential {
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            math::exp(-self.lambda * x)
+        }
+    }
+
+    #[inline]
+    fn log_survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            -self.lambda * x
+        }
+    }
+
+    #[inline]
+    fn hazard(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else {
+            self.lambda
+        }
+    }
+
+    #[inline]
+    fn cumulative_hazard(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            self.lambda * x
+        }
+    }
+}
+
 impl distribution::Variance for Exponential {
     #[inline]
     fn variance(&self) -> f64 {
-        self.lambda.powi(-2)
+        math::powi(self.lambda, -2)
     }
 
     #[inline]
@@ -125,16 +255,271 @@ impl distribution::Variance for Exponential {
     }
 }
 
+impl estimate::MaximumLikelihood for Exponential {
+    fn maximum_likelihood(data: &[f64]) -> Option<Estimate<Self>> {
+        if data.is_empty() || data.iter().any(|&x| x < 0.0) {
+            return None;
+        }
+        let n = data.len() as f64;
+        let mean = estimate::mean(data);
+        if mean == 0.0 {
+            return None;
+        }
+        let lambda = mean.recip();
+        Some(Estimate {
+            distribution: Exponential::new(lambda),
+            parameters: vec![lambda],
+            errors: vec![lambda / n.sqrt()],
+            report: Report::closed(n * (math::ln(lambda) - 1.0)),
+        })
+    }
+}
+
+/// Draw a sample from the standard exponential distribution.
+pub fn sample<S: Source>(source: &mut S) -> f64 {
+    let mut u = source.read::<u64>();
+    loop {
+        let i = (u & 0xFF) as usize;
+        let j = (u >> 32) as u32;
+        let x = j as f64 * W[i];
+        if j < K[i] {
+            return x;
+        }
+        if i == 0 {
+            return R - math::ln_1p(-source.read::<f64>());
+        }
+        if F[i] + source.read::<f64>() * (F[i - 1] - F[i]) < math::exp(-x) {
+            return x;
+        }
+        u = source.read::<u64>();
+    }
+}
+
+const R: f64 = 7.697117470131487;
+
+#[rustfmt::skip]
+const K: [u32; 256] = [
+    3801129273, 0, 2615860924, 3279400049,
+    3571300752, 3733536696, 3836274812, 3906990442,
+    3958562475, 3997804264, 4028649213, 4053523342,
+    4074002619, 4091154507, 4105727352, 4118261130,
+    4129155133, 4138710916, 4147160435, 4154685009,
+    4161428406, 4167506077, 4173011791, 4178022498,
+    4182601930, 4186803325, 4190671498, 4194244443,
+    4197554582, 4200629752, 4203493986, 4206168142,
+    4208670408, 4211016720, 4213221098, 4215295924,
+    4217252177, 4219099625, 4220846988, 4222502074,
+    4224071896, 4225562770, 4226980400, 4228329951,
+    4229616109, 4230843138, 4232014925, 4233135020,
+    4234206673, 4235232866, 4236216336, 4237159604,
+    4238064994, 4238934652, 4239770563, 4240574564,
+    4241348362, 4242093539, 4242811568, 4243503822,
+    4244171579, 4244816032, 4245438297, 4246039419,
+    4246620374, 4247182079, 4247725394, 4248251127,
+    4248760037, 4249252839, 4249730206, 4250192773,
+    4250641138, 4251075867, 4251497493, 4251906522,
+    4252303431, 4252688672, 4253062674, 4253425844,
+    4253778565, 4254121205, 4254454110, 4254777611,
+    4255092022, 4255397640, 4255694750, 4255983622,
+    4256264513, 4256537670, 4256803325, 4257061702,
+    4257313014, 4257557464, 4257795244, 4258026541,
+    4258251531, 4258470383, 4258683258, 4258890309,
+    4259091685, 4259287526, 4259477966, 4259663135,
+    4259843154, 4260018142, 4260188212, 4260353470,
+    4260514019, 4260669958, 4260821380, 4260968374,
+    4261111028, 4261249421, 4261383632, 4261513736,
+    4261639802, 4261761900, 4261880092, 4261994441,
+    4262105003, 4262211835, 4262314988, 4262414513,
+    4262510454, 4262602857, 4262691764, 4262777212,
+    4262859239, 4262937878, 4263013162, 4263085118,
+    4263153776, 4263219158, 4263281289, 4263340187,
+    4263395872, 4263448358, 4263497660, 4263543789,
+    4263586755, 4263626565, 4263663224, 4263696735,
+    4263727099, 4263754314, 4263778377, 4263799282,
+    4263817020, 4263831582, 4263842955, 4263851124,
+    4263856071, 4263857776, 4263856218, 4263851370,
+    4263843206, 4263831695, 4263816804, 4263798497,
+    4263776735, 4263751476, 4263722676, 4263690284,
+    4263654251, 4263614520, 4263571032, 4263523724,
+    4263472530, 4263417377, 4263358192, 4263294892,
+    4263227394, 4263155608, 4263079437, 4262998781,
+    4262913534, 4262823581, 4262728804, 4262629075,
+    4262524261, 4262414220, 4262298801, 4262177846,
+    4262051187, 4261918645, 4261780032, 4261635148,
+    4261483780, 4261325704, 4261160681, 4260988457,
+    4260808763, 4260621313, 4260425802, 4260221905,
+    4260009277, 4259787550, 4259556329, 4259315195,
+    4259063697, 4258801357, 4258527656, 4258242044,
+    4257943926, 4257632664, 4257307571, 4256967906,
+    4256612870, 4256241598, 4255853155, 4255446525,
+    4255020608, 4254574202, 4254106002, 4253614578,
+    4253098370, 4252555662, 4251984571, 4251383021,
+    4250748722, 4250079132, 4249371435, 4248622490,
+    4247828790, 4246986404, 4246090910, 4245137315,
+    4244119963, 4243032411, 4241867296, 4240616155,
+    4239269214, 4237815118, 4236240596, 4234530035,
+    4232664930, 4230623176, 4228378137, 4225897409,
+    4223141146, 4220059768, 4216590757, 4212654085,
+    4208145538, 4202926710, 4196809522, 4189531420,
+    4180713890, 4169789475, 4155865042, 4137444620,
+    4111806704, 4073393724, 4008685917, 3873074895,
+];
+
+#[rustfmt::skip]
+const W: [f64; 256] = [
+    2.02495545850482e-09, 1.4866740399734205e-11, 2.440961719625702e-11, 3.1968807089142434e-11,
+    3.844677064665035e-11, 4.422820397243411e-11, 4.9516444707046597e-11, 5.443358865093118e-11,
+    5.905944001532719e-11, 6.344942037911552e-11, 6.764381087646427e-11, 7.167294497483531e-11,
+    7.556032319946743e-11, 7.932458097693574e-11, 8.298078557904521e-11, 8.654132143825089e-11,
+    9.001651265218711e-11, 9.34150719307997e-11, 9.674443155535292e-11, 1.0001099208030049e-10,
+    1.0322031240760055e-10, 1.0637725725104457e-10, 1.0948611308870936e-10, 1.1255068044491511e-10,
+    1.1557434814019747e-10, 1.1856015362861798e-10, 1.2151083247552875e-10, 1.2442885926858554e-10,
+    1.2731648170466222e-10, 1.3017574919190648e-10, 1.3300853700670057e-10, 1.3581656682043475e-10,
+    1.3860142424039064e-10, 1.4136457387830522e-10, 1.4410737235911022e-10, 1.468310796035191e-10,
+    1.495368686561783e-10, 1.522258342820364e-10, 1.548990005144558e-10, 1.5755732730718325e-10,
+    1.602017164169217e-10, 1.6283301662263209e-10, 1.6545202837084708e-10, 1.6805950792244488e-10,
+    1.7065617106490835e-10, 1.7324269644462167e-10, 1.758197285658633e-10, 1.7838788049654857e-10,
+    1.8094773631522604e-10, 1.8349985332914868e-10, 1.8604476408927817e-10, 1.885829782247115e-10,
+    1.911149841161467e-10, 1.9364125042554713e-10, 1.9616222749705577e-10, 1.986783486423947e-10,
+    2.0119003132241833e-10, 2.0369767823513203e-10, 2.062016783193102e-10, 2.087024076818228e-10,
+    2.112002304558848e-10, 2.136954995966615e-10, 2.1618855761997602e-10, 2.1867973728926396e-10,
+    2.2116936225538936e-10, 2.2365774765346773e-10, 2.2614520066042933e-10, 2.2863202101668828e-10,
+    2.3111850151495869e-10, 2.336049284589698e-10, 2.3609158209457405e-10, 2.385787370155136e-10,
+    2.410666625459043e-10, 2.435556231013133e-10, 2.4604587853014233e-10, 2.4853768443687966e-10,
+    2.51031292488652e-10, 2.535269507063891e-10, 2.5602490374180384e-10, 2.5852539314129605e-10,
+    2.6102865759779895e-10, 2.635349331915091e-10, 2.6604445362036835e-10, 2.685574504211016e-10,
+    2.7107415318155595e-10, 2.735947897450323e-10, 2.761195864072536e-10, 2.786487681065689e-10,
+    2.8118255860795257e-10, 2.8372118068132283e-10, 2.862648562746699e-10, 2.888138066824537e-10,
+    2.9136825270970607e-10, 2.9392841483224504e-10, 2.9649451335338866e-10, 2.9906676855753434e-10,
+    3.0164540086095204e-10, 3.0423063096012276e-10, 3.068226799779392e-10, 3.094217696080717e-10,
+    3.120281222577913e-10, 3.1464196118953024e-10, 3.1726351066145236e-10, 3.198929960672951e-10,
+    3.2253064407574023e-10, 3.251766827695632e-10, 3.278313417848047e-10, 3.304948524502064e-10,
+    3.331674479271468e-10, 3.358493633503121e-10, 3.385408359693346e-10, 3.412421052916312e-10,
+    3.439534132266727e-10, 3.46675004231917e-10, 3.494071254606396e-10, 3.5215002691189675e-10,
+    3.5490396158286035e-10, 3.576691856237667e-10, 3.6044595849572514e-10, 3.632345431316382e-10,
+    3.660352061004911e-10, 3.688482177752741e-10, 3.716738525048091e-10, 3.7451238878976035e-10,
+    3.7736410946311836e-10, 3.8022930187545505e-10, 3.8310825808526086e-10, 3.860012750546849e-10,
+    3.889086548510128e-10, 3.918307048542317e-10, 3.947677379710455e-10, 3.977200728557207e-10,
+    4.006880341381615e-10, 4.036719526596301e-10, 4.0667216571654994e-10, 4.0968901731285145e-10,
+    4.1272285842134283e-10, 4.1577404725461407e-10, 4.1884294954601e-10, 4.219299388412365e-10,
+    4.2503539680119604e-10, 4.2815971351668243e-10, 4.3130328783559985e-10, 4.3446652770341104e-10,
+    4.376498505175607e-10, 4.4085368349666444e-10, 4.4407846406530314e-10, 4.4732464025531173e-10,
+    4.5059267112450964e-10, 4.5388302719387827e-10, 4.5719619090425536e-10, 4.6053265709368553e-10,
+    4.638929334966415e-10, 4.672775412664097e-10, 4.706870155220217e-10, 4.741219059212066e-10,
+    4.775827772609392e-10, 4.810702101072708e-10, 4.845848014562452e-10, 4.881271654278311e-10,
+    4.916979339949422e-10, 4.952977577497646e-10, 4.989273067097746e-10, 5.025872711660078e-10,
+    5.062783625763319e-10, 5.100013145066847e-10, 5.137568836234662e-10, 5.175458507405217e-10,
+    5.213690219244245e-10, 5.252272296620581e-10, 5.291213340948234e-10, 5.330522243241479e-10,
+    5.370208197933578e-10, 5.410280717513984e-10, 5.450749648043504e-10, 5.491625185611982e-10,
+    5.532917893808668e-10, 5.574638722281578e-10, 5.616799026468938e-10, 5.659410588593273e-10,
+    5.702485640016971e-10, 5.746036885067278e-10, 5.790077526448787e-10, 5.834621292372691e-10,
+    5.879682465544507e-10, 5.925275914165826e-10, 5.97141712512101e-10, 6.01812223953694e-10,
+    6.06540809092307e-10, 6.113292246120497e-10, 6.161793049312692e-10, 6.210929669377558e-10,
+    6.26072215089064e-10, 6.31119146912343e-10, 6.362359589419104e-10, 6.4142495313714e-10,
+    6.466885438281486e-10, 6.520292652423359e-10, 6.574497796711604e-10, 6.629528863437458e-10,
+    6.685415310821358e-10, 6.742188168224288e-10, 6.799880150968082e-10, 6.858525785838838e-10,
+    6.918161548490393e-10, 6.978826014129764e-10, 7.040560023057467e-10, 7.10340686285743e-10,
+    7.167412469289491e-10, 7.232625648239234e-10, 7.29909832143329e-10, 7.366885799043766e-10,
+    7.436047082795407e-10, 7.506645203768909e-10, 7.578747599782558e-10, 7.652426538055478e-10,
+    7.727759589838696e-10, 7.804830164881701e-10, 7.883728115028495e-10, 7.964550417966978e-10,
+    8.047401954263381e-10, 8.132396393395194e-10, 8.219657207674708e-10, 8.309318836890974e-10,
+    8.401528031399757e-10, 8.496445407534173e-10, 8.594247256958466e-10, 8.695127661432631e-10,
+    8.799300977056106e-10, 8.907004768313727e-10, 9.018503293393935e-10, 9.134091670009088e-10,
+    9.254100887742372e-10, 9.378903882224007e-10, 9.50892295317798e-10, 9.644638899862932e-10,
+    9.78660237448105e-10, 9.935448133101195e-10, 1.0091913119697238e-09, 1.0256859691519288e-09,
+    1.0431305846498463e-09, 1.0616465149697337e-09, 1.0813800351275404e-09, 1.1025096747562698e-09,
+    1.1252564706432517e-09, 1.1498986477733807e-09, 1.1767932423347028e-09, 1.2064090187897797e-09,
+    1.2393785886826128e-09, 1.2765849538906782e-09, 1.3193139264951723e-09, 1.3695434471116157e-09,
+    1.4305498138471953e-09, 1.5083650345524605e-09, 1.6160853275511056e-09, 1.7921248148501588e-09,
+];
+
+#[rustfmt::skip]
+const F: [f64; 256] = [
+    1.0, 0.9381436808621963, 0.9004699299257618, 0.8717043323812159,
+    0.847785500624, 0.8269932966430594, 0.8084216515230165, 0.7915276369725031,
+    0.7759568520401224, 0.7614633888499026, 0.7478686219852011, 0.7350380924314291,
+    0.7228676595935773, 0.711274760805081, 0.7001926550827929, 0.6895664961170825,
+    0.6793505722647697, 0.6695063167319288, 0.6600008410790036, 0.6508058334145748,
+    0.6418967164272696, 0.6332519942143695, 0.6248527387036692, 0.6166821809152108,
+    0.6087253820796251, 0.6009689663652352, 0.5934009016917363, 0.5860103184772708,
+    0.5787873586028477, 0.5717230486648284, 0.5648091929124027, 0.5580382822625899,
+    0.5514034165406436, 0.5448982376724418, 0.538516872002864, 0.5322538802630453,
+    0.5261042139836217, 0.5200631773682355, 0.5141263938147504, 0.5082897764106447,
+    0.5025495018413495, 0.49690198724155127, 0.4913438695940342, 0.4858719873418865,
+    0.48048336393045576, 0.4751751930373789, 0.4699448252839615, 0.4647897562504276,
+    0.4597076156421391, 0.45469615747461684, 0.44975325116275633, 0.44487687341454984,
+    0.4400651008423552, 0.43531610321563785, 0.43062813728846006, 0.42599954114303556,
+    0.4214287289976178, 0.41691418643300404, 0.4124544659971623, 0.40804818315203345,
+    0.40369401253053133, 0.3993906844752321, 0.39513698183329116, 0.3909317369847981,
+    0.38677382908413865, 0.3826621814960108, 0.37859575940958173, 0.37457356761590305,
+    0.3705946484351469, 0.36665807978151504, 0.36276297335481866, 0.35890847294875056,
+    0.3550937528667882, 0.351318016437484, 0.34758049462163765, 0.3438804447045031,
+    0.3402171490667807, 0.33658991402867827, 0.33299806876180965, 0.32944096426413705,
+    0.3259179723935569, 0.32242848495608983, 0.3189719128449579, 0.31554768522712956,
+    0.31215524877418016, 0.30879406693456074, 0.3054636192445908, 0.3021634006756941,
+    0.2988929210155823, 0.2956517042812617, 0.2924392881618931, 0.2892552234896782,
+    0.28609907373707727, 0.2829704145387812, 0.2798688332369733, 0.27679392844851775,
+    0.27374530965280336, 0.27072259679906047, 0.26772541993204524, 0.2647534188350626,
+    0.2618062426893633, 0.25888354974901656, 0.2559850070304157, 0.2531102900156298,
+    0.25025908236886263, 0.24743107566532793, 0.24462596913189236, 0.24184346939887746,
+    0.23908329026244937, 0.23634515245705984, 0.23362878343743348, 0.23093391716962755,
+    0.2282602939307168, 0.22560766011668415, 0.22297576805812028, 0.22036437584335958,
+    0.2177732471487006, 0.21520215107537877, 0.21265086199297836, 0.21011915938898837,
+    0.20760682772422212, 0.2051136562938378, 0.2026394390937091, 0.20018397469191135,
+    0.19774706610509893, 0.19532852067956327, 0.1929281499767714, 0.19054576966319545,
+    0.18818119940425435, 0.18583426276219714, 0.18350478709776744, 0.18119260347549626,
+    0.17889754657247828, 0.17661945459049483, 0.1743581691713534, 0.17211353531531998,
+    0.16988540130252755, 0.16767361861725008, 0.1654780418749359, 0.16329852875190168,
+    0.1611349399175919, 0.15898713896931407, 0.1568549923693651, 0.15473836938446794,
+    0.15263714202744272, 0.15055118500103976, 0.14848037564386662, 0.14642459387834475,
+    0.14438372216063458, 0.142357645432472, 0.14034625107486226, 0.13834942886358,
+    0.13636707092642864, 0.1343990717022134, 0.13244532790138733, 0.1305057384683306,
+    0.128580204545228, 0.1266686294375105, 0.12477091858083077, 0.12288697950954494,
+    0.12101672182667463, 0.11916005717532749, 0.11731689921155537, 0.11548716357863334,
+    0.11367076788274413, 0.11186763167005613, 0.11007767640518522, 0.1083008254510336,
+    0.10653700405000148, 0.10478613930657, 0.10304816017125756, 0.10132299742595349,
+    0.099610583670637, 0.09791085331149207, 0.09622374255043266, 0.09454918937605569,
+    0.09288713355604336, 0.09123751663103996, 0.08960028191003268, 0.08797537446727004,
+    0.08636274114075673, 0.08476233053236795, 0.08317409300963222, 0.08159798070923724,
+    0.08003394754231972, 0.07848194920160623, 0.07694194317048031, 0.0754138887340582,
+    0.07389774699236455, 0.07239348087570853, 0.07090105516237159, 0.0694204364987285,
+    0.06795159342193637, 0.06649449638533955, 0.06504911778675354, 0.0636154319998071,
+    0.06219341540854076, 0.06078304644547939, 0.059384305633420016, 0.0579971756312004,
+    0.05662164128374262, 0.05525768967669679, 0.053905310196045816, 0.05256449459307141,
+    0.05123523705512598, 0.049917534282706066, 0.0486113855733792, 0.04731679291318125,
+    0.04603376107617487, 0.04476229773294299, 0.04350241356888789, 0.042254122413315935,
+    0.04101744138041453, 0.03979239102337382, 0.038578995503074545, 0.03737728277295905,
+    0.03618728478193111, 0.03500903769739709, 0.03384258215087401, 0.03268796350895922,
+    0.03154523217289329, 0.030414443910466285, 0.02929566022463707, 0.028188948763978306,
+    0.027094383780955467, 0.026012046645133884, 0.024942026419731454, 0.023884420511557845,
+    0.022839335406384914, 0.02180688750428326, 0.0207872040725778, 0.019780424338009424,
+    0.018786700744695708, 0.01780620041091104, 0.016839106826039625, 0.015885621839972847,
+    0.01494596801169083, 0.014020391403181618, 0.013109164931254677, 0.012212592426255064,
+    0.011331013597834288, 0.010464810181029675, 0.009614413642501905, 0.008780314985808673,
+    0.00796307743801674, 0.0071633531836346855, 0.006381905937318883, 0.005619642207205189,
+    0.004877655983542105, 0.0041572951208335126, 0.0034602647778366304, 0.0027887987935738107,
+    0.0021459677437186517, 0.0015362997803013297, 0.0009672692823269484, 0.00045413435384129814,
+];
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
         ($lambda:expr) => (Exponential::new($lambda));
     );
 
+    #[test]
+    fn characteristic() {
+        let d = new!(2.0);
+        assert_eq!(d.characteristic(0.0), (1.0, 0.0));
+        assert_eq!(d.characteristic(2.0), (0.5, 0.5));
+        assert_eq!(d.characteristic(-2.0), (0.5, -0.5));
+    }
+
     #[test]
     fn density() {
         let d = new!(2.0);
@@ -160,6 +545,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn density_slice() {
+        let d = new!(2.0);
+        let x = (-20..60).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let mut density = vec![0.0; x.len()];
+        let mut distribution = vec![0.0; x.len()];
+        d.density_slice(&x, &mut density);
+        d.distribution_slice(&x, &mut distribution);
+        for (i, &x) in x.iter().enumerate() {
+            assert::close(density[i], d.density(x), 1e-15);
+            assert::close(distribution[i], d.distribution(x), 1e-15);
+        }
+    }
+
     #[test]
     fn distribution() {
         let d = new!(2.0);
@@ -197,11 +596,21 @@ mod tests {
 
     #[test]
     fn inverse() {
-        use core::f64::INFINITY;
-
         let d = new!(2.0);
         let x = vec![
-            0.0, 0.01, 0.05, 0.1, 0.15, 0.25, 0.5, 1.0, 1.5, 2.0, 3.0, 4.0, INFINITY,
+            0.0,
+            0.01,
+            0.05,
+            0.1,
+            0.15,
+            0.25,
+            0.5,
+            1.0,
+            1.5,
+            2.0,
+            3.0,
+            4.0,
+            f64::INFINITY,
         ];
         let p = vec![
             0.000000000000000e+00,
@@ -231,6 +640,43 @@ mod tests {
         assert_eq!(new!(2.0).kurtosis(), 6.0);
     }
 
+    #[test]
+    fn log_density() {
+        let d = new!(2.0);
+        assert_eq!(d.log_density(-1.0), f64::NEG_INFINITY);
+        for &x in &[0.0, 0.5, 3.0] {
+            assert::close(d.log_density(x), d.density(x).ln(), 1e-14);
+        }
+        assert::close(d.log_density(1000.0), -1999.3068528194400547, 1e-10);
+    }
+
+    #[test]
+    fn log_distribution() {
+        let d = new!(2.0);
+        assert::close(d.log_distribution(1e-10), -22.332703749480512, 1e-12);
+        assert_eq!(d.log_distribution(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_survival() {
+        let d = new!(2.0);
+        assert_eq!(d.log_survival(1000.0), -2000.0);
+        assert_eq!(d.log_survival(-1.0), 0.0);
+    }
+
+    #[test]
+    fn maximum_likelihood() {
+        let estimate = estimate::maximum_likelihood::<Exponential>(&[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(estimate.parameters, vec![0.5]);
+        assert::close(&estimate.errors, &[0.5 / 3f64.sqrt()], 1e-15);
+        assert::close(
+            estimate.report.log_likelihood,
+            3.0 * (0.5f64.ln() - 1.0),
+            1e-15,
+        );
+        assert!(estimate::maximum_likelihood::<Exponential>(&[1.0, -1.0]).is_none());
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0).mean(), 0.5);
@@ -242,16 +688,67 @@ mod tests {
         assert_eq!(new!(LN_2).median(), 1.0);
     }
 
+    #[test]
+    fn mgf() {
+        let d = new!(2.0);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert_eq!(d.mgf(1.0), 2.0);
+        assert_eq!(d.log_mgf(-2.0), -(2f64.ln()));
+        assert!(d.mgf(2.0).is_nan());
+        assert!(d.log_mgf(3.0).is_nan());
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(2.0).modes(), vec![0.0]);
     }
 
+    #[test]
+    fn sample() {
+        let d = new!(2.0);
+        let mut source = source::default(42);
+        let samples = Independent(&d, &mut source)
+            .take(100000)
+            .collect::<Vec<_>>();
+        assert!(samples.iter().all(|&x| x >= 0.0 && x.is_finite()));
+        let mean = samples.iter().fold(0.0, |sum, &x| sum + x) / 100000.0;
+        assert::close(mean, 0.5, 0.01);
+        for &x in [0.1, 0.5, 1.0, 2.0, 4.0].iter() {
+            let count = samples.iter().filter(|&&y| y <= x).count();
+            assert::close(count as f64 / 100000.0, d.distribution(x), 0.01);
+        }
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(2.0).skewness(), 2.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 10.0, 100.0];
+        let p = vec![
+            1.000000000000000e+00,
+            1.000000000000000e+00,
+            3.678794411714423e-01,
+            1.353352832366127e-01,
+            2.061153622438558e-09,
+            1.383896526736738e-87,
+        ];
+
+        assert::close(
+            &x.iter()
+                .zip(&p)
+                .map(|(&x, &p)| d.survival(x) / p)
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+        assert_eq!(d.hazard(42.0), 2.0);
+        assert_eq!(d.cumulative_hazard(42.0), 84.0);
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(2.0).variance(), 0.25);
@@ -1,12 +1,15 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// A gamma distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Gamma {
     k: f64,
     theta: f64,
@@ -25,10 +28,20 @@ impl Gamma {
         Gamma {
             k,
             theta,
-            norm: k.gamma() * theta.powf(k),
+            norm: SpecialGamma::gamma(k) * math::powf(theta, k),
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(k: f64, theta: f64) -> Result<Self, Error> {
+        error::positive("k", k)?;
+        error::positive("theta", theta)?;
+        Ok(Gamma::new(k, theta))
+    }
+
     /// Return the shape parameter.
     #[inline(always)]
     pub fn k(&self) -> f64 {
@@ -42,12 +55,34 @@ impl Gamma {
     }
 }
 
+impl distribution::CharacteristicFunction for Gamma {
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let x = self.theta * t;
+        let modulus = math::ln_1p(x * x) * (-0.5 * self.k);
+        let argument = self.k * math::atan(x);
+        let modulus = math::exp(modulus);
+        (modulus * libm::cos(argument), modulus * libm::sin(argument))
+    }
+}
+
 impl distribution::Continuous for Gamma {
     fn density(&self, x: f64) -> f64 {
         if x <= 0.0 {
             0.0
         } else {
-            x.powf(self.k - 1.0) * (-x / self.theta).exp() / self.norm
+            math::powf(x, self.k - 1.0) * math::exp(-x / self.theta) / self.norm
+        }
+    }
+
+    fn log_density(&self, x: f64) -> f64 {
+        use special::Gamma;
+        if x <= 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            (self.k - 1.0) * math::ln(x)
+                - x / self.theta
+                - Gamma::ln_gamma(self.k).0
+                - self.k * math::ln(self.theta)
         }
     }
 }
@@ -63,12 +98,92 @@ impl distribution::Distribution for Gamma {
             (x / self.theta).inc_gamma(self.k)
         }
     }
+
+    fn log_distribution(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            f64::NEG_INFINITY
+        } else {
+            ln_inc_gamma(x / self.theta, self.k)
+        }
+    }
 }
 
 impl distribution::Entropy for Gamma {
     fn entropy(&self) -> f64 {
         use special::Gamma;
-        self.k + self.theta.ln() + self.k.ln_gamma().0 + (1.0 - self.k) * self.k.digamma()
+        self.k
+            + math::ln(self.theta)
+            + Gamma::ln_gamma(self.k).0
+            + (1.0 - self.k) * self.k.digamma()
+    }
+}
+
+impl distribution::Inverse for Gamma {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The inverse is computed by Newton’s method safeguarded by bisection
+    /// and started from the Wilson–Hilferty approximation. Far in the lower
+    /// tail, Newton’s method is applied to the logarithm of the function
+    /// started from the leading term of its series expansion, which retains
+    /// the relative accuracy down to the smallest positive `f64`. No
+    /// extended-precision arithmetic is involved, and the upper tail is limited
+    /// by the resolution of `p` near one.
+    fn inverse(&self, p: f64) -> f64 {
+        use distribution::{self, gaussian};
+        use special::Gamma as SpecialGamma;
+        const ITERATIONS: usize = 100;
+        const TOLERANCE: f64 = 1e-15;
+
+        should!((0.0..=1.0).contains(&p));
+        if p <= 0.0 {
+            return 0.0;
+        }
+        if p >= 1.0 {
+            return f64::INFINITY;
+        }
+        let k = self.k;
+        let ln_gamma = SpecialGamma::ln_gamma(k).0;
+        if p < TAIL {
+            let ln_p = math::ln(p);
+            let x = math::exp((ln_p + SpecialGamma::ln_gamma(k + 1.0).0) / k);
+            let x = distribution::invert_lower_tail(ln_p, x, f64::INFINITY, |x| {
+                let ln_distribution = ln_inc_gamma(x, k);
+                (ln_distribution, k * math::ln(x) - x - ln_gamma)
+            });
+            return x * self.theta;
+        }
+        let c = 1.0 / (9.0 * k);
+        let mut x = k * math::powi(1.0 - c + gaussian::inverse(p) * c.sqrt(), 3);
+        if x <= 0.0 {
+            x = math::exp((math::ln(p) + SpecialGamma::ln_gamma(k + 1.0).0) / k);
+        }
+        let (mut low, mut high) = (0.0, f64::INFINITY);
+        for _ in 0..ITERATIONS {
+            let error = x.inc_gamma(k) - p;
+            if error == 0.0 {
+                break;
+            }
+            if error < 0.0 {
+                low = x;
+            } else {
+                high = x;
+            }
+            let density = math::exp((k - 1.0) * math::ln(x) - x - ln_gamma);
+            let mut next = x - error / density;
+            if next.is_nan() || next <= low || next >= high {
+                next = if high.is_finite() {
+                    0.5 * (low + high)
+                } else {
+                    2.0 * x
+                };
+            }
+            let done = (next - x).abs() <= TOLERANCE * x;
+            x = next;
+            if done {
+                break;
+            }
+        }
+        x * self.theta
     }
 }
 
@@ -86,12 +201,34 @@ impl distribution::Mean for Gamma {
     }
 }
 
-impl distribution::Modes for Gamma {
-    fn modes(&self) -> Vec<f64> {
+impl distribution::Median for Gamma {
+    #[inline]
+    fn median(&self) -> f64 {
+        use distribution::Inverse;
+        self.inverse(0.5)
+    }
+}
+
+impl distribution::Mgf for Gamma {
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        if t < self.theta.recip() {
+            -self.k * math::ln_1p(-self.theta * t)
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl distribution::ModesIter for Gamma {
+    type Iter<'l> = distribution::ModePair<f64>;
+
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        use distribution::ModePair;
         if self.k >= 1.0 {
-            vec![(self.k - 1.0) * self.theta]
+            ModePair::one((self.k - 1.0) * self.theta)
         } else {
-            vec![]
+            ModePair::none()
         }
     }
 }
@@ -120,6 +257,31 @@ impl distribution::Skewness for Gamma {
     }
 }
 
+impl distribution::Support for Gamma {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Gamma {
+    fn survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            compl_inc_gamma(x / self.theta, self.k)
+        }
+    }
+
+    fn log_survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            ln_compl_inc_gamma(x / self.theta, self.k)
+        }
+    }
+}
+
 impl distribution::Variance for Gamma {
     #[inline]
     fn variance(&self) -> f64 {
@@ -127,12 +289,80 @@ impl distribution::Variance for Gamma {
     }
 }
 
+impl estimate::MaximumLikelihood for Gamma {
+    /// Estimate the parameters from a sample.
+    ///
+    /// The scale parameter is eliminated from the likelihood equations, and
+    /// the remaining equation for the shape parameter, which involves the
+    /// digamma function, is solved by Newton’s method starting from the
+    /// method-of-moments estimate. All the values should be positive.
+    fn maximum_likelihood(data: &[f64]) -> Option<Estimate<Self>> {
+        use special::Gamma as SpecialGamma;
+        const ITERATIONS: usize = 100;
+        const TOLERANCE: f64 = 1e-12;
+
+        let moments = estimate::moments::<Gamma>(data)?;
+        let n = data.len() as f64;
+        let mean = estimate::mean(data);
+        let ln_mean = data.iter().fold(0.0, |sum, &x| sum + math::ln(x)) / n;
+        let s = math::ln(mean) - ln_mean;
+        let mut k = moments.k;
+        let mut iterations = 0;
+        let mut converged = false;
+        while iterations < ITERATIONS {
+            iterations += 1;
+            let value = math::ln(k) - SpecialGamma::digamma(k) - s;
+            let slope = 1.0 / k - SpecialGamma::trigamma(&k);
+            let mut delta = value / slope;
+            while k - delta <= 0.0 {
+                delta /= 2.0;
+            }
+            k -= delta;
+            if delta.abs() <= TOLERANCE * k {
+                converged = true;
+                break;
+            }
+        }
+        let theta = mean / k;
+        let log_likelihood =
+            n * ((k - 1.0) * ln_mean - k - SpecialGamma::ln_gamma(k).0 - k * math::ln(theta));
+        let hessian = [
+            [-n * SpecialGamma::trigamma(&k), -n / theta],
+            [-n / theta, -n * k / (theta * theta)],
+        ];
+        Some(Estimate {
+            distribution: Gamma::new(k, theta),
+            parameters: vec![k, theta],
+            errors: estimate::errors(hessian),
+            report: Report {
+                iterations,
+                log_likelihood,
+                converged,
+            },
+        })
+    }
+}
+
+impl estimate::MethodOfMoments for Gamma {
+    fn moments(data: &[f64]) -> Option<Self> {
+        if data.len() < 2 || data.iter().any(|&x| x <= 0.0) {
+            return None;
+        }
+        let mean = estimate::mean(data);
+        let variance = estimate::variance(data, mean);
+        if variance == 0.0 {
+            return None;
+        }
+        Some(Gamma::new(mean * mean / variance, variance / mean))
+    }
+}
+
 /// Draw a sample from the standard Gamma distribution.
 pub fn sample<S: Source>(k: f64, source: &mut S) -> f64 {
     use distribution::gaussian;
 
     if k < 1.0 {
-        return sample(1.0 + k, source) * source.read::<f64>().powf(1.0 / k);
+        return sample(1.0 + k, source) * math::powf(source.read::<f64>(), 1.0 / k);
     }
 
     let d = k - 1.0 / 3.0;
@@ -157,7 +387,7 @@ pub fn sample<S: Source>(k: f64, source: &mut S) -> f64 {
             if u < 1.0 - 0.0331 * x * x {
                 return d * v;
             }
-            if u.ln() < 0.5 * x + d * (1.0 - v + v.ln()) {
+            if math::ln(u) < 0.5 * x + d * (1.0 - v + math::ln(v)) {
                 return d * v;
             }
 
@@ -166,16 +396,143 @@ pub fn sample<S: Source>(k: f64, source: &mut S) -> f64 {
     }
 }
 
+/// Compute the regularized upper incomplete gamma function.
+///
+/// In the region where the lower function is computed via a series expansion,
+/// the result is obtained by complementing it. Otherwise, the continued
+/// fraction is evaluated directly, which avoids cancellation in the tail.
+///
+/// ## References
+///
+/// 1. B. L. Shea, “Algorithm AS 239: Chi-squared and incomplete gamma
+///    integral,” Journal of the Royal Statistical Society. Series C (Applied
+///    Statistics), vol. 37, no. 3, pp. 466–473, 1988.
+pub fn compl_inc_gamma(x: f64, p: f64) -> f64 {
+    use special::Gamma;
+
+    nonnan!(x);
+    if x == f64::INFINITY {
+        return 0.0;
+    }
+    if x <= 1.0 || x < p {
+        return 1.0 - x.inc_gamma(p);
+    }
+    math::exp(ln_compl_inc_gamma(x, p))
+}
+
+/// The probability below which quantiles are computed in the logarithmic
+/// domain.
+const TAIL: f64 = 1e-10;
+
+/// Compute the natural logarithm of the regularized lower incomplete gamma
+/// function.
+///
+/// Below the mean, the series expansion is evaluated with the prefactor kept
+/// in the logarithmic domain, which avoids the underflow in the lower tail.
+/// Otherwise, the logarithm of the complement of the upper function is taken.
+fn ln_inc_gamma(x: f64, p: f64) -> f64 {
+    use special::Gamma;
+
+    if x >= p + 1.0 {
+        return math::ln_1p(-compl_inc_gamma(x, p));
+    }
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut n = 1.0;
+    while term > f64::EPSILON * sum {
+        term *= x / (p + n);
+        sum += term;
+        n += 1.0;
+    }
+    p * math::ln(x) - x - Gamma::ln_gamma(p + 1.0).0 + math::ln(sum)
+}
+
+/// Compute the natural logarithm of the regularized upper incomplete gamma
+/// function.
+///
+/// In the region of the continued fraction, the prefactor is kept in the
+/// logarithmic domain, which avoids the underflow in the upper tail. The
+/// convergents are normalized at each step, which keeps them finite for
+/// arbitrarily large `x`.
+fn ln_compl_inc_gamma(x: f64, p: f64) -> f64 {
+    use special::Gamma;
+
+    const ITERATIONS: usize = 100_000;
+    const TOL: f64 = 1e-14;
+
+    nonnan!(x);
+    if x == f64::INFINITY {
+        return f64::NEG_INFINITY;
+    }
+    if x <= 1.0 || x < p {
+        return math::ln_1p(-x.inc_gamma(p));
+    }
+
+    let mut a = 1.0 - p;
+    let mut b = a + x + 1.0;
+    let mut c = 0.0;
+    let mut pn1 = 1.0 / x / b;
+    let mut pn2 = 1.0 / b;
+    let mut pn3 = (1.0 + 1.0 / x) / b;
+    let mut pn4 = 1.0;
+    let mut value = pn3;
+
+    for _ in 0..ITERATIONS {
+        a += 1.0;
+        b += 2.0;
+        c += 1.0;
+        let an = a * c;
+        let pn5 = b * pn3 - an * pn1;
+        let pn6 = b * pn4 - an * pn2;
+        if pn6 != 0.0 {
+            let rn = pn5 / pn6;
+            if (value - rn).abs() <= TOL.min(TOL * rn) {
+                break;
+            }
+            value = rn;
+        }
+        pn1 = pn3;
+        pn2 = pn4;
+        pn3 = pn5;
+        pn4 = pn6;
+        let scale = pn4.abs();
+        if scale != 0.0 {
+            pn1 /= scale;
+            pn2 /= scale;
+            pn3 /= scale;
+            pn4 /= scale;
+        }
+    }
+
+    p * math::ln(x) - x - Gamma::ln_gamma(p).0 + math::ln(value)
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
         ($k:expr, $theta:expr) => (Gamma::new($k, $theta));
     );
 
+    #[test]
+    fn characteristic() {
+        use distribution;
+
+        let d = new!(9.0, 0.5);
+        let x = vec![1.0, 2.0, 4.0, 6.0, 10.0];
+        assert::close(
+            &x.iter()
+                .map(|&x| distribution::invert_characteristic(&d, x))
+                .collect::<Vec<_>>(),
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            1e-10,
+        );
+    }
+
     #[test]
     fn density() {
         let d = new!(9.0, 0.5);
@@ -213,6 +570,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn density_slice() {
+        let d = new!(9.0, 0.5);
+        let x = (-20..60).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let mut density = vec![0.0; x.len()];
+        let mut distribution = vec![0.0; x.len()];
+        d.density_slice(&x, &mut density);
+        d.distribution_slice(&x, &mut distribution);
+        for (i, &x) in x.iter().enumerate() {
+            assert::close(density[i], d.density(x), 1e-15);
+            assert::close(distribution[i], d.distribution(x), 1e-15);
+        }
+    }
+
     #[test]
     fn distribution() {
         let d = new!(9.0, 0.5);
@@ -260,26 +631,169 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse() {
+        let k = vec![0.1, 0.5, 1.0, 5.0, 100.0];
+        let p = vec![1e-10, 0.01, 0.25, 0.5, 0.75, 0.99, 1.0 - 1e-10];
+        for &k in k.iter() {
+            let d = new!(k, 2.0);
+            assert::close(
+                &p.iter()
+                    .map(|&p| d.distribution(d.inverse(p)))
+                    .collect::<Vec<_>>(),
+                &p,
+                1e-12,
+            );
+        }
+        assert_eq!(new!(2.0, 1.0).inverse(0.0), 0.0);
+        assert_eq!(new!(2.0, 1.0).inverse(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn inverse_tail() {
+        let d = new!(9.0, 0.5);
+        let p = vec![1e-20, 1e-100, 1e-300];
+        let x = vec![
+            0.012461821536612886,
+            1.605500116157917e-11,
+            9.62472033518811e-34,
+        ];
+        for (&p, &x) in p.iter().zip(&x) {
+            assert::close(d.inverse(p) / x, 1.0, 1e-13);
+            assert::close(d.log_distribution(x), p.ln(), 1e-10);
+        }
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(3.0, 1.5).kurtosis(), 2.0);
     }
 
+    #[test]
+    fn log_density() {
+        let d = new!(9.0, 0.5);
+        assert_eq!(d.log_density(0.0), f64::NEG_INFINITY);
+        for &x in &[0.5, 2.0, 6.5] {
+            assert::close(d.log_density(x), d.density(x).ln(), 1e-12);
+        }
+        assert::close(d.log_density(1000.0), -1949.104236045848646, 1e-10);
+        let d = new!(200.0, 1.0);
+        assert::close(d.log_density(200.0), -3.5685138827981380051, 1e-10);
+    }
+
+    #[test]
+    fn log_distribution() {
+        let d = new!(9.0, 0.5);
+        assert::close(d.log_distribution(0.001), -68.7351003495161, 1e-12);
+        assert::close(d.log_distribution(5.0), -0.4046949229030263, 1e-13);
+        assert_eq!(d.log_distribution(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_survival() {
+        let d = new!(9.0, 0.5);
+        assert::close(d.log_survival(500.0), -955.3345366122386, 1e-10);
+        assert::close(d.log_survival(20.0), 8.569271064141691e-10f64.ln(), 1e-10);
+        assert_eq!(d.log_survival(0.0), 0.0);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(9.0, 0.5).mean(), 4.5);
     }
 
+    #[test]
+    fn median() {
+        assert::close(new!(1.0, 2.0).median(), 2.0 * 2f64.ln(), 1e-14);
+        assert::close(new!(3.0, 1.0).median(), 2.674060313723561, 1e-12);
+    }
+
+    #[test]
+    fn mgf() {
+        let d = new!(9.0, 0.5);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert::close(d.mgf(1.0), 512.0, 1e-12);
+        assert!(d.mgf(2.0).is_nan());
+    }
+
+    #[test]
+    fn maximum_likelihood() {
+        let mut source = source::default(42);
+        let data = Independent(&new!(2.0, 3.0), &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        let estimate = estimate::maximum_likelihood::<Gamma>(&data).unwrap();
+        assert!(estimate.report.converged);
+        assert::close(&estimate.parameters, &[2.0, 3.0], 0.1);
+        assert::close(
+            estimate.report.log_likelihood,
+            data.iter()
+                .fold(0.0, |sum, &x| sum + estimate.distribution.density(x).ln()),
+            1e-6,
+        );
+        let (k, theta) = (estimate.parameters[0], estimate.parameters[1]);
+        for &(k, theta) in &[(k * 1.01, theta), (k, theta * 1.01)] {
+            let log_likelihood = data
+                .iter()
+                .fold(0.0, |sum, &x| sum + new!(k, theta).density(x).ln());
+            assert!(log_likelihood < estimate.report.log_likelihood);
+        }
+        assert::close(estimate.errors[0], 0.027, 0.005);
+        assert!(estimate::maximum_likelihood::<Gamma>(&[1.0, 0.0]).is_none());
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(5.5, 1.5).modes(), vec![6.75]);
     }
 
+    #[test]
+    fn moments() {
+        let d = estimate::moments::<Gamma>(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert::close(d.k(), 5.0, 1e-14);
+        assert::close(d.theta(), 0.5, 1e-15);
+        assert!(estimate::moments::<Gamma>(&[1.0, -1.0]).is_none());
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(4.0, 1.5).skewness(), 1.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(9.0, 0.5);
+        let x = vec![0.0, 1.0, 4.0, 10.0, 20.0, 40.0];
+        let p = vec![
+            1.000000000000000e+00,
+            9.997625526717389e-01,
+            5.925473414375914e-01,
+            2.087259049135019e-03,
+            8.569271064141691e-10,
+            8.331981903289990e-25,
+        ];
+
+        assert::close(
+            &x.iter()
+                .zip(&p)
+                .map(|(&x, &p)| d.survival(x) / p)
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn survival_extreme() {
+        let d = new!(2.0, 1.0);
+        assert_eq!(d.survival(f64::INFINITY), 0.0);
+        assert_eq!(d.log_survival(f64::INFINITY), f64::NEG_INFINITY);
+        assert!(d.survival(f64::NAN).is_nan());
+        assert_eq!(d.survival(1e308), 0.0);
+        assert::close(d.log_survival(1e308), 1e308f64.ln() - 1e308, 1e292);
+        assert::close(d.log_survival(1e10) / (1e10f64.ln() - 1e10), 1.0, 1e-15);
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(9.0, 0.5).variance(), 2.25);
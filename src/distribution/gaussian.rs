@@ -3,10 +3,13 @@ use alloc::{vec, vec::Vec};
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// A Gaussian distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Gaussian {
     mu: f64,
     sigma: f64,
@@ -29,6 +32,16 @@ impl Gaussian {
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, Error> {
+        error::finite("mu", mu)?;
+        error::positive("sigma", sigma)?;
+        Ok(Gaussian::new(mu, sigma))
+    }
+
     /// Return the mean.
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -49,19 +62,59 @@ impl Default for Gaussian {
     }
 }
 
+impl distribution::CharacteristicFunction for Gaussian {
+    #[inline]
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let modulus = math::exp(-0.5 * math::powi(self.sigma * t, 2));
+        let x = self.mu * t;
+        (modulus * libm::cos(x), modulus * libm::sin(x))
+    }
+}
+
 impl distribution::Continuous for Gaussian {
     fn density(&self, x: f64) -> f64 {
-        (-(x - self.mu).powi(2) / (2.0 * self.sigma * self.sigma)).exp() / self.norm
+        math::exp(-math::powi(x - self.mu, 2) / (2.0 * self.sigma * self.sigma)) / self.norm
+    }
+
+    #[inline]
+    fn log_density(&self, x: f64) -> f64 {
+        -math::powi(x - self.mu, 2) / (2.0 * self.sigma * self.sigma) - math::ln(self.norm)
+    }
+
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        let (mu, scale, norm) = (self.mu, -0.5 / (self.sigma * self.sigma), self.norm.recip());
+        for (value, &x) in out.iter_mut().zip(xs) {
+            let y = x - mu;
+            *value = math::exp(scale * y * y) * norm;
+        }
     }
 }
 
 impl distribution::Distribution for Gaussian {
     type Value = f64;
 
+    /// Compute the cumulative distribution function.
+    ///
+    /// The function is computed via the complementary error function, which
+    /// retains the relative accuracy in the lower tail.
     fn distribution(&self, x: f64) -> f64 {
         use core::f64::consts::SQRT_2;
-        use special::Error;
-        (1.0 + ((x - self.mu) / (self.sigma * SQRT_2)).error()) / 2.0
+        tail(-(x - self.mu) / (self.sigma * SQRT_2))
+    }
+
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        use core::f64::consts::FRAC_1_SQRT_2;
+        should!(xs.len() == out.len());
+        let (mu, scale) = (self.mu, -FRAC_1_SQRT_2 / self.sigma);
+        for (value, &x) in out.iter_mut().zip(xs) {
+            *value = tail(scale * (x - mu));
+        }
+    }
+
+    fn log_distribution(&self, x: f64) -> f64 {
+        use core::f64::consts::SQRT_2;
+        ln_tail(-(x - self.mu) / (self.sigma * SQRT_2))
     }
 }
 
@@ -69,7 +122,7 @@ impl distribution::Entropy for Gaussian {
     #[inline]
     fn entropy(&self) -> f64 {
         use core::f64::consts::{E, PI};
-        0.5 * (2.0 * PI * E * self.sigma * self.sigma).ln()
+        0.5 * math::ln(2.0 * PI * E * self.sigma * self.sigma)
     }
 }
 
@@ -110,10 +163,19 @@ impl distribution::Median for Gaussian {
     }
 }
 
-impl distribution::Modes for Gaussian {
+impl distribution::Mgf for Gaussian {
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![self.mu]
+    fn log_mgf(&self, t: f64) -> f64 {
+        t * (self.mu + 0.5 * self.sigma * self.sigma * t)
+    }
+}
+
+impl distribution::ModesIter for Gaussian {
+    type Iter<'l> = distribution::ModePair<f64>;
+
+    #[inline]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(self.mu)
     }
 }
 
@@ -143,6 +205,25 @@ impl distribution::Skewness for Gaussian {
     }
 }
 
+impl distribution::Support for Gaussian {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Gaussian {
+    fn survival(&self, x: f64) -> f64 {
+        use core::f64::consts::SQRT_2;
+        tail((x - self.mu) / (self.sigma * SQRT_2))
+    }
+
+    fn log_survival(&self, x: f64) -> f64 {
+        use core::f64::consts::SQRT_2;
+        ln_tail((x - self.mu) / (self.sigma * SQRT_2))
+    }
+}
+
 impl distribution::Variance for Gaussian {
     #[inline]
     fn variance(&self) -> f64 {
@@ -155,15 +236,48 @@ impl distribution::Variance for Gaussian {
     }
 }
 
+impl estimate::MaximumLikelihood for Gaussian {
+    fn maximum_likelihood(data: &[f64]) -> Option<Estimate<Self>> {
+        use core::f64::consts::PI;
+        if data.len() < 2 {
+            return None;
+        }
+        let n = data.len() as f64;
+        let mu = estimate::mean(data);
+        let sigma = estimate::variance(data, mu).sqrt();
+        if sigma == 0.0 {
+            return None;
+        }
+        Some(Estimate {
+            distribution: Gaussian::new(mu, sigma),
+            parameters: vec![mu, sigma],
+            errors: vec![sigma / n.sqrt(), sigma / (2.0 * n).sqrt()],
+            report: Report::closed(-0.5 * n * (math::ln(2.0 * PI * sigma * sigma) + 1.0)),
+        })
+    }
+}
+
+impl estimate::MethodOfMoments for Gaussian {
+    fn moments(data: &[f64]) -> Option<Self> {
+        if data.len() < 2 {
+            return None;
+        }
+        let mu = estimate::mean(data);
+        let sigma = estimate::variance(data, mu).sqrt();
+        if sigma == 0.0 {
+            return None;
+        }
+        Some(Gaussian::new(mu, sigma))
+    }
+}
+
 impl core::iter::FromIterator<f64> for Gaussian {
     /// Infer the distribution from an iterator.
     fn from_iter<T: IntoIterator<Item = f64>>(iterator: T) -> Self {
         let samples: Vec<f64> = iterator.into_iter().collect();
         let mu = samples.iter().fold(0.0, |a, b| a + b) / samples.len() as f64;
         let sigma = f64::sqrt(
-            samples
-                .iter()
-                .fold(0.0, |a, b| a + f64::powf(b - mu as f64, 2.0))
+            samples.iter().fold(0.0, |a, b| a + f64::powf(b - mu, 2.0))
                 / (samples.len() - 1) as f64,
         );
         Gaussian::new(mu, sigma)
@@ -174,8 +288,6 @@ impl core::iter::FromIterator<f64> for Gaussian {
 /// Gaussian distribution.
 #[allow(clippy::excessive_precision)]
 pub fn inverse(p: f64) -> f64 {
-    use core::f64::{INFINITY, NEG_INFINITY};
-
     should!((0.0..=1.0).contains(&p));
 
     const CONST1: f64 = 0.180625;
@@ -251,10 +363,10 @@ pub fn inverse(p: f64) -> f64 {
     }
 
     if p <= 0.0 {
-        return NEG_INFINITY;
+        return f64::NEG_INFINITY;
     }
     if 1.0 <= p {
-        return INFINITY;
+        return f64::INFINITY;
     }
 
     let q = p - 0.5;
@@ -266,7 +378,7 @@ pub fn inverse(p: f64) -> f64 {
 
     let mut x = if q < 0.0 { p } else { 1.0 - p };
 
-    x = (-x.ln()).sqrt();
+    x = (-math::ln(x)).sqrt();
 
     if x <= SPLIT2 {
         x -= CONST2;
@@ -283,6 +395,58 @@ pub fn inverse(p: f64) -> f64 {
     }
 }
 
+/// Compute `erfc(x) / 2`.
+///
+/// Far in the tail, the result is computed from the scaled complementary error
+/// function with the square in the exponent split into an exact and a small
+/// part.
+#[inline]
+fn tail(x: f64) -> f64 {
+    use special::Error;
+    if x < SCALED || x.is_infinite() {
+        return x.compl_error() / 2.0;
+    }
+    let high = libm::trunc(x * 16.0) / 16.0;
+    0.5 * compl_error_scaled(x) * math::exp(-high * high) * math::exp(-(x - high) * (x + high))
+}
+
+/// Compute `ln(erfc(x) / 2)`.
+///
+/// Far in the tail, the logarithm is computed from the scaled complementary
+/// error function without exponentiating the square, and, for negative `x`,
+/// the logarithm of the complement is computed via `ln_1p`.
+#[inline]
+fn ln_tail(x: f64) -> f64 {
+    if x < 0.0 {
+        math::ln_1p(-tail(-x))
+    } else if x < SCALED || x.is_infinite() {
+        math::ln(tail(x))
+    } else {
+        math::ln(0.5 * compl_error_scaled(x)) - x * x
+    }
+}
+
+/// Compute the scaled complementary error function `exp(x²) erfc(x)` for `x`
+/// not smaller than `SCALED` via its continued fraction.
+///
+/// ## References
+///
+/// 1. M. Abramowitz and I. A. Stegun, Handbook of Mathematical Functions,
+///    formula 7.1.14, 1964.
+fn compl_error_scaled(x: f64) -> f64 {
+    use core::f64::consts::FRAC_2_SQRT_PI;
+    should!(x >= SCALED);
+    let mut fraction = x;
+    for k in (1..=60).rev() {
+        fraction = x + 0.5 * k as f64 / fraction;
+    }
+    0.5 * FRAC_2_SQRT_PI / fraction
+}
+
+/// The argument above which the complementary error function is computed via
+/// its scaled counterpart.
+const SCALED: f64 = 3.0;
+
 /// Draw a sample from the standard Gaussian distribution.
 pub fn sample<S: Source>(source: &mut S) -> f64 {
     loop {
@@ -302,12 +466,12 @@ pub fn sample<S: Source>(source: &mut S) -> f64 {
             let y = Y[i + 1] + (Y[i] - Y[i + 1]) * source.read::<f64>();
             (x, y)
         } else {
-            let x = R - (-source.read::<f64>()).ln_1p() / R;
-            let y = (-R * (x - 0.5 * R)).exp() * source.read::<f64>();
+            let x = R - math::ln_1p(-source.read::<f64>()) / R;
+            let y = math::exp(-R * (x - 0.5 * R)) * source.read::<f64>();
             (x, y)
         };
 
-        if y < (-0.5 * x * x).exp() {
+        if y < math::exp(-0.5 * x * x) {
             return s * x;
         }
     }
@@ -429,12 +593,29 @@ mod tests {
 
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
         ($mu:expr, $sigma:expr) => (Gaussian::new($mu, $sigma));
     );
 
+    #[test]
+    fn characteristic() {
+        use distribution;
+
+        let d = new!(1.0, 2.0);
+        let x = vec![-5.0, -1.0, 0.0, 1.0, 2.5, 4.0, 8.0];
+        assert::close(
+            &x.iter()
+                .map(|&x| distribution::invert_characteristic(&d, x))
+                .collect::<Vec<_>>(),
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            1e-10,
+        );
+        assert_eq!(d.characteristic(0.0), (1.0, 0.0));
+    }
+
     #[test]
     fn density() {
         let d = new!(1.0, 2.0);
@@ -469,6 +650,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn density_slice() {
+        let d = new!(1.0, 2.0);
+        let x = (-20..60).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let mut density = vec![0.0; x.len()];
+        let mut distribution = vec![0.0; x.len()];
+        d.density_slice(&x, &mut density);
+        d.distribution_slice(&x, &mut distribution);
+        for (i, &x) in x.iter().enumerate() {
+            assert::close(density[i], d.density(x), 1e-15);
+            assert::close(distribution[i], d.distribution(x), 1e-15);
+        }
+    }
+
     #[test]
     fn distribution() {
         let d = new!(1.0, 2.0);
@@ -503,6 +698,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn distribution_tail() {
+        let d = new!(0.0, 1.0);
+        let x = vec![-5.0, -8.0, -10.0, -20.0];
+        let p = vec![
+            2.866515718791939e-07,
+            6.220960574271784e-16,
+            7.619853024160525e-24,
+            2.7536241186062337e-89,
+        ];
+        for (&x, &p) in x.iter().zip(&p) {
+            assert::close(d.distribution(x) / p, 1.0, 1e-13);
+            assert::close(d.survival(-x) / p, 1.0, 1e-13);
+        }
+        assert!(d.distribution(-38.0) > 0.0);
+        assert::close(1.0 - d.distribution(8.0), 0.0, 1e-15);
+    }
+
     #[test]
     fn entropy() {
         use core::f64::consts::PI;
@@ -510,16 +723,27 @@ mod tests {
     }
 
     #[test]
-    fn inverse() {
-        use core::f64::{INFINITY, NEG_INFINITY};
+    fn expect() {
+        let d = new!(1.0, 2.0);
+        assert::close(d.expect(|x| x * x), 5.0, 1e-10);
+        assert::close(d.expect(|x| if x > 1.0 { 1.0 } else { 0.0 }), 0.5, 1e-10);
+
+        let d = new!(100.0, 1.0);
+        assert::close(d.expect(|x| x), 100.0, 1e-10);
 
+        let d = new!(0.0, 1e-3);
+        assert::close(d.expect(|x| x * x) / 1e-6, 1.0, 1e-8);
+    }
+
+    #[test]
+    fn inverse() {
         let d = new!(-1.0, 0.25);
         let p = vec![
             0.00, 0.05, 0.10, 0.15, 0.20, 0.25, 0.30, 0.35, 0.40, 0.45, 0.50, 0.55, 0.60, 0.65,
             0.70, 0.75, 0.80, 0.85, 0.90, 0.95, 1.00,
         ];
         let x = vec![
-            NEG_INFINITY,
+            f64::NEG_INFINITY,
             -1.411213406737868e+00,
             -1.320387891386150e+00,
             -1.259108347373447e+00,
@@ -539,7 +763,7 @@ mod tests {
             -7.408916526265525e-01,
             -6.796121086138498e-01,
             -5.887865932621319e-01,
-            INFINITY,
+            f64::INFINITY,
         ];
 
         assert::close(
@@ -554,6 +778,50 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).kurtosis(), 0.0);
     }
 
+    #[test]
+    fn log_density() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-4.0, 0.0, 1.0, 3.5] {
+            assert::close(d.log_density(x), d.density(x).ln(), 1e-14);
+        }
+        assert::close(d.log_density(100.0), -1226.7370857137646181, 1e-10);
+    }
+
+    #[test]
+    fn log_distribution() {
+        let d = new!(0.0, 1.0);
+        assert::close(d.log_distribution(-40.0), -804.6084420137538, 1e-10);
+        assert::close(d.log_distribution(-3.0), -6.607726221510349, 1e-13);
+        assert::close(d.log_distribution(3.0), -0.0013508099647481938, 1e-16);
+        assert_eq!(d.log_distribution(f64::INFINITY), 0.0);
+        assert_eq!(d.log_distribution(f64::NEG_INFINITY), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn log_survival() {
+        let d = new!(1.0, 2.0);
+        assert::close(d.log_survival(101.0), -1254.8313611394199, 1e-9);
+        assert::close(d.log_survival(-5.0), -0.0013508099647481938, 1e-16);
+        assert::close(d.cumulative_hazard(101.0), 1254.8313611394199, 1e-9);
+    }
+
+    #[test]
+    fn maximum_likelihood() {
+        let estimate = estimate::maximum_likelihood::<Gaussian>(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        let sigma = 1.25f64.sqrt();
+        assert::close(&estimate.parameters, &[2.5, sigma], 1e-15);
+        assert::close(&estimate.errors, &[sigma / 2.0, sigma / 8f64.sqrt()], 1e-15);
+        assert::close(
+            estimate.report.log_likelihood,
+            [1.0, 2.0, 3.0, 4.0]
+                .iter()
+                .fold(0.0, |sum, &x| sum + estimate.distribution.density(x).ln()),
+            1e-14,
+        );
+        assert!(estimate::maximum_likelihood::<Gaussian>(&[1.0]).is_none());
+        assert!(estimate::maximum_likelihood::<Gaussian>(&[1.0, 1.0]).is_none());
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.0, 1.0).mean(), 0.0);
@@ -564,16 +832,89 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).median(), 0.0);
     }
 
+    #[test]
+    fn mgf() {
+        let d = new!(1.0, 2.0);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert_eq!(d.log_mgf(1.0), 3.0);
+        assert_eq!(d.log_mgf(-1.0), 1.0);
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(2.0, 5.0).modes(), vec![2.0]);
     }
 
+    #[test]
+    fn moments() {
+        let d = estimate::moments::<Gaussian>(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(d.mu(), 2.5);
+        assert::close(d.sigma(), 1.25f64.sqrt(), 1e-15);
+        assert!(estimate::moments::<Gaussian>(&[1.0, 1.0]).is_none());
+    }
+
+    #[test]
+    fn partial_eq() {
+        assert_eq!(new!(1.0, 2.0), new!(1.0, 2.0));
+        assert_ne!(new!(1.0, 2.0), new!(1.0, 3.0));
+    }
+
+    #[test]
+    fn probability() {
+        let d = new!(1.0, 2.0);
+        assert::close(d.probability(-1.0..3.0), 0.6826894921370859, 1e-15);
+        assert_eq!(d.probability(3.0..3.0), 0.0);
+        assert_eq!(d.probability(3.0..-1.0), 0.0);
+        assert_eq!(d.probability(f64::NEG_INFINITY..f64::INFINITY), 1.0);
+        assert!(d.probability(f64::NAN..1.0).is_nan());
+    }
+
     #[test]
     fn skewness() {
         assert_eq!(new!(0.0, 2.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-1.0, 1.0, 3.0, 10.0, 20.0, 40.0];
+        let p = vec![
+            8.413447460685429e-01,
+            5.000000000000000e-01,
+            1.586552539314570e-01,
+            3.397673124730060e-06,
+            1.049451507536261e-21,
+            5.489115475660410e-85,
+        ];
+
+        assert::close(
+            &x.iter()
+                .zip(&p)
+                .map(|(&x, &p)| d.survival(x) / p)
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-13,
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        use Error;
+        assert!(Gaussian::try_new(1.0, 2.0).is_ok());
+        assert_eq!(
+            Gaussian::try_new(f64::NAN, 2.0).unwrap_err(),
+            Error::NotFinite("mu")
+        );
+        assert_eq!(
+            Gaussian::try_new(1.0, f64::NAN).unwrap_err(),
+            Error::NotFinite("sigma")
+        );
+        assert_eq!(
+            Gaussian::try_new(1.0, 0.0).unwrap_err(),
+            Error::NotPositive("sigma")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.0, 2.0).variance(), 4.0);
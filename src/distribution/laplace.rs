@@ -1,13 +1,16 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
 use distribution::Inverse;
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// A Laplace distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Laplace {
     mu: f64,
     b: f64,
@@ -18,11 +21,21 @@ impl Laplace {
     ///
     /// It should hold that `b > 0`.
     #[inline]
-    pub fn new(mu: f64, b: f64) -> Self {
+    pub const fn new(mu: f64, b: f64) -> Self {
         should!(b > 0.0);
         Laplace { mu, b }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(mu: f64, b: f64) -> Result<Self, Error> {
+        error::finite("mu", mu)?;
+        error::positive("b", b)?;
+        Ok(Laplace::new(mu, b))
+    }
+
     // Return the location parameter
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -36,10 +49,19 @@ impl Laplace {
     }
 }
 
+impl distribution::CharacteristicFunction for Laplace {
+    #[inline]
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let modulus = 1.0 / (1.0 + math::powi(self.b * t, 2));
+        let x = self.mu * t;
+        (modulus * libm::cos(x), modulus * libm::sin(x))
+    }
+}
+
 impl distribution::Continuous for Laplace {
     #[inline]
     fn density(&self, x: f64) -> f64 {
-        self.b.recip() * 0.5 * (-(x - self.mu).abs() / self.b).exp()
+        self.b.recip() * 0.5 * math::exp(-(x - self.mu).abs() / self.b)
     }
 }
 
@@ -49,9 +71,9 @@ impl distribution::Distribution for Laplace {
     #[inline]
     fn distribution(&self, x: f64) -> f64 {
         if x <= self.mu {
-            0.5 * ((x - self.mu) / self.b).exp()
+            0.5 * math::exp((x - self.mu) / self.b)
         } else {
-            1.0 - 0.5 * (-(x - self.mu) / self.b).exp()
+            1.0 - 0.5 * math::exp(-(x - self.mu) / self.b)
         }
     }
 }
@@ -59,7 +81,7 @@ impl distribution::Distribution for Laplace {
 impl distribution::Entropy for Laplace {
     #[inline]
     fn entropy(&self) -> f64 {
-        (core::f64::consts::E * 2.0 * self.b).ln()
+        math::ln(core::f64::consts::E * 2.0 * self.b)
     }
 }
 
@@ -69,14 +91,14 @@ impl distribution::Inverse for Laplace {
         should!((0.0..=1.0).contains(&p));
         if p > 0.5 {
             if p == 1.0 {
-                return core::f64::INFINITY;
+                return f64::INFINITY;
             }
-            self.mu - self.b * (2.0 - 2.0 * p).ln()
+            self.mu - self.b * math::ln(2.0 - 2.0 * p)
         } else {
             if p == 0.0 {
-                return core::f64::NEG_INFINITY;
+                return f64::NEG_INFINITY;
             }
-            self.mu + self.b * (2.0 * p).ln()
+            self.mu + self.b * math::ln(2.0 * p)
         }
     }
 }
@@ -102,10 +124,24 @@ impl distribution::Median for Laplace {
     }
 }
 
-impl distribution::Modes for Laplace {
+impl distribution::Mgf for Laplace {
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        let bt = self.b * t;
+        if bt.abs() < 1.0 {
+            self.mu * t - math::ln_1p(-bt * bt)
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl distribution::ModesIter for Laplace {
+    type Iter<'l> = distribution::ModePair<f64>;
+
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![self.mu]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(self.mu)
     }
 }
 
@@ -126,10 +162,28 @@ impl distribution::Skewness for Laplace {
     }
 }
 
+impl distribution::Support for Laplace {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Laplace {
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x >= self.mu {
+            0.5 * math::exp(-(x - self.mu) / self.b)
+        } else {
+            1.0 - 0.5 * math::exp((x - self.mu) / self.b)
+        }
+    }
+}
+
 impl distribution::Variance for Laplace {
     #[inline]
     fn variance(&self) -> f64 {
-        2.0 * self.b.powi(2)
+        2.0 * math::powi(self.b, 2)
     }
 
     #[inline]
@@ -138,16 +192,58 @@ impl distribution::Variance for Laplace {
     }
 }
 
+impl estimate::MaximumLikelihood for Laplace {
+    /// Estimate the parameters from a sample.
+    ///
+    /// The location is estimated by the sample median and the scale by the
+    /// mean absolute deviation from the median, which are robust to outliers.
+    fn maximum_likelihood(data: &[f64]) -> Option<Estimate<Self>> {
+        if data.len() < 2 {
+            return None;
+        }
+        let n = data.len() as f64;
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|one, other| one.total_cmp(other));
+        let mu = estimate::quantile(&sorted, 0.5);
+        let b = data.iter().fold(0.0, |sum, &x| sum + (x - mu).abs()) / n;
+        if b == 0.0 {
+            return None;
+        }
+        Some(Estimate {
+            distribution: Laplace::new(mu, b),
+            parameters: vec![mu, b],
+            errors: vec![b / n.sqrt(), b / n.sqrt()],
+            report: Report::closed(-n * (math::ln(2.0 * b) + 1.0)),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
         ($mu:expr, $b:expr) => (Laplace::new($mu, $b));
     );
 
+    #[test]
+    fn characteristic() {
+        use distribution;
+
+        let d = new!(2.0, 8.0);
+        let x = vec![-100.0, -1.0, 0.0, 2.0, 5.0, 10.0, 100.0];
+        assert::close(
+            &x.iter()
+                .map(|&x| distribution::invert_characteristic(&d, x))
+                .collect::<Vec<_>>(),
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            1e-7,
+        );
+    }
+
     #[test]
     fn density() {
         let d = new!(2.0, 8.0);
@@ -205,20 +301,20 @@ mod tests {
     #[test]
     fn entropy() {
         use core::f64::consts::E;
-        assert_eq!(new!(2.0, 1.0).entropy(), (2.0 * 1.0 * E).ln());
+        assert::close(new!(2.0, 1.0).entropy(), (2.0 * 1.0 * E).ln(), 1e-15);
     }
 
     #[test]
     fn inverse() {
         let d = new!(2.0, 3.0);
         let x = vec![
-            core::f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
             -2.8283137373023006,
             -0.07944154167983575,
             2.0,
             4.079441541679836,
             6.8283137373023015,
-            core::f64::INFINITY,
+            f64::INFINITY,
         ];
         let p = vec![0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.00];
 
@@ -234,6 +330,21 @@ mod tests {
         assert_eq!(new!(2.0, 9.0).kurtosis(), 3.0);
     }
 
+    #[test]
+    fn maximum_likelihood() {
+        let data = [1.0, 2.0, 3.0, 4.0, 100.0];
+        let estimate = estimate::maximum_likelihood::<Laplace>(&data).unwrap();
+        assert::close(&estimate.parameters, &[3.0, 20.2], 1e-14);
+        assert::close(
+            estimate.report.log_likelihood,
+            data.iter()
+                .fold(0.0, |sum, &x| sum + estimate.distribution.density(x).ln()),
+            1e-12,
+        );
+        assert!(estimate::maximum_likelihood::<Laplace>(&[1.0]).is_none());
+        assert!(estimate::maximum_likelihood::<Laplace>(&[1.0, 1.0]).is_none());
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0, 1.0).mean(), 2.0);
@@ -244,6 +355,15 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).median(), 2.0);
     }
 
+    #[test]
+    fn mgf() {
+        let d = new!(2.0, 8.0);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert::close(d.mgf(0.0625), 0.125f64.exp() / 0.75, 1e-15);
+        assert!(d.mgf(0.125).is_nan());
+        assert!(d.mgf(-0.125).is_nan());
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(2.0, 1.0).modes(), vec![2.0]);
@@ -254,6 +374,20 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0, 8.0);
+        let x = vec![-10.0, -1.0, 0.0, 2.0, 5.0, 10.0];
+
+        assert::close(
+            &x.iter()
+                .map(|&x| d.survival(x) + d.distribution(x))
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(2.0, 3.0).variance(), 18.0);
@@ -1,12 +1,15 @@
-use alloc::{vec, vec::Vec};
+use alloc::vec;
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// A logistic distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Logistic {
     mu: f64,
     s: f64,
@@ -17,11 +20,21 @@ impl Logistic {
     ///
     /// It should hold that `s > 0`.
     #[inline]
-    pub fn new(mu: f64, s: f64) -> Self {
+    pub const fn new(mu: f64, s: f64) -> Self {
         should!(s > 0.0);
         Logistic { mu, s }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(mu: f64, s: f64) -> Result<Self, Error> {
+        error::finite("mu", mu)?;
+        error::positive("s", s)?;
+        Ok(Logistic::new(mu, s))
+    }
+
     /// Return the location parameter.
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -42,11 +55,25 @@ impl Default for Logistic {
     }
 }
 
+impl distribution::CharacteristicFunction for Logistic {
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        use core::f64::consts::PI;
+        let x = (PI * self.s * t).abs();
+        let modulus = if x == 0.0 {
+            1.0
+        } else {
+            2.0 * x * math::exp(-x) / -math::exp_m1(-2.0 * x)
+        };
+        let x = self.mu * t;
+        (modulus * libm::cos(x), modulus * libm::sin(x))
+    }
+}
+
 impl distribution::Continuous for Logistic {
     #[inline]
     fn density(&self, x: f64) -> f64 {
-        let exp = (-(x - self.mu) / self.s).exp();
-        exp / (self.s * (1.0 + exp).powi(2))
+        let exp = math::exp(-(x - self.mu) / self.s);
+        exp / (self.s * math::powi(1.0 + exp, 2))
     }
 }
 
@@ -55,14 +82,14 @@ impl distribution::Distribution for Logistic {
 
     #[inline]
     fn distribution(&self, x: f64) -> f64 {
-        1.0 / (1.0 + (-(x - self.mu) / self.s).exp())
+        1.0 / (1.0 + math::exp(-(x - self.mu) / self.s))
     }
 }
 
 impl distribution::Entropy for Logistic {
     #[inline]
     fn entropy(&self) -> f64 {
-        self.s.ln() + 2.0
+        math::ln(self.s) + 2.0
     }
 }
 
@@ -70,7 +97,7 @@ impl distribution::Inverse for Logistic {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
         should!((0.0..=1.0).contains(&p));
-        self.mu - self.s * (1.0 / p - 1.0).ln()
+        self.mu - self.s * math::ln(1.0 / p - 1.0)
     }
 }
 
@@ -95,10 +122,26 @@ impl distribution::Median for Logistic {
     }
 }
 
-impl distribution::Modes for Logistic {
+impl distribution::Mgf for Logistic {
+    fn log_mgf(&self, t: f64) -> f64 {
+        use core::f64::consts::PI;
+        let st = PI * self.s * t;
+        if t == 0.0 {
+            0.0
+        } else if self.s * t.abs() < 1.0 {
+            self.mu * t + math::ln(st / math::sin(st))
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl distribution::ModesIter for Logistic {
+    type Iter<'l> = distribution::ModePair<f64>;
+
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![self.mu]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(self.mu)
     }
 }
 
@@ -120,25 +163,145 @@ impl distribution::Skewness for Logistic {
     }
 }
 
+impl distribution::Support for Logistic {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Logistic {
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        1.0 / (1.0 + math::exp((x - self.mu) / self.s))
+    }
+
+    #[inline]
+    fn hazard(&self, x: f64) -> f64 {
+        use distribution::Distribution;
+        self.distribution(x) / self.s
+    }
+}
+
 impl distribution::Variance for Logistic {
     #[inline]
     fn variance(&self) -> f64 {
         use core::f64::consts::PI;
-        (PI * self.s).powi(2) / 3.0
+        math::powi(PI * self.s, 2) / 3.0
     }
 }
 
+impl estimate::MaximumLikelihood for Logistic {
+    /// Estimate the parameters from a sample.
+    ///
+    /// The likelihood equations are solved by Newton’s method starting from
+    /// the method-of-moments estimate.
+    fn maximum_likelihood(data: &[f64]) -> Option<Estimate<Self>> {
+        use core::f64::consts::PI;
+        const ITERATIONS: usize = 100;
+        const TOLERANCE: f64 = 1e-12;
+
+        if data.len() < 2 {
+            return None;
+        }
+        let n = data.len() as f64;
+        let mut mu = estimate::mean(data);
+        let variance = estimate::variance(data, mu);
+        if variance == 0.0 {
+            return None;
+        }
+        let mut s = (3.0 * variance).sqrt() / PI;
+        let (mut gradient, mut hessian) = derivatives(data, mu, s);
+        let mut iterations = 0;
+        let mut converged = false;
+        while iterations < ITERATIONS {
+            iterations += 1;
+            let [[a, b], [_, d]] = hessian;
+            let determinant = a * d - b * b;
+            let mut delta_mu = (d * gradient[0] - b * gradient[1]) / determinant;
+            let mut delta_s = (a * gradient[1] - b * gradient[0]) / determinant;
+            while s - delta_s <= 0.0 {
+                delta_mu /= 2.0;
+                delta_s /= 2.0;
+            }
+            mu -= delta_mu;
+            s -= delta_s;
+            let result = derivatives(data, mu, s);
+            gradient = result.0;
+            hessian = result.1;
+            if delta_mu.abs() <= TOLERANCE * s && delta_s.abs() <= TOLERANCE * s {
+                converged = true;
+                break;
+            }
+        }
+        let log_likelihood = data.iter().fold(0.0, |sum, &x| {
+            let z = ((x - mu) / s).abs();
+            sum - z - 2.0 * math::ln_1p(math::exp(-z))
+        }) - n * math::ln(s);
+        Some(Estimate {
+            distribution: Logistic::new(mu, s),
+            parameters: vec![mu, s],
+            errors: estimate::errors(hessian),
+            report: Report {
+                iterations,
+                log_likelihood,
+                converged,
+            },
+        })
+    }
+}
+
+/// Compute the gradient and the Hessian of the log-likelihood.
+fn derivatives(data: &[f64], mu: f64, s: f64) -> ([f64; 2], [[f64; 2]; 2]) {
+    let n = data.len() as f64;
+    let (mut g, mut zg, mut h, mut zh, mut zzh) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for &x in data {
+        let z = (x - mu) / s;
+        let p = 1.0 / (1.0 + math::exp(-z));
+        let value = 2.0 * p - 1.0;
+        let slope = 2.0 * p * (1.0 - p);
+        g += value;
+        zg += z * value;
+        h += slope;
+        zh += z * slope;
+        zzh += z * z * slope;
+    }
+    let s2 = s * s;
+    (
+        [g / s, (zg - n) / s],
+        [
+            [-h / s2, -(g + zh) / s2],
+            [-(g + zh) / s2, (n - 2.0 * zg - zzh) / s2],
+        ],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
-    use core::f64::{INFINITY, NEG_INFINITY};
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
         ($mu:expr, $s:expr) => (Logistic::new($mu, $s));
     );
 
+    #[test]
+    fn characteristic() {
+        use distribution;
+
+        let d = new!(2.0, 3.0);
+        let x = vec![-100.0, -1.0, 0.0, 2.0, 5.0, 10.0, 100.0];
+        assert::close(
+            &x.iter()
+                .map(|&x| distribution::invert_characteristic(&d, x))
+                .collect::<Vec<_>>(),
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            1e-10,
+        );
+    }
+
     #[test]
     fn density() {
         let d = new!(5.0, 5.0);
@@ -199,7 +362,7 @@ mod tests {
         let d = new!(5.0, 5.0);
         let p = vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
         let x = vec![
-            NEG_INFINITY,
+            f64::NEG_INFINITY,
             -5.9861228866810947e+00,
             -1.9314718055994531e+00,
             7.6351069806398275e-01,
@@ -209,7 +372,7 @@ mod tests {
             9.2364893019360199e+00,
             1.1931471805599454e+01,
             1.5986122886681098e+01,
-            INFINITY,
+            f64::INFINITY,
         ];
 
         assert::close(
@@ -224,6 +387,28 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).kurtosis(), 1.2);
     }
 
+    #[test]
+    fn maximum_likelihood() {
+        use super::derivatives;
+
+        let mut source = source::default(42);
+        let data = Independent(&new!(2.0, 3.0), &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        let estimate = estimate::maximum_likelihood::<Logistic>(&data).unwrap();
+        assert!(estimate.report.converged);
+        assert::close(&estimate.parameters, &[2.0, 3.0], 0.1);
+        let (gradient, _) = derivatives(&data, estimate.parameters[0], estimate.parameters[1]);
+        assert::close(&gradient, &[0.0, 0.0], 1e-8);
+        assert::close(
+            estimate.report.log_likelihood,
+            data.iter()
+                .fold(0.0, |sum, &x| sum + estimate.distribution.density(x).ln()),
+            1e-8,
+        );
+        assert::close(estimate.errors[0], 3.0 * 3f64.sqrt() / 100.0, 0.005);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0, 1.0).mean(), 2.0);
@@ -234,6 +419,16 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).median(), 2.0);
     }
 
+    #[test]
+    fn mgf() {
+        use core::f64::consts::FRAC_PI_2;
+        let d = new!(0.0, 1.0);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert::close(d.mgf(0.5), FRAC_PI_2, 1e-15);
+        assert::close(d.mgf(-0.5), FRAC_PI_2, 1e-15);
+        assert!(d.mgf(1.0).is_nan());
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(2.0, 1.0).modes(), vec![2.0]);
@@ -244,6 +439,20 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0, 3.0);
+        let x = vec![-10.0, -1.0, 0.0, 2.0, 5.0, 10.0];
+
+        assert::close(
+            &x.iter()
+                .map(|&x| d.survival(x) + d.distribution(x))
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+    }
+
     #[test]
     fn variance() {
         use core::f64::consts::PI;
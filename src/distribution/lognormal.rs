@@ -3,10 +3,13 @@ use alloc::{vec, vec::Vec};
 use special::Primitive;
 
 use distribution::{self, Gaussian};
+use error::{self, Error};
+use estimate::{self, Estimate, Report};
+use math;
 use source::Source;
 
 /// A lognormal distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Lognormal {
     mu: f64,
     sigma: f64,
@@ -27,6 +30,16 @@ impl Lognormal {
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, Error> {
+        error::finite("mu", mu)?;
+        error::positive("sigma", sigma)?;
+        Ok(Lognormal::new(mu, sigma))
+    }
+
     /// Return the location parameter.
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -54,7 +67,8 @@ impl distribution::Continuous for Lognormal {
             0.0
         } else {
             let &Lognormal { mu, sigma, .. } = self;
-            (-(x.ln() - mu).powi(2) / (2.0 * sigma * sigma)).exp() / (x * sigma * (2.0 * PI).sqrt())
+            math::exp(-math::powi(math::ln(x) - mu, 2) / (2.0 * sigma * sigma))
+                / (x * sigma * (2.0 * PI).sqrt())
         }
     }
 }
@@ -66,7 +80,7 @@ impl distribution::Distribution for Lognormal {
         if x <= 0.0 {
             0.0
         } else {
-            self.gaussian.distribution(x.ln())
+            self.gaussian.distribution(math::ln(x))
         }
     }
 }
@@ -75,13 +89,13 @@ impl distribution::Entropy for Lognormal {
     #[inline]
     fn entropy(&self) -> f64 {
         use core::f64::consts::PI;
-        (self.sigma * (self.mu + 0.5).exp() * (2.0 * PI).sqrt()).ln()
+        math::ln(self.sigma * math::exp(self.mu + 0.5) * (2.0 * PI).sqrt())
     }
 }
 
 impl distribution::Inverse for Lognormal {
     fn inverse(&self, p: f64) -> f64 {
-        self.gaussian.inverse(p).exp()
+        math::exp(self.gaussian.inverse(p))
     }
 }
 
@@ -89,28 +103,30 @@ impl distribution::Kurtosis for Lognormal {
     #[inline]
     fn kurtosis(&self) -> f64 {
         let s2 = self.sigma * self.sigma;
-        (4.0 * s2).exp() + 2.0 * (3.0 * s2).exp() + 3.0 * (2.0 * s2).exp() - 6.0
+        math::exp(4.0 * s2) + 2.0 * math::exp(3.0 * s2) + 3.0 * math::exp(2.0 * s2) - 6.0
     }
 }
 
 impl distribution::Mean for Lognormal {
     #[inline]
     fn mean(&self) -> f64 {
-        (self.mu + self.sigma * self.sigma / 2.0).exp()
+        math::exp(self.mu + self.sigma * self.sigma / 2.0)
     }
 }
 
 impl distribution::Median for Lognormal {
     #[inline]
     fn median(&self) -> f64 {
-        self.mu.exp()
+        math::exp(self.mu)
     }
 }
 
-impl distribution::Modes for Lognormal {
+impl distribution::ModesIter for Lognormal {
+    type Iter<'l> = distribution::ModePair<f64>;
+
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![(self.mu - self.sigma * self.sigma).exp()]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(math::exp(self.mu - self.sigma * self.sigma))
     }
 }
 
@@ -120,23 +136,83 @@ impl distribution::Sample for Lognormal {
     where
         S: Source,
     {
-        self.gaussian.sample(source).exp()
+        math::exp(self.gaussian.sample(source))
     }
 }
 
 impl distribution::Skewness for Lognormal {
     #[inline]
     fn skewness(&self) -> f64 {
-        let es2 = (self.sigma * self.sigma).exp();
+        let es2 = math::exp(self.sigma * self.sigma);
         (es2 - 1.0).sqrt() * (2.0 + es2)
     }
 }
 
+impl distribution::Support for Lognormal {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Lognormal {
+    fn survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            self.gaussian.survival(math::ln(x))
+        }
+    }
+}
+
 impl distribution::Variance for Lognormal {
     #[inline]
     fn variance(&self) -> f64 {
         let s2 = self.sigma * self.sigma;
-        (s2.exp() - 1.0) * (2.0 * self.mu + s2).exp()
+        (math::exp(s2) - 1.0) * math::exp(2.0 * self.mu + s2)
+    }
+}
+
+impl estimate::MaximumLikelihood for Lognormal {
+    /// Estimate the parameters from a sample.
+    ///
+    /// The estimate is given by the mean and standard deviation of the
+    /// logarithms of the values. If any value is not positive, `None` is
+    /// returned instead of propagating `NaN`.
+    fn maximum_likelihood(data: &[f64]) -> Option<Estimate<Self>> {
+        use core::f64::consts::PI;
+        if data.len() < 2 || data.iter().any(|&x| x <= 0.0) {
+            return None;
+        }
+        let n = data.len() as f64;
+        let logarithms = data.iter().map(|&x| math::ln(x)).collect::<Vec<_>>();
+        let mu = estimate::mean(&logarithms);
+        let sigma = estimate::variance(&logarithms, mu).sqrt();
+        if sigma == 0.0 {
+            return None;
+        }
+        let log_likelihood = -0.5 * n * (math::ln(2.0 * PI * sigma * sigma) + 1.0) - n * mu;
+        Some(Estimate {
+            distribution: Lognormal::new(mu, sigma),
+            parameters: vec![mu, sigma],
+            errors: vec![sigma / n.sqrt(), sigma / (2.0 * n).sqrt()],
+            report: Report::closed(log_likelihood),
+        })
+    }
+}
+
+impl estimate::MethodOfMoments for Lognormal {
+    fn moments(data: &[f64]) -> Option<Self> {
+        if data.len() < 2 || data.iter().any(|&x| x <= 0.0) {
+            return None;
+        }
+        let mean = estimate::mean(data);
+        let variance = estimate::variance(data, mean);
+        if variance == 0.0 {
+            return None;
+        }
+        let sigma2 = math::ln_1p(variance / (mean * mean));
+        Some(Lognormal::new(math::ln(mean) - 0.5 * sigma2, sigma2.sqrt()))
     }
 }
 
@@ -144,6 +220,7 @@ impl distribution::Variance for Lognormal {
 mod tests {
     use alloc::{vec, vec::Vec};
     use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
@@ -208,7 +285,6 @@ mod tests {
 
     #[test]
     fn inverse() {
-        use core::f64::INFINITY;
         let d = new!(1.0, 2.0);
         let p = vec![
             0.00, 0.05, 0.10, 0.15, 0.20, 0.25, 0.30, 0.35, 0.40, 0.45, 0.50, 0.55, 0.60, 0.65,
@@ -235,7 +311,7 @@ mod tests {
             2.1603747153814467e+01,
             3.5272482631261830e+01,
             7.2945110977081981e+01,
-            INFINITY,
+            f64::INFINITY,
         ];
 
         assert::close(
@@ -265,11 +341,58 @@ mod tests {
         assert_eq!(new!(1.0, 1.0).modes(), vec![1.0]);
     }
 
+    #[test]
+    fn maximum_likelihood() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let estimate = estimate::maximum_likelihood::<Lognormal>(&data).unwrap();
+        let logarithms = data.iter().map(|&x| x.ln()).collect::<Vec<_>>();
+        let gaussian = estimate::maximum_likelihood::<Gaussian>(&logarithms).unwrap();
+        assert::close(&estimate.parameters, &gaussian.parameters, 1e-15);
+        assert::close(&estimate.errors, &gaussian.errors, 1e-15);
+        assert::close(
+            estimate.report.log_likelihood,
+            data.iter()
+                .fold(0.0, |sum, &x| sum + estimate.distribution.density(x).ln()),
+            1e-14,
+        );
+        assert!(estimate::maximum_likelihood::<Lognormal>(&[1.0, 0.0]).is_none());
+        assert!(estimate::maximum_likelihood::<Lognormal>(&[1.0, -2.0]).is_none());
+    }
+
+    #[test]
+    fn moments() {
+        let d = estimate::moments::<Lognormal>(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert::close(d.mean(), 2.5, 1e-14);
+        assert::close(d.variance(), 1.25, 1e-14);
+        assert!(estimate::moments::<Lognormal>(&[1.0, 0.0]).is_none());
+    }
+
     #[test]
     fn skewness() {
         assert!(4.0 - new!(0.0, 2f64.ln().sqrt()).skewness() < 1e-10);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        let x = vec![0.5, 1.0, 10.0, 1e6];
+        let p = vec![
+            8.013835802426387e-01,
+            6.914624612740131e-01,
+            2.574288294450581e-01,
+            7.383884625561780e-11,
+        ];
+
+        assert::close(
+            &x.iter()
+                .zip(&p)
+                .map(|(&x, &p)| d.survival(x) / p)
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-13,
+        );
+    }
+
     #[test]
     fn variance() {
         assert!(2.0 - new!(0.0, 2f64.ln().sqrt()).variance() < 1e-10);
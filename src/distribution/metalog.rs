@@ -0,0 +1,408 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution;
+use error::{self, Error};
+use linear;
+use math;
+use source::Source;
+
+/// A metalog distribution.
+///
+/// The distribution is unbounded and defined via its quantile function, which
+/// is a linear combination of basis functions of the cumulative probability.
+/// The coefficients are fitted to a set of quantiles.
+///
+/// ## References
+///
+/// 1. T. W. Keelin, “The metalog distributions,” Decision Analysis, vol. 13,
+///    no. 4, pp. 243–277, 2016.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metalog {
+    a: Vec<f64>,
+}
+
+impl Metalog {
+    /// Create a metalog distribution with `terms` terms fitted to quantiles
+    /// `x` at cumulative probabilities `p`.
+    ///
+    /// The coefficients are found by least squares; when the number of terms
+    /// equals the number of quantiles, the quantiles are matched exactly. It
+    /// should hold that `x` and `p` have the same length, `p[i] > 0`, `p[i] <
+    /// 1`, and `2 <= terms <= x.len()`. In addition, the resulting quantile
+    /// function should be increasing, which can be checked using
+    /// `is_feasible`.
+    pub fn new(x: &[f64], p: &[f64], terms: usize) -> Self {
+        should!(x.len() == p.len());
+        should!(p.iter().all(|&p| p > 0.0 && p < 1.0));
+        should!(2 <= terms && terms <= x.len());
+        let m = x.len();
+        let mut matrix = vec![0.0; terms * (terms + 1)];
+        let mut basis = vec![0.0; terms];
+        for i in 0..m {
+            evaluate(p[i], &mut basis);
+            for j in 0..terms {
+                for l in 0..terms {
+                    matrix[j * (terms + 1) + l] += basis[j] * basis[l];
+                }
+                matrix[j * (terms + 1) + terms] += basis[j] * x[i];
+            }
+        }
+        Metalog {
+            a: linear::solve(&mut matrix, terms),
+        }
+    }
+
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated,
+    /// including the feasibility of the fitted quantile function.
+    pub fn try_new(x: &[f64], p: &[f64], terms: usize) -> Result<Self, Error> {
+        error::require("x.len() == p.len()", x.len() == p.len())?;
+        for &x in x {
+            error::finite("x", x)?;
+        }
+        for &p in p {
+            error::probability("p", p)?;
+        }
+        error::require("2 <= terms", 2 <= terms)?;
+        error::require("terms <= x.len()", terms <= x.len())?;
+        let distribution = Metalog::new(x, p, terms);
+        error::require("is_feasible()", distribution.is_feasible())?;
+        Ok(distribution)
+    }
+
+    /// Return the coefficients.
+    #[inline(always)]
+    pub fn a(&self) -> &[f64] {
+        &self.a
+    }
+
+    /// Check if the quantile function is increasing, which is required for
+    /// the distribution to be valid.
+    ///
+    /// The check is performed on a grid of cumulative probabilities.
+    pub fn is_feasible(&self) -> bool {
+        const POINTS: usize = 1000;
+        if self.a.iter().any(|a| !a.is_finite()) {
+            return false;
+        }
+        (1..POINTS).all(|i| self.slope(i as f64 / POINTS as f64) > 0.0)
+    }
+
+    /// Compute the derivative of the quantile function.
+    fn slope(&self, p: f64) -> f64 {
+        let logit = math::ln(p / (1.0 - p));
+        let centered = p - 0.5;
+        let odds = 1.0 / (p * (1.0 - p));
+        let mut value = self.a[1] * odds;
+        for (j, &a) in self.a.iter().enumerate().skip(2) {
+            let (power, logistic) = shape(j);
+            let mut term = power as f64 * math::powi(centered, power - 1);
+            if logistic {
+                term = term * logit + math::powi(centered, power) * odds;
+            }
+            value += a * term;
+        }
+        value
+    }
+
+    /// Compute the quantile function and its derivative with respect to the
+    /// logit of the cumulative probability.
+    fn locate(&self, logit: f64) -> (f64, f64) {
+        let (p, q) = (logistic(logit), logistic(-logit));
+        let centered = p - 0.5;
+        let weight = p * q;
+        let (mut value, mut slope) = (0.0, 0.0);
+        for (j, &a) in self.a.iter().enumerate() {
+            value += a * basis(j, logit, centered);
+            slope += a * match j {
+                0 => 0.0,
+                1 => 1.0,
+                _ => {
+                    let (power, logistic) = shape(j);
+                    let mut term = power as f64 * math::powi(centered, power - 1) * weight;
+                    if logistic {
+                        term = term * logit + math::powi(centered, power);
+                    }
+                    term
+                }
+            };
+        }
+        (value, slope)
+    }
+
+    /// Find the logit of the cumulative probability of a point.
+    ///
+    /// The equation is solved by Newton’s method safeguarded by bisection.
+    fn solve(&self, x: f64) -> f64 {
+        const ITERATIONS: usize = 200;
+        const LIMIT: f64 = 745.0;
+        const TOLERANCE: f64 = 1e-15;
+
+        nonnan!(x);
+        if x.is_infinite() {
+            return x;
+        }
+        let (mut low, mut high) = (-LIMIT, LIMIT);
+        let mut y = 0.0;
+        for _ in 0..ITERATIONS {
+            let (value, slope) = self.locate(y);
+            if value < x {
+                low = y;
+            } else {
+                high = y;
+            }
+            let mut next = y - (value - x) / slope;
+            if !(next > low && next < high) {
+                next = 0.5 * (low + high);
+            }
+            let done = (next - y).abs() <= TOLERANCE * y.abs().max(1.0);
+            y = next;
+            if done || high - low <= TOLERANCE * y.abs().max(1.0) {
+                break;
+            }
+        }
+        y
+    }
+}
+
+impl distribution::Continuous for Metalog {
+    fn density(&self, x: f64) -> f64 {
+        let y = self.solve(x);
+        if !y.is_finite() {
+            return 0.0;
+        }
+        let (_, slope) = self.locate(y);
+        logistic(y) * logistic(-y) / slope
+    }
+}
+
+impl distribution::Distribution for Metalog {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        logistic(self.solve(x))
+    }
+}
+
+impl distribution::Inverse for Metalog {
+    fn inverse(&self, p: f64) -> f64 {
+        should!((0.0..=1.0).contains(&p));
+        if p <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if p >= 1.0 {
+            return f64::INFINITY;
+        }
+        let logit = math::ln(p / (1.0 - p));
+        let centered = p - 0.5;
+        self.a
+            .iter()
+            .enumerate()
+            .fold(0.0, |sum, (j, a)| sum + a * basis(j, logit, centered))
+    }
+}
+
+impl distribution::Median for Metalog {
+    #[inline]
+    fn median(&self) -> f64 {
+        self.a[0]
+    }
+}
+
+impl distribution::Sample for Metalog {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        use distribution::Inverse;
+        loop {
+            let p = source.read::<f64>();
+            if p > 0.0 {
+                return self.inverse(p);
+            }
+        }
+    }
+}
+
+impl distribution::Support for Metalog {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl distribution::Survival for Metalog {
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        logistic(-self.solve(x))
+    }
+}
+
+/// Evaluate the basis functions of the quantile function.
+fn evaluate(p: f64, values: &mut [f64]) {
+    let logit = math::ln(p / (1.0 - p));
+    let centered = p - 0.5;
+    for (j, value) in values.iter_mut().enumerate() {
+        *value = basis(j, logit, centered);
+    }
+}
+
+/// Evaluate a basis function given the logit and the centered probability.
+#[inline]
+fn basis(j: usize, logit: f64, centered: f64) -> f64 {
+    match j {
+        0 => 1.0,
+        1 => logit,
+        _ => {
+            let (power, logistic) = shape(j);
+            let value = math::powi(centered, power);
+            if logistic {
+                value * logit
+            } else {
+                value
+            }
+        }
+    }
+}
+
+/// Return the power of the centered probability in a basis function and
+/// whether the function involves the logit.
+#[inline]
+fn shape(j: usize) -> (i32, bool) {
+    match j {
+        2 => (1, true),
+        3 => (1, false),
+        _ => ((j / 2) as i32, j % 2 == 1),
+    }
+}
+
+#[inline]
+fn logistic(x: f64) -> f64 {
+    1.0 / (1.0 + math::exp(-x))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($x:expr, $p:expr, $terms:expr) => (Metalog::new(&$x, &$p, $terms));
+    );
+
+    fn logistic() -> (Logistic, Metalog) {
+        let logistic = Logistic::new(1.0, 2.0);
+        let p = vec![0.1, 0.25, 0.5, 0.75, 0.9];
+        let x = p.iter().map(|&p| logistic.inverse(p)).collect::<Vec<_>>();
+        (logistic, new!(x, p, 3))
+    }
+
+    #[test]
+    fn a() {
+        let (_, d) = logistic();
+        assert::close(d.a(), &[1.0, 2.0, 0.0], 1e-12);
+    }
+
+    #[test]
+    fn density() {
+        let (logistic, d) = logistic();
+        let x = vec![-10.0, -1.0, 0.0, 1.0, 3.0, 10.0];
+        assert::close(
+            &x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+            &x.iter().map(|&x| logistic.density(x)).collect::<Vec<_>>(),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn distribution() {
+        let (logistic, d) = logistic();
+        let x = vec![-10.0, -1.0, 0.0, 1.0, 3.0, 10.0];
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &x.iter()
+                .map(|&x| logistic.distribution(x))
+                .collect::<Vec<_>>(),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        let x = vec![1.0, 2.5, 3.5, 6.0];
+        let p = vec![0.1, 0.4, 0.6, 0.9];
+        let d = new!(x, p, 4);
+        assert!(d.is_feasible());
+        assert::close(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &x,
+            1e-10,
+        );
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &p,
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn is_feasible() {
+        let (_, d) = logistic();
+        assert!(d.is_feasible());
+        let d = new!([1.0, 1.1, 5.0, 5.1], [0.1, 0.4, 0.6, 0.9], 4);
+        assert!(!d.is_feasible());
+    }
+
+    #[test]
+    fn median() {
+        let (_, d) = logistic();
+        assert::close(d.median(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn sample() {
+        let (_, d) = logistic();
+        let mut source = source::default(42);
+        let sum = Independent(&d, &mut source)
+            .take(10000)
+            .fold(0.0, |sum, x| sum + x);
+        assert::close(sum / 10000.0, 1.0, 0.1);
+    }
+
+    #[test]
+    fn survival() {
+        let (logistic, d) = logistic();
+        let x = vec![-10.0, -1.0, 0.0, 1.0, 3.0, 10.0];
+        assert::close(
+            &x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+            &x.iter().map(|&x| logistic.survival(x)).collect::<Vec<_>>(),
+            1e-12,
+        );
+        for &x in &[50.0, 200.0, 1000.0] {
+            assert::close(d.survival(x) / logistic.survival(x), 1.0, 1e-10);
+        }
+        assert_eq!(d.survival(f64::INFINITY), 0.0);
+        assert_eq!(d.distribution(f64::NEG_INFINITY), 0.0);
+        assert!(d.distribution(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn try_new() {
+        use Error;
+        assert_eq!(
+            Metalog::try_new(&[1.0, 1.1, 5.0, 5.1], &[0.1, 0.4, 0.6, 0.9], 4).unwrap_err(),
+            Error::Violated("is_feasible()")
+        );
+        assert_eq!(
+            Metalog::try_new(&[1.0, 2.0], &[0.0, 0.5], 2).unwrap_err(),
+            Error::NotProbability("p")
+        );
+    }
+}
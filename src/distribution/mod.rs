@@ -1,21 +1,95 @@
 //! Probability distributions.
 
 use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::Range;
 #[allow(unused_imports)]
 use special::Primitive;
 
+use math;
+use quadrature;
 use source::Source;
 
+/// A distribution capable of computing the characteristic function.
+pub trait CharacteristicFunction: Distribution {
+    /// Compute the characteristic function.
+    ///
+    /// The result is returned as a pair of the real and imaginary parts.
+    fn characteristic(&self, t: f64) -> (f64, f64);
+}
+
 /// A continuous distribution.
 pub trait Continuous: Distribution {
     /// Compute the probability density function.
     fn density(&self, x: f64) -> f64;
+
+    /// Compute the natural logarithm of the probability density function.
+    ///
+    /// Distributions override the method in order to compute the logarithm
+    /// directly, which avoids the underflow in the tails.
+    #[inline]
+    fn log_density(&self, x: f64) -> f64 {
+        math::ln(self.density(x))
+    }
+
+    /// Compute the probability density function at several points.
+    ///
+    /// The default implementation evaluates the points one by one.
+    /// Distributions override the method so that the parameter-dependent work
+    /// is performed only once and the loop is amenable to vectorization. The
+    /// slices should have the same length.
+    #[inline]
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        for (value, &x) in out.iter_mut().zip(xs) {
+            *value = self.density(x);
+        }
+    }
 }
 
 /// A discrete distribution.
 pub trait Discrete: Distribution {
     /// Compute the probability mass function.
     fn mass(&self, x: Self::Value) -> f64;
+
+    /// Compute the probability of an interval of outcomes by summing the
+    /// probability masses.
+    ///
+    /// The interval includes its start and excludes its end. In contrast to
+    /// `Distribution::probability`, the result involves no subtraction and is
+    /// hence accurate even for intervals that are far in the tails.
+    fn interval_mass(&self, interval: Range<Self::Value>) -> f64
+    where
+        Range<Self::Value>: Iterator<Item = Self::Value>,
+    {
+        interval.fold(0.0, |sum, x| sum + self.mass(x))
+    }
+
+    /// Return an iterator over the outcomes paired with their probability
+    /// masses.
+    ///
+    /// The iteration starts at the left endpoint of the support and stops
+    /// either at the right endpoint or once the probability mass of the
+    /// remaining outcomes does not exceed `tail`, whichever comes first. For
+    /// distributions with finite support, a `tail` of zero gives the whole
+    /// support. The method is named so as not to clash with
+    /// `Support::support`.
+    #[inline]
+    fn outcomes(&self, tail: f64) -> Outcomes<'_, Self>
+    where
+        Self: Sized + Support,
+        Self::Value: Copy + TryFrom<usize>,
+    {
+        should!((0.0..1.0).contains(&tail));
+        let (start, end) = self.support();
+        Outcomes {
+            distribution: self,
+            next: libm::ceil(start.max(0.0)) as usize,
+            end,
+            tail,
+            done: false,
+        }
+    }
 }
 
 /// A distribution.
@@ -25,6 +99,61 @@ pub trait Distribution {
 
     /// Compute the cumulative distribution function.
     fn distribution(&self, x: f64) -> f64;
+
+    /// Compute the cumulative distribution function at several points.
+    ///
+    /// The default implementation evaluates the points one by one.
+    /// Distributions override the method so that the parameter-dependent work
+    /// is performed only once and the loop is amenable to vectorization. The
+    /// slices should have the same length.
+    #[inline]
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        for (value, &x) in out.iter_mut().zip(xs) {
+            *value = self.distribution(x);
+        }
+    }
+
+    /// Compute the natural logarithm of the cumulative distribution function.
+    ///
+    /// Distributions override the method in order to compute the logarithm
+    /// directly, which avoids the underflow in the lower tail.
+    #[inline]
+    fn log_distribution(&self, x: f64) -> f64 {
+        math::ln(self.distribution(x))
+    }
+
+    /// Compute the probability of an interval.
+    ///
+    /// The interval includes its start and excludes its end, that is, the
+    /// result is the probability of `start <= X < end`, which is consistent
+    /// with `Range` and `Discrete::interval_mass`. The default implementation
+    /// is exact for continuous distributions, and discrete distributions
+    /// override it. If `start >= end`, the result is zero.
+    fn probability(&self, interval: Range<f64>) -> f64 {
+        let Range { start, end } = interval;
+        if start.is_nan() || end.is_nan() {
+            return f64::NAN;
+        }
+        if start >= end {
+            return 0.0;
+        }
+        (self.distribution(end) - self.distribution(start)).max(0.0)
+    }
+
+    /// Compute the expected value of a function of the random variable.
+    ///
+    /// For discrete distributions, the probability masses are summed over
+    /// the support until the remaining mass is negligible. For continuous
+    /// distributions, the density is integrated over the support by adaptive
+    /// Gauss–Kronrod quadrature. The expected value is assumed to exist.
+    fn expect<F>(&self, function: F) -> f64
+    where
+        Self: Sized + Measure,
+        F: Fn(f64) -> f64,
+    {
+        expect(self, function)
+    }
 }
 
 /// A distribution capable of computing the differential entropy.
@@ -33,6 +162,22 @@ pub trait Entropy: Distribution {
     ///
     /// The entropy is computed in nats.
     fn entropy(&self) -> f64;
+
+    /// Compute the differential entropy in bits.
+    #[inline]
+    fn entropy_bits(&self) -> f64 {
+        use core::f64::consts::LN_2;
+        self.entropy() / LN_2
+    }
+
+    /// Compute the differential entropy with respect to logarithm base `b`.
+    ///
+    /// It should hold that `b > 0` and `b != 1`.
+    #[inline]
+    fn entropy_base(&self, b: f64) -> f64 {
+        should!(b > 0.0 && b != 1.0);
+        self.entropy() / math::ln(b)
+    }
 }
 
 /// A distribution capable of inverting the distribution function.
@@ -41,6 +186,52 @@ pub trait Inverse: Distribution {
     fn inverse(&self, p: f64) -> Self::Value;
 }
 
+/// A discrete distribution capable of inverting the distribution function and
+/// reporting the values of the distribution function around the result.
+pub trait InverseBracket: Discrete + Inverse {
+    /// Compute the inverse of the cumulative distribution function along with
+    /// the values of the cumulative distribution function right below and at
+    /// the result.
+    ///
+    /// For the returned triplet `(k, lower, upper)`, `lower` is the cumulative
+    /// distribution function at the predecessor of `k`, and `upper` is the one
+    /// at `k`. Up to rounding, it holds that `lower < p <= upper` unless `p` is
+    /// zero.
+    fn inverse_bracket(&self, p: f64) -> (Self::Value, f64, f64);
+}
+
+/// An iterator over the outcomes of a discrete distribution.
+///
+/// The iterator is created by `Discrete::outcomes`.
+#[derive(Clone, Debug)]
+pub struct Outcomes<'l, D: 'l> {
+    distribution: &'l D,
+    next: usize,
+    end: f64,
+    tail: f64,
+    done: bool,
+}
+
+impl<'l, D> Iterator for Outcomes<'l, D>
+where
+    D: Discrete + Support,
+    D::Value: Copy + TryFrom<usize>,
+{
+    type Item = (D::Value, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.next as f64;
+        if self.done || x > self.end {
+            return None;
+        }
+        let value = D::Value::try_from(self.next).ok()?;
+        let mass = self.distribution.mass(value);
+        self.done = 1.0 - self.distribution.distribution(x) <= self.tail;
+        self.next += 1;
+        Some((value, mass))
+    }
+}
+
 /// A distribution capable of computing the excess kurtosis.
 pub trait Kurtosis: Skewness {
     /// Compute the excess kurtosis.
@@ -55,6 +246,32 @@ pub trait Mean: Distribution {
     fn mean(&self) -> f64;
 }
 
+/// A distribution with a density with respect to a base measure.
+///
+/// The base measure is the counting measure for discrete distributions and
+/// the Lebesgue measure for continuous ones.
+pub trait Measure: Support {
+    /// Indicate whether the distribution is discrete.
+    fn is_discrete(&self) -> bool;
+
+    /// Compute the density with respect to the base measure.
+    ///
+    /// For discrete distributions, this is the probability mass function,
+    /// which is zero at non-integer points.
+    fn weight(&self, x: f64) -> f64;
+
+    /// Return the points delimiting the bulk of the probability mass.
+    ///
+    /// The points are used to split the domain of integration so that the
+    /// quadrature does not miss narrow or off-center densities. The default
+    /// implementation returns `None`, in which case the support is integrated
+    /// as a whole.
+    #[inline]
+    fn bulk(&self) -> Option<[f64; 3]> {
+        None
+    }
+}
+
 /// A distribution capable of computing the median.
 ///
 /// The trait is applicable when exactly one median exists.
@@ -63,20 +280,152 @@ pub trait Median: Distribution {
     fn median(&self) -> f64;
 }
 
+/// A distribution capable of computing the moment-generating function.
+///
+/// The trait is applicable when the moment-generating function exists in a
+/// neighborhood of zero. Outside the region of convergence, the functions
+/// return `NaN`.
+pub trait Mgf: Distribution {
+    /// Compute the moment-generating function.
+    #[inline]
+    fn mgf(&self, t: f64) -> f64 {
+        math::exp(self.log_mgf(t))
+    }
+
+    /// Compute the logarithm of the moment-generating function, which is also
+    /// known as the cumulant-generating function.
+    fn log_mgf(&self, t: f64) -> f64;
+}
+
 /// A distribution capable of computing the modes.
 ///
-/// The trait is applicable when the number of modes is finite.
+/// The trait is applicable when the number of modes is finite. It is
+/// implemented for every distribution implementing `ModesIter`.
 pub trait Modes: Distribution {
     /// Compute the modes.
     fn modes(&self) -> Vec<Self::Value>;
 }
 
+impl<D: ModesIter> Modes for D {
+    #[inline]
+    fn modes(&self) -> Vec<Self::Value> {
+        self.modes_iter().collect()
+    }
+}
+
+/// A distribution capable of enumerating the modes without allocation.
+///
+/// The trait is applicable when the number of modes is finite. The modes are
+/// produced in increasing order.
+pub trait ModesIter: Distribution {
+    /// The iterator over the modes.
+    type Iter<'l>: Iterator<Item = Self::Value>
+    where
+        Self: 'l;
+
+    /// Enumerate the modes.
+    fn modes_iter(&self) -> Self::Iter<'_>;
+}
+
+/// An iterator over at most two modes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModePair<T> {
+    first: Option<T>,
+    second: Option<T>,
+}
+
+impl<T> ModePair<T> {
+    /// Create an iterator over no modes.
+    #[inline]
+    pub fn none() -> Self {
+        ModePair {
+            first: None,
+            second: None,
+        }
+    }
+
+    /// Create an iterator over one mode.
+    #[inline]
+    pub fn one(first: T) -> Self {
+        ModePair {
+            first: Some(first),
+            second: None,
+        }
+    }
+
+    /// Create an iterator over two modes.
+    #[inline]
+    pub fn two(first: T, second: T) -> Self {
+        ModePair {
+            first: Some(first),
+            second: Some(second),
+        }
+    }
+}
+
+impl<T> Iterator for ModePair<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        match self.first.take() {
+            None => self.second.take(),
+            first => first,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.first.is_some() as usize + self.second.is_some() as usize;
+        (count, Some(count))
+    }
+}
+
+impl<T> ExactSizeIterator for ModePair<T> {}
+
+/// A discrete distribution capable of computing the probability-generating
+/// function.
+pub trait Pgf: Discrete {
+    /// Compute the probability-generating function.
+    fn pgf(&self, z: f64) -> f64;
+
+    /// Compute the factorial moment of order `k`.
+    ///
+    /// The factorial moment is the `k`th derivative of the
+    /// probability-generating function at one.
+    fn factorial_moment(&self, k: usize) -> f64;
+}
+
 /// A distribution capable of drawing samples.
 pub trait Sample: Distribution {
     /// Draw a sample.
     fn sample<S>(&self, source: &mut S) -> Self::Value
     where
         S: Source;
+
+    /// Draw samples filling a buffer.
+    ///
+    /// The default implementation draws the samples one by one. Distributions
+    /// whose sampling involves setup work override the method so that the
+    /// setup is performed only once per buffer.
+    #[inline]
+    fn sample_into<S>(&self, buffer: &mut [Self::Value], source: &mut S)
+    where
+        S: Source,
+    {
+        for value in buffer.iter_mut() {
+            *value = self.sample(source);
+        }
+    }
+
+    /// Draw a fixed number of samples into an array.
+    ///
+    /// The array is allocated on the stack, which makes the method usable
+    /// without an allocator.
+    #[inline]
+    fn sample_array<const N: usize>(&self, source: &mut impl Source) -> [Self::Value; N] {
+        core::array::from_fn(|_| self.sample(source))
+    }
 }
 
 /// A distribution capable of computing the skewness.
@@ -85,6 +434,45 @@ pub trait Skewness: Variance {
     fn skewness(&self) -> f64;
 }
 
+/// A distribution capable of reporting its support.
+pub trait Support: Distribution {
+    /// Return the endpoints of the smallest interval containing the support.
+    ///
+    /// The endpoints are infinite when the support is unbounded.
+    fn support(&self) -> (f64, f64);
+}
+
+/// A distribution capable of computing the survival function.
+pub trait Survival: Continuous {
+    /// Compute the survival function.
+    ///
+    /// The survival function is the complement of the cumulative distribution
+    /// function; however, it is computed directly in order to retain accuracy
+    /// in the upper tail.
+    fn survival(&self, x: f64) -> f64;
+
+    /// Compute the natural logarithm of the survival function.
+    ///
+    /// Distributions override the method in order to compute the logarithm
+    /// directly, which avoids the underflow in the upper tail.
+    #[inline]
+    fn log_survival(&self, x: f64) -> f64 {
+        math::ln(self.survival(x))
+    }
+
+    /// Compute the hazard function.
+    #[inline]
+    fn hazard(&self, x: f64) -> f64 {
+        self.density(x) / self.survival(x)
+    }
+
+    /// Compute the cumulative hazard function.
+    #[inline]
+    fn cumulative_hazard(&self, x: f64) -> f64 {
+        -self.log_survival(x)
+    }
+}
+
 /// A distribution capable of computing the variance.
 ///
 /// The trait is applicable when the variance exists, that is, finite.
@@ -99,6 +487,8 @@ pub trait Variance: Mean {
     }
 }
 
+#[cfg(feature = "approx")]
+mod approximate;
 mod bernoulli;
 mod beta;
 mod binomial;
@@ -110,14 +500,17 @@ mod gaussian;
 mod laplace;
 mod logistic;
 mod lognormal;
+mod metalog;
+mod negative_binomial;
 mod pert;
+mod poisson;
 mod triangular;
 mod uniform;
 
 pub use self::bernoulli::Bernoulli;
 pub use self::beta::Beta;
-pub use self::binomial::Binomial;
-pub use self::categorical::Categorical;
+pub use self::binomial::{Binomial, BinomialMasses};
+pub use self::categorical::{Categorical, CategoricalModes};
 pub use self::cauchy::Cauchy;
 pub use self::exponential::Exponential;
 pub use self::gamma::Gamma;
@@ -125,6 +518,198 @@ pub use self::gaussian::Gaussian;
 pub use self::laplace::Laplace;
 pub use self::logistic::Logistic;
 pub use self::lognormal::Lognormal;
+pub use self::metalog::Metalog;
+pub use self::negative_binomial::NegativeBinomial;
 pub use self::pert::Pert;
+pub use self::poisson::Poisson;
 pub use self::triangular::Triangular;
 pub use self::uniform::Uniform;
+
+/// Compute the cumulative distribution function of a continuous distribution
+/// via its characteristic function.
+///
+/// The computation is based on the Gil-Pelaez inversion formula, which is
+/// evaluated by numerical integration. It is meant for distributions that are
+/// defined only via their characteristic functions, such as stable laws, and
+/// requires the characteristic function to decay at infinity.
+///
+/// ## References
+///
+/// 1. J. Gil-Pelaez, “Note on the inversion theorem,” Biometrika, vol. 38,
+///    no. 3–4, pp. 481–482, 1951.
+pub fn invert_characteristic<D>(distribution: &D, x: f64) -> f64
+where
+    D: CharacteristicFunction + ?Sized,
+{
+    use core::f64::consts::FRAC_1_PI;
+
+    nonnan!(x);
+    let integral = quadrature::integrate(
+        |t| {
+            if t == 0.0 {
+                return 0.0;
+            }
+            let (re, im) = distribution.characteristic(t);
+            let (cos, sin) = (libm::cos(t * x), libm::sin(t * x));
+            (im * cos - re * sin) / t
+        },
+        0.0,
+        f64::INFINITY,
+    );
+    (0.5 - FRAC_1_PI * integral).clamp(0.0, 1.0)
+}
+
+/// Compute the probability of an interval for a distribution supported on
+/// the integers.
+///
+/// The interval includes its start and excludes its end, and the probability
+/// of `X < x` is obtained as `F(ceil(x) - 1)`.
+pub(crate) fn integer_probability<D>(distribution: &D, interval: Range<f64>) -> f64
+where
+    D: Distribution + ?Sized,
+{
+    let Range { start, end } = interval;
+    if start.is_nan() || end.is_nan() {
+        return f64::NAN;
+    }
+    if start >= end {
+        return 0.0;
+    }
+    let below = |x: f64| distribution.distribution(libm::ceil(x) - 1.0);
+    (below(end) - below(start)).max(0.0)
+}
+
+/// Sum or integrate a function weighted by the density over the support.
+fn expect<D, F>(distribution: &D, function: F) -> f64
+where
+    D: Measure + ?Sized,
+    F: Fn(f64) -> f64,
+{
+    const EPSILON: f64 = 1e-15;
+
+    let (start, end) = distribution.support();
+    if distribution.is_discrete() {
+        let mut sum = 0.0;
+        let mut x = start;
+        while x <= end {
+            let weight = distribution.weight(x);
+            if weight > 0.0 {
+                sum += weight * function(x);
+            }
+            if distribution.distribution(x) >= 1.0 - EPSILON {
+                break;
+            }
+            x += 1.0;
+        }
+        sum
+    } else {
+        let mut points = distribution.bulk().unwrap_or([end; 3]);
+        quadrature::integrate_pieces(
+            |x| {
+                let weight = distribution.weight(x);
+                if weight > 0.0 {
+                    weight * function(x)
+                } else {
+                    0.0
+                }
+            },
+            start,
+            end,
+            &mut points,
+        )
+    }
+}
+
+/// Invert a cumulative distribution function far in the lower tail.
+///
+/// The equation `ln F(x) = ln p` is solved by Newton’s method with respect to
+/// `ln x` starting from `x`, which avoids the underflow of `F`. The closure
+/// returns `ln F(x)` and `ln(x f(x))` where `f` is the density, and the
+/// iterates are kept below `upper`.
+pub(crate) fn invert_lower_tail<F>(ln_p: f64, x: f64, upper: f64, function: F) -> f64
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    const ITERATIONS: usize = 100;
+    const TOLERANCE: f64 = 1e-15;
+
+    let bound = math::ln(upper);
+    let mut u = math::ln(x).min(bound);
+    for _ in 0..ITERATIONS {
+        let (ln_distribution, ln_slope) = function(math::exp(u));
+        let step = (ln_distribution - ln_p) / math::exp(ln_slope - ln_distribution);
+        if !step.is_finite() {
+            break;
+        }
+        let next = (u - step).min(bound);
+        let done = (next - u).abs() <= TOLERANCE * u.abs().max(1.0);
+        u = next;
+        if done {
+            break;
+        }
+    }
+    math::exp(u)
+}
+
+macro_rules! implement_continuous(
+    ($($kind:ident),*) => ($(
+        impl Measure for $kind {
+            #[inline(always)]
+            fn is_discrete(&self) -> bool {
+                false
+            }
+
+            #[inline(always)]
+            fn weight(&self, x: f64) -> f64 {
+                self.density(x)
+            }
+
+            fn bulk(&self) -> Option<[f64; 3]> {
+                const TAIL: f64 = 1e-10;
+                Some([self.inverse(TAIL), self.inverse(0.5), self.inverse(1.0 - TAIL)])
+            }
+        }
+    )*);
+);
+
+macro_rules! implement_discrete(
+    ($($kind:ident as $value:ty),*) => ($(
+        impl Measure for $kind {
+            #[inline(always)]
+            fn is_discrete(&self) -> bool {
+                true
+            }
+
+            fn weight(&self, x: f64) -> f64 {
+                let (start, end) = self.support();
+                if x < start || x > end || x != x.trunc() {
+                    0.0
+                } else {
+                    self.mass(x as $value)
+                }
+            }
+        }
+    )*);
+);
+
+implement_continuous!(
+    Beta,
+    Cauchy,
+    Exponential,
+    Gamma,
+    Gaussian,
+    Laplace,
+    Logistic,
+    Lognormal,
+    Metalog,
+    Pert,
+    Triangular,
+    Uniform
+);
+implement_discrete!(
+    Bernoulli as u8,
+    Binomial as u64,
+    Categorical as usize,
+    NegativeBinomial as usize,
+    Poisson as usize
+);
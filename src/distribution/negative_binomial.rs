@@ -0,0 +1,374 @@
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution;
+use error::{self, Error};
+use math;
+use source::Source;
+
+/// A negative binomial distribution.
+///
+/// The distribution is the one of the number of failures before the `r`th
+/// success in a sequence of independent trials with success probability `p`,
+/// where `r` is allowed to be any positive real number. It is also the
+/// Poisson distribution whose rate follows a gamma distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NegativeBinomial {
+    r: f64,
+    p: f64,
+    ln_gamma: f64,
+}
+
+impl NegativeBinomial {
+    /// Create a negative binomial distribution with number of successes `r`
+    /// and success probability `p`.
+    ///
+    /// It should hold that `r > 0`, `p > 0`, and `p <= 1`.
+    #[inline]
+    pub fn new(r: f64, p: f64) -> Self {
+        should!(r > 0.0 && p > 0.0 && p <= 1.0);
+        NegativeBinomial {
+            r,
+            p,
+            ln_gamma: special::Gamma::ln_gamma(r).0,
+        }
+    }
+
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(r: f64, p: f64) -> Result<Self, Error> {
+        error::positive("r", r)?;
+        if p.is_nan() || p <= 0.0 || p > 1.0 {
+            return Err(Error::NotProbability("p"));
+        }
+        Ok(NegativeBinomial::new(r, p))
+    }
+
+    /// Return the number of successes.
+    #[inline(always)]
+    pub fn r(&self) -> f64 {
+        self.r
+    }
+
+    /// Return the success probability.
+    #[inline(always)]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Compute the natural logarithm of the probability mass function at `x`.
+    fn ln_mass(&self, x: f64) -> f64 {
+        use special::Gamma;
+        Gamma::ln_gamma(self.r + x).0 - self.ln_gamma - Gamma::ln_gamma(x + 1.0).0
+            + self.r * math::ln(self.p)
+            + x * math::ln_1p(-self.p)
+    }
+}
+
+impl distribution::Discrete for NegativeBinomial {
+    fn mass(&self, x: usize) -> f64 {
+        if self.p == 1.0 {
+            return if x == 0 { 1.0 } else { 0.0 };
+        }
+        math::exp(self.ln_mass(x as f64))
+    }
+}
+
+impl distribution::Distribution for NegativeBinomial {
+    type Value = usize;
+
+    fn distribution(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x < 0.0 {
+            return 0.0;
+        }
+        if self.p == 1.0 {
+            return 1.0;
+        }
+        let k = libm::floor(x) + 1.0;
+        self.p.inc_beta(self.r, k, self.r.ln_beta(k))
+    }
+
+    #[inline]
+    fn probability(&self, interval: core::ops::Range<f64>) -> f64 {
+        distribution::integer_probability(self, interval)
+    }
+}
+
+impl distribution::Entropy for NegativeBinomial {
+    /// Compute the differential entropy.
+    ///
+    /// The summation starts at the mode and proceeds outward via the
+    /// recurrence of the probability mass function until the masses become
+    /// negligible.
+    fn entropy(&self) -> f64 {
+        const EPSILON: f64 = 1e-20;
+
+        if self.p == 1.0 {
+            return 0.0;
+        }
+        let ln_q = math::ln_1p(-self.p);
+        let mode = if self.r > 1.0 {
+            libm::floor((self.r - 1.0) * (1.0 - self.p) / self.p)
+        } else {
+            0.0
+        };
+        let ln_mass = self.ln_mass(mode);
+        let mut sum = -math::exp(ln_mass) * ln_mass;
+        let (mut k, mut ln_mass_k) = (mode, ln_mass);
+        loop {
+            ln_mass_k += math::ln((self.r + k) / (k + 1.0)) + ln_q;
+            k += 1.0;
+            let mass = math::exp(ln_mass_k);
+            if mass < EPSILON {
+                break;
+            }
+            sum -= mass * ln_mass_k;
+        }
+        let (mut k, mut ln_mass_k) = (mode, ln_mass);
+        while k > 0.0 {
+            ln_mass_k -= math::ln((self.r + k - 1.0) / k) + ln_q;
+            k -= 1.0;
+            let mass = math::exp(ln_mass_k);
+            if mass < EPSILON {
+                break;
+            }
+            sum -= mass * ln_mass_k;
+        }
+        sum
+    }
+}
+
+impl distribution::Inverse for NegativeBinomial {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The result is bracketed by doubling and then found by bisection. The
+    /// support is unbounded, and `usize::MAX` is returned for `p = 1`.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::{Distribution, Mean};
+        should!((0.0..=1.0).contains(&p));
+        if p >= 1.0 {
+            return usize::MAX;
+        }
+        if self.distribution(0.0) >= p {
+            return 0;
+        }
+        let (mut low, mut high) = (0.0, libm::ceil(self.mean()).max(1.0));
+        while self.distribution(high) < p {
+            if high >= usize::MAX as f64 {
+                return usize::MAX;
+            }
+            low = high;
+            high *= 2.0;
+        }
+        while high - low > 1.0 {
+            let middle = libm::floor(0.5 * (low + high));
+            if self.distribution(middle) >= p {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        high as usize
+    }
+}
+
+impl distribution::Mean for NegativeBinomial {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.r * (1.0 - self.p) / self.p
+    }
+}
+
+impl distribution::Median for NegativeBinomial {
+    #[inline]
+    fn median(&self) -> f64 {
+        use distribution::Inverse;
+        self.inverse(0.5) as f64
+    }
+}
+
+impl distribution::Pgf for NegativeBinomial {
+    /// Compute the probability-generating function.
+    ///
+    /// The function exists for `z < 1 / (1 - p)`; otherwise, `NaN` is
+    /// returned.
+    fn pgf(&self, z: f64) -> f64 {
+        let denominator = 1.0 - (1.0 - self.p) * z;
+        if denominator <= 0.0 {
+            return f64::NAN;
+        }
+        math::powf(self.p / denominator, self.r)
+    }
+
+    fn factorial_moment(&self, k: usize) -> f64 {
+        use special::Gamma;
+        if k == 0 {
+            return 1.0;
+        }
+        let k = k as f64;
+        math::exp(
+            Gamma::ln_gamma(self.r + k).0 - self.ln_gamma + k * math::ln((1.0 - self.p) / self.p),
+        )
+    }
+}
+
+impl distribution::Sample for NegativeBinomial {
+    /// Draw a sample.
+    ///
+    /// The sample is drawn from the Poisson distribution whose rate is drawn
+    /// from the corresponding gamma distribution.
+    fn sample<S>(&self, source: &mut S) -> usize
+    where
+        S: Source,
+    {
+        use distribution::{gamma, Poisson};
+        let lambda = gamma::sample(self.r, source) * (1.0 - self.p) / self.p;
+        if lambda > 0.0 {
+            Poisson::new(lambda).sample(source)
+        } else {
+            0
+        }
+    }
+}
+
+impl distribution::Support for NegativeBinomial {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl distribution::Variance for NegativeBinomial {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.r * (1.0 - self.p) / (self.p * self.p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($r:expr, $p:expr) => (NegativeBinomial::new($r, $p));
+    );
+
+    #[test]
+    fn distribution() {
+        let d = new!(2.5, 0.4);
+        let mut sum = 0.0;
+        for x in 0..20 {
+            sum += d.mass(x);
+            assert::close(d.distribution(x as f64 + 0.5), sum, 1e-12);
+        }
+        assert_eq!(d.distribution(-1.0), 0.0);
+    }
+
+    #[test]
+    fn entropy() {
+        let d = new!(2.5, 0.4);
+        let expected = -(0..1000).fold(0.0, |sum, x| {
+            let p = d.mass(x);
+            sum + if p > 0.0 { p * p.ln() } else { 0.0 }
+        });
+        assert::close(d.entropy(), expected, 1e-12);
+        let d = new!(0.5, 0.01);
+        let expected = -(0..100000).fold(0.0, |sum, x| {
+            let p = d.mass(x);
+            sum + if p > 0.0 { p * p.ln() } else { 0.0 }
+        });
+        assert::close(d.entropy(), expected, 1e-10);
+        assert_eq!(new!(3.0, 1.0).entropy(), 0.0);
+    }
+
+    #[test]
+    fn expect() {
+        let d = new!(2.5, 0.4);
+        assert::close(d.expect(|x| x), d.mean(), 1e-10);
+        assert::close(d.expect(|x| (x - d.mean()).powi(2)), d.variance(), 1e-8);
+    }
+
+    #[test]
+    fn inverse() {
+        for &(r, p) in &[(2.5, 0.4), (0.5, 0.01), (100.0, 0.9)] {
+            let d = new!(r, p);
+            for &u in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+                let x = d.inverse(u);
+                assert!(d.distribution(x as f64) >= u);
+                assert!(x == 0 || d.distribution(x as f64 - 1.0) < u);
+            }
+            assert_eq!(d.inverse(0.0), 0);
+            assert_eq!(d.inverse(1.0), usize::MAX);
+        }
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(3.0, 0.5);
+        assert::close(
+            (0..5).map(|x| d.mass(x)).collect::<Vec<_>>(),
+            &[0.125, 0.1875, 0.1875, 0.15625, 0.1171875],
+            1e-14,
+        );
+        assert_eq!(new!(3.0, 1.0).mass(0), 1.0);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(3.0, 0.5).mean(), 3.0);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(new!(2.5, 0.4).median(), 3.0);
+    }
+
+    #[test]
+    fn pgf() {
+        let d = new!(2.5, 0.4);
+        assert::close(d.pgf(0.0), d.mass(0), 1e-15);
+        assert::close(d.pgf(1.0), 1.0, 1e-15);
+        assert::close(d.pgf(0.5), (0.4f64 / 0.7).powf(2.5), 1e-15);
+        assert!(d.pgf(2.0).is_nan());
+        assert_eq!(d.factorial_moment(0), 1.0);
+        assert::close(d.factorial_moment(1), d.mean(), 1e-12);
+        assert::close(d.factorial_moment(2), 2.5 * 3.5 * 1.5 * 1.5, 1e-12);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!(2.5, 0.4);
+        let mut source = source::default(42);
+        let samples = Independent(&d, &mut source)
+            .take(100000)
+            .collect::<Vec<_>>();
+        let mean = samples.iter().fold(0.0, |sum, &x| sum + x as f64) / 100000.0;
+        assert::close(mean / d.mean(), 1.0, 0.02);
+        let count = samples.iter().filter(|&&x| x <= 3).count();
+        assert::close(count as f64 / 100000.0, d.distribution(3.0), 0.01);
+    }
+
+    #[test]
+    fn try_new() {
+        use Error;
+        assert!(NegativeBinomial::try_new(2.0, 1.0).is_ok());
+        assert_eq!(
+            NegativeBinomial::try_new(2.0, 0.0).unwrap_err(),
+            Error::NotProbability("p")
+        );
+        assert_eq!(
+            NegativeBinomial::try_new(0.0, 0.5).unwrap_err(),
+            Error::NotPositive("r")
+        );
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!(3.0, 0.5).variance(), 6.0);
+    }
+}
@@ -1,12 +1,13 @@
-use alloc::{vec, vec::Vec};
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use math;
 use source::Source;
 
 /// A PERT distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Pert {
     a: f64,
     b: f64,
@@ -36,6 +37,19 @@ impl Pert {
         }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(a: f64, b: f64, c: f64) -> Result<Self, Error> {
+        error::finite("a", a)?;
+        error::finite("b", b)?;
+        error::finite("c", c)?;
+        error::require("a < b", a < b)?;
+        error::require("b < c", b < c)?;
+        Ok(Pert::new(a, b, c))
+    }
+
     /// Return the first parameter.
     #[inline(always)]
     pub fn a(&self) -> f64 {
@@ -75,8 +89,10 @@ impl distribution::Continuous for Pert {
         } else {
             let scale = self.c - self.a;
             let x = (x - self.a) / scale;
-            ((self.alpha - 1.0) * x.ln() + (self.beta - 1.0) * (-x).ln_1p() - self.ln_beta).exp()
-                / scale
+            math::exp(
+                (self.alpha - 1.0) * math::ln(x) + (self.beta - 1.0) * math::ln_1p(-x)
+                    - self.ln_beta,
+            ) / scale
         }
     }
 }
@@ -100,7 +116,7 @@ impl distribution::Entropy for Pert {
     fn entropy(&self) -> f64 {
         use special::Gamma;
         let sum = self.alpha + self.beta;
-        (self.c - self.a).ln() + self.ln_beta
+        math::ln(self.c - self.a) + self.ln_beta
             - (self.alpha - 1.0) * self.alpha.digamma()
             - (self.beta - 1.0) * self.beta.digamma()
             + (sum - 2.0) * sum.digamma()
@@ -140,9 +156,12 @@ impl distribution::Median for Pert {
     }
 }
 
-impl distribution::Modes for Pert {
-    fn modes(&self) -> Vec<f64> {
-        vec![self.b]
+impl distribution::ModesIter for Pert {
+    type Iter<'l> = distribution::ModePair<f64>;
+
+    #[inline]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(self.b)
     }
 }
 
@@ -167,6 +186,26 @@ impl distribution::Skewness for Pert {
     }
 }
 
+impl distribution::Support for Pert {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.c)
+    }
+}
+
+impl distribution::Survival for Pert {
+    fn survival(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x <= self.a {
+            1.0
+        } else if x >= self.c {
+            0.0
+        } else {
+            ((self.c - x) / (self.c - self.a)).inc_beta(self.beta, self.alpha, self.ln_beta)
+        }
+    }
+}
+
 impl distribution::Variance for Pert {
     fn variance(&self) -> f64 {
         use distribution::Mean;
@@ -324,6 +363,20 @@ mod tests {
         assert::close(new!(-1.0, 0.8, 2.0).skewness(), -0.17797249266332246, 1e-14);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(-1.0, 0.5, 2.0);
+        let x = vec![-1.5, -1.0, 0.0, 0.5, 1.0, 2.0, 2.5];
+
+        assert::close(
+            &x.iter()
+                .map(|&x| d.survival(x) + d.distribution(x))
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+    }
+
     #[test]
     fn variance() {
         assert::close(new!(0.0, 0.5, 1.0).variance(), 0.25 / 7.0, 1e-14);
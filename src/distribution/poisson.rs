@@ -0,0 +1,487 @@
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution;
+use error::{self, Error};
+use math;
+use source::Source;
+
+/// A Poisson distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Poisson {
+    lambda: f64,
+    sampler: Sampler,
+}
+
+impl Poisson {
+    /// Create a Poisson distribution with rate `lambda`.
+    ///
+    /// It should hold that `lambda > 0`.
+    #[inline]
+    pub fn new(lambda: f64) -> Self {
+        should!(lambda > 0.0);
+        Poisson {
+            lambda,
+            sampler: Sampler::new(lambda),
+        }
+    }
+
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(lambda: f64) -> Result<Self, Error> {
+        error::positive("lambda", lambda)?;
+        Ok(Poisson::new(lambda))
+    }
+
+    /// Return the rate parameter.
+    #[inline(always)]
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl distribution::CharacteristicFunction for Poisson {
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        let modulus = math::exp(self.lambda * (libm::cos(t) - 1.0));
+        let argument = self.lambda * libm::sin(t);
+        (modulus * libm::cos(argument), modulus * libm::sin(argument))
+    }
+}
+
+impl distribution::Discrete for Poisson {
+    /// Compute the probability mass function.
+    ///
+    /// A saddle-point expansion is used, which retains the accuracy for large
+    /// `x` and `lambda`.
+    ///
+    /// ## References
+    ///
+    /// 1. C. Loader, “Fast and Accurate Computation of Binomial Probabilities,”
+    ///    2000.
+    fn mass(&self, x: usize) -> f64 {
+        use core::f64::consts::PI;
+        use distribution::binomial::{ln_d0, stirlerr};
+        if x == 0 {
+            return math::exp(-self.lambda);
+        }
+        let x = x as f64;
+        math::exp(-stirlerr(x) - ln_d0(x, self.lambda)) / (2.0 * PI * x).sqrt()
+    }
+}
+
+impl distribution::Distribution for Poisson {
+    type Value = usize;
+
+    fn distribution(&self, x: f64) -> f64 {
+        use distribution::gamma;
+        nonnan!(x);
+        if x < 0.0 {
+            return 0.0;
+        }
+        if x == f64::INFINITY {
+            return 1.0;
+        }
+        gamma::compl_inc_gamma(self.lambda, x.floor() + 1.0)
+    }
+
+    #[inline]
+    fn probability(&self, interval: core::ops::Range<f64>) -> f64 {
+        distribution::integer_probability(self, interval)
+    }
+}
+
+impl distribution::Inverse for Poisson {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The support is unbounded, and `usize::MAX` is returned for `p = 1`.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::{gaussian, Distribution};
+        should!((0.0..=1.0).contains(&p));
+        if p <= 0.0 {
+            return 0;
+        }
+        if p >= 1.0 {
+            return usize::MAX;
+        }
+        let guess = self.lambda + self.lambda.sqrt() * gaussian::inverse(p);
+        let mut x = guess.max(0.0).floor();
+        while self.distribution(x) < p {
+            x += 1.0;
+        }
+        while x > 0.0 && self.distribution(x - 1.0) >= p {
+            x -= 1.0;
+        }
+        x as usize
+    }
+}
+
+impl distribution::Kurtosis for Poisson {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.lambda.recip()
+    }
+}
+
+impl distribution::Mean for Poisson {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl distribution::Median for Poisson {
+    #[inline]
+    fn median(&self) -> f64 {
+        use distribution::Inverse;
+        self.inverse(0.5) as f64
+    }
+}
+
+impl distribution::Mgf for Poisson {
+    #[inline]
+    fn log_mgf(&self, t: f64) -> f64 {
+        self.lambda * math::exp_m1(t)
+    }
+}
+
+impl distribution::ModesIter for Poisson {
+    type Iter<'l> = distribution::ModePair<usize>;
+
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        use distribution::ModePair;
+        let x = self.lambda.floor();
+        if x == self.lambda {
+            ModePair::two(x as usize - 1, x as usize)
+        } else {
+            ModePair::one(x as usize)
+        }
+    }
+}
+
+impl distribution::Pgf for Poisson {
+    #[inline]
+    fn pgf(&self, z: f64) -> f64 {
+        math::exp(self.lambda * (z - 1.0))
+    }
+
+    #[inline]
+    fn factorial_moment(&self, k: usize) -> f64 {
+        math::powf(self.lambda, k as f64)
+    }
+}
+
+impl distribution::Sample for Poisson {
+    /// Draw a sample.
+    ///
+    /// For small rates, the sample is drawn by sequential inversion;
+    /// otherwise, the transformed rejection method with squeeze (PTRS) is
+    /// used, whose cost does not depend on the rate.
+    ///
+    /// ## References
+    ///
+    /// 1. W. Hörmann, “The transformed rejection method for generating Poisson
+    ///    random variables,” Insurance: Mathematics and Economics, vol. 12,
+    ///    no. 1, pp. 39–45, 1993.
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> usize
+    where
+        S: Source,
+    {
+        match self.sampler {
+            Sampler::Inversion(ref sampler) => sampler.sample(source),
+            Sampler::Ptrs(ref sampler) => sampler.sample(source),
+        }
+    }
+
+    fn sample_into<S>(&self, buffer: &mut [usize], source: &mut S)
+    where
+        S: Source,
+    {
+        match self.sampler {
+            Sampler::Inversion(ref sampler) => {
+                for x in buffer.iter_mut() {
+                    *x = sampler.sample(source);
+                }
+            }
+            Sampler::Ptrs(ref sampler) => {
+                for x in buffer.iter_mut() {
+                    *x = sampler.sample(source);
+                }
+            }
+        }
+    }
+}
+
+impl distribution::Skewness for Poisson {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.lambda.sqrt().recip()
+    }
+}
+
+impl distribution::Support for Poisson {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl distribution::Variance for Poisson {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.lambda
+    }
+}
+
+/// A sampler chosen according to the rate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Sampler {
+    Inversion(Inversion),
+    Ptrs(Ptrs),
+}
+
+impl Sampler {
+    fn new(lambda: f64) -> Self {
+        if lambda < 10.0 {
+            Sampler::Inversion(Inversion::new(lambda))
+        } else {
+            Sampler::Ptrs(Ptrs::new(lambda))
+        }
+    }
+}
+
+/// A sampler based on the sequential inversion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Inversion {
+    lambda: f64,
+    p0: f64,
+}
+
+impl Inversion {
+    fn new(lambda: f64) -> Self {
+        Inversion {
+            lambda,
+            p0: math::exp(-lambda),
+        }
+    }
+
+    fn sample<S: Source>(&self, source: &mut S) -> usize {
+        let &Inversion { lambda, p0 } = self;
+        let mut x = 0;
+        let mut p = p0;
+        let mut u = source.read::<f64>();
+        while u > p {
+            u -= p;
+            x += 1;
+            p *= lambda / x as f64;
+            if p == 0.0 {
+                x = 0;
+                p = p0;
+                u = source.read::<f64>();
+            }
+        }
+        x
+    }
+}
+
+/// A sampler based on the PTRS algorithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ptrs {
+    lambda: f64,
+    ln_lambda: f64,
+    a: f64,
+    b: f64,
+    ln_alpha: f64,
+    v_r: f64,
+}
+
+impl Ptrs {
+    fn new(lambda: f64) -> Self {
+        let b = 0.931 + 2.53 * lambda.sqrt();
+        Ptrs {
+            lambda,
+            ln_lambda: math::ln(lambda),
+            a: -0.059 + 0.02483 * b,
+            b,
+            ln_alpha: math::ln(1.1239 + 1.1328 / (b - 3.4)),
+            v_r: 0.9277 - 3.6224 / (b - 2.0),
+        }
+    }
+
+    fn sample<S: Source>(&self, source: &mut S) -> usize {
+        let &Ptrs {
+            lambda,
+            ln_lambda,
+            a,
+            b,
+            ln_alpha,
+            v_r,
+        } = self;
+        loop {
+            let u = source.read::<f64>() - 0.5;
+            let v = source.read::<f64>();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+            if us >= 0.07 && v <= v_r {
+                return k as usize;
+            }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                continue;
+            }
+            if math::ln(v) + ln_alpha - math::ln(a / (us * us) + b)
+                <= -lambda + k * ln_lambda - special::Gamma::ln_gamma(k + 1.0).0
+            {
+                return k as usize;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($lambda:expr) => (Poisson::new($lambda));
+    );
+
+    #[test]
+    fn characteristic() {
+        let d = new!(2.5);
+        assert_eq!(d.characteristic(0.0), (1.0, 0.0));
+        let (re, im) = d.characteristic(1.0);
+        let expected = d.outcomes(1e-16).fold((0.0, 0.0), |(re, im), (x, p)| {
+            (re + p * (x as f64).cos(), im + p * (x as f64).sin())
+        });
+        assert::close(&[re, im], &[expected.0, expected.1], 1e-14);
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(2.5);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0, 5.0, 10.0];
+        let p = vec![
+            0.0,
+            0.0820849986238988,
+            0.0820849986238988,
+            0.2872974951836458,
+            0.5438131158833297,
+            0.9579789618046942,
+            0.9999383730898754,
+        ];
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+        assert_eq!(d.distribution(f64::INFINITY), 1.0);
+        assert_eq!(d.distribution(1e308), 1.0);
+        assert!(d.distribution(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(2.5);
+        let p = vec![0.0, 0.05, 0.0820849986238988, 0.1, 0.5, 0.9, 1.0 - 1e-12];
+        let x = vec![0, 0, 0, 1, 2, 5, 20];
+        assert_eq!(p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), x);
+        assert_eq!(d.inverse(1.0), usize::MAX);
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(2.5);
+        let x = vec![0, 1, 2, 5, 10];
+        let p = vec![
+            0.0820849986238988,
+            0.205212496559747,
+            0.25651562069968387,
+            0.0668009428905426,
+            0.00021572518449585203,
+        ];
+        assert::close(&x.iter().map(|&x| d.mass(x)).collect::<Vec<_>>(), &p, 1e-14);
+
+        assert::close(
+            new!(1000.0).mass(1000) / 0.012614611348721499718,
+            1.0,
+            1e-14,
+        );
+        assert::close(
+            new!(1e6).mass(1001000) / 0.00024189010120174141723,
+            1.0,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(2.5).mean(), 2.5);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(new!(2.5).median(), 2.0);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(2.5).modes(), vec![2]);
+        assert_eq!(new!(3.0).modes(), vec![2, 3]);
+    }
+
+    #[test]
+    fn pgf() {
+        let d = new!(2.5);
+        assert::close(d.pgf(0.0), d.mass(0), 1e-15);
+        assert::close(d.factorial_moment(2), 6.25, 1e-15);
+        assert::close(new!(1e6).factorial_moment(30) / 1e180, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default(42);
+        for &lambda in [0.5, 5.0, 10.0, 42.0, 1000.0].iter() {
+            let d = new!(lambda);
+            let samples = Independent(&d, &mut source)
+                .take(100000)
+                .collect::<Vec<_>>();
+            let mean = samples.iter().fold(0.0, |sum, &x| sum + x as f64) / 100000.0;
+            let variance = samples
+                .iter()
+                .fold(0.0, |sum, &x| sum + (x as f64 - mean).powi(2))
+                / 100000.0;
+            assert::close(mean / lambda, 1.0, 0.01);
+            assert::close(variance / lambda, 1.0, 0.05);
+            let x = d.median();
+            let count = samples.iter().filter(|&&y| y as f64 <= x).count();
+            assert::close(count as f64 / 100000.0, d.distribution(x), 0.01);
+        }
+    }
+
+    #[test]
+    fn sample_into() {
+        let d = new!(42.0);
+        let mut buffer = vec![0; 100000];
+        d.sample_into(&mut buffer, &mut source::default(42));
+        let mean = buffer.iter().fold(0.0, |sum, &x| sum + x as f64) / 100000.0;
+        assert::close(mean / d.mean(), 1.0, 0.01);
+
+        let mut source = source::default(42);
+        let samples = Independent(&d, &mut source).take(10).collect::<Vec<_>>();
+        assert_eq!(&buffer[..10], &samples[..]);
+    }
+
+    #[test]
+    fn skewness() {
+        assert_eq!(new!(4.0).skewness(), 0.5);
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!(2.5).variance(), 2.5);
+    }
+}
@@ -1,12 +1,13 @@
-use alloc::{vec, vec::Vec};
 #[allow(unused_imports)]
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use math;
 use source::Source;
 
 /// A triangular distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Triangular {
     a: f64,
     b: f64,
@@ -18,11 +19,25 @@ impl Triangular {
     ///
     /// It should hold that `a < b`, `a <= c`, and `c <= b`.
     #[inline]
-    pub fn new(a: f64, b: f64, c: f64) -> Self {
+    pub const fn new(a: f64, b: f64, c: f64) -> Self {
         should!(a < b && a <= c && c <= b);
         Triangular { a, b, c }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(a: f64, b: f64, c: f64) -> Result<Self, Error> {
+        error::finite("a", a)?;
+        error::finite("b", b)?;
+        error::finite("c", c)?;
+        error::require("a < b", a < b)?;
+        error::require("a <= c", a <= c)?;
+        error::require("c <= b", c <= b)?;
+        Ok(Triangular::new(a, b, c))
+    }
+
     /// Return the left endpoint of the support.
     #[inline(always)]
     pub fn a(&self) -> f64 {
@@ -73,9 +88,9 @@ impl distribution::Distribution for Triangular {
         } else {
             let diff = b - a;
             if x <= c {
-                (x - a).powi(2) / diff / (c - a)
+                math::powi(x - a, 2) / diff / (c - a)
             } else {
-                1.0 - (b - x).powi(2) / diff / (b - c)
+                1.0 - math::powi(b - x, 2) / diff / (b - c)
             }
         }
     }
@@ -84,7 +99,7 @@ impl distribution::Distribution for Triangular {
 impl distribution::Entropy for Triangular {
     #[inline]
     fn entropy(&self) -> f64 {
-        0.5 + ((self.b - self.a) / 2.0).ln()
+        0.5 + math::ln((self.b - self.a) / 2.0)
     }
 }
 
@@ -135,10 +150,12 @@ impl distribution::Median for Triangular {
     }
 }
 
-impl distribution::Modes for Triangular {
+impl distribution::ModesIter for Triangular {
+    type Iter<'l> = distribution::ModePair<f64>;
+
     #[inline]
-    fn modes(&self) -> Vec<f64> {
-        vec![self.c]
+    fn modes_iter(&self) -> Self::Iter<'_> {
+        distribution::ModePair::one(self.c)
     }
 }
 
@@ -158,7 +175,33 @@ impl distribution::Skewness for Triangular {
         let &Triangular { a, b, c } = self;
         let npart = (a + b - 2.0 * c) * (2.0 * a - b - c) * (a - 2.0 * b + c);
         let dpart = a * a + b * b + c * c - a * b - a * c - b * c;
-        (2f64.sqrt() * npart) / (5.0 * dpart.powf(3.0 / 2.0))
+        (2f64.sqrt() * npart) / (5.0 * math::powf(dpart, 3.0 / 2.0))
+    }
+}
+
+impl distribution::Support for Triangular {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+}
+
+impl distribution::Survival for Triangular {
+    fn survival(&self, x: f64) -> f64 {
+        nonnan!(x);
+        let &Triangular { a, b, c } = self;
+        if x <= a {
+            1.0
+        } else if b <= x {
+            0.0
+        } else {
+            let diff = b - a;
+            if x <= c {
+                1.0 - math::powi(x - a, 2) / diff / (c - a)
+            } else {
+                math::powi(b - x, 2) / diff / (b - c)
+            }
+        }
     }
 }
 
@@ -255,6 +298,38 @@ mod tests {
         assert_eq!(new!(1.0, 5.0, 3.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 5.0, 2.0);
+        let x = vec![0.0, 1.0, 1.5, 2.0, 3.0, 5.0, 6.0];
+
+        assert::close(
+            &x.iter()
+                .map(|&x| d.survival(x) + d.distribution(x))
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        use Error;
+        assert!(Triangular::try_new(1.0, 5.0, 3.0).is_ok());
+        assert_eq!(
+            Triangular::try_new(1.0, 5.0, f64::INFINITY).unwrap_err(),
+            Error::NotFinite("c")
+        );
+        assert_eq!(
+            Triangular::try_new(5.0, 1.0, 3.0).unwrap_err(),
+            Error::Violated("a < b")
+        );
+        assert_eq!(
+            Triangular::try_new(1.0, 5.0, 6.0).unwrap_err(),
+            Error::Violated("c <= b")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(1.0, 5.0, 3.0).variance(), (12.0 / 18.0));
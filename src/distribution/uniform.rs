@@ -2,10 +2,13 @@
 use special::Primitive;
 
 use distribution;
+use error::{self, Error};
+use estimate;
+use math;
 use source::Source;
 
 /// A continuous uniform distribution.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Uniform {
     a: f64,
     b: f64,
@@ -16,11 +19,22 @@ impl Uniform {
     ///
     /// It should hold that `a < b`.
     #[inline]
-    pub fn new(a: f64, b: f64) -> Self {
+    pub const fn new(a: f64, b: f64) -> Self {
         should!(a < b);
         Uniform { a, b }
     }
 
+    /// Create a distribution with the parameters validated.
+    ///
+    /// In contrast to `new`, the constraints on the parameters are checked in
+    /// all builds, and an error is returned if any of them is violated.
+    pub fn try_new(a: f64, b: f64) -> Result<Self, Error> {
+        error::finite("a", a)?;
+        error::finite("b", b)?;
+        error::require("a < b", a < b)?;
+        Ok(Uniform::new(a, b))
+    }
+
     /// Return the left endpoint of the support.
     #[inline(always)]
     pub fn a(&self) -> f64 {
@@ -41,6 +55,20 @@ impl Default for Uniform {
     }
 }
 
+impl distribution::CharacteristicFunction for Uniform {
+    fn characteristic(&self, t: f64) -> (f64, f64) {
+        if t == 0.0 {
+            return (1.0, 0.0);
+        }
+        let (a, b) = (t * self.a, t * self.b);
+        let norm = t * (self.b - self.a);
+        (
+            (libm::sin(b) - libm::sin(a)) / norm,
+            (libm::cos(a) - libm::cos(b)) / norm,
+        )
+    }
+}
+
 impl distribution::Continuous for Uniform {
     #[inline]
     fn density(&self, x: f64) -> f64 {
@@ -50,6 +78,14 @@ impl distribution::Continuous for Uniform {
             1.0 / (self.b - self.a)
         }
     }
+
+    fn density_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        let (a, b, density) = (self.a, self.b, 1.0 / (self.b - self.a));
+        for (value, &x) in out.iter_mut().zip(xs) {
+            *value = if x < a || x > b { 0.0 } else { density };
+        }
+    }
 }
 
 impl distribution::Distribution for Uniform {
@@ -65,12 +101,20 @@ impl distribution::Distribution for Uniform {
             (x - self.a) / (self.b - self.a)
         }
     }
+
+    fn distribution_slice(&self, xs: &[f64], out: &mut [f64]) {
+        should!(xs.len() == out.len());
+        let (a, b) = (self.a, self.b);
+        for (value, &x) in out.iter_mut().zip(xs) {
+            *value = ((x - a) / (b - a)).clamp(0.0, 1.0);
+        }
+    }
 }
 
 impl distribution::Entropy for Uniform {
     #[inline]
     fn entropy(&self) -> f64 {
-        (self.b - self.a).ln()
+        math::ln(self.b - self.a)
     }
 }
 
@@ -104,6 +148,17 @@ impl distribution::Median for Uniform {
     }
 }
 
+impl distribution::Mgf for Uniform {
+    fn log_mgf(&self, t: f64) -> f64 {
+        if t == 0.0 {
+            0.0
+        } else {
+            let z = t * (self.b - self.a);
+            t * self.a + math::ln(math::exp_m1(z) / z)
+        }
+    }
+}
+
 impl distribution::Sample for Uniform {
     #[inline]
     fn sample<S>(&self, source: &mut S) -> f64
@@ -121,22 +176,88 @@ impl distribution::Skewness for Uniform {
     }
 }
 
+impl distribution::Support for Uniform {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+}
+
+impl distribution::Survival for Uniform {
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x <= self.a {
+            1.0
+        } else if x >= self.b {
+            0.0
+        } else {
+            (self.b - x) / (self.b - self.a)
+        }
+    }
+}
+
 impl distribution::Variance for Uniform {
     #[inline]
     fn variance(&self) -> f64 {
-        (self.b - self.a).powi(2) / 12.0
+        math::powi(self.b - self.a, 2) / 12.0
+    }
+}
+
+impl estimate::MethodOfMoments for Uniform {
+    fn moments(data: &[f64]) -> Option<Self> {
+        if data.len() < 2 {
+            return None;
+        }
+        let mean = estimate::mean(data);
+        let half = (3.0 * estimate::variance(data, mean)).sqrt();
+        if half == 0.0 {
+            return None;
+        }
+        Some(Uniform::new(mean - half, mean + half))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use alloc::{vec, vec::Vec};
+    use assert;
+    use estimate;
     use prelude::*;
 
     macro_rules! new(
         ($a:expr, $b:expr) => (Uniform::new($a, $b));
     );
 
+    #[test]
+    fn characteristic() {
+        use core::f64::consts::PI;
+        let d = new!(-1.0, 3.0);
+        assert_eq!(d.characteristic(0.0), (1.0, 0.0));
+        let (re, im) = d.characteristic(PI / 4.0);
+        assert::close(re, 2f64.sqrt() / PI, 1e-15);
+        assert::close(im, 2f64.sqrt() / PI, 1e-15);
+    }
+
+    #[test]
+    fn constant() {
+        const D: Uniform = Uniform::new(0.0, 2.0);
+        assert_eq!(D.mean(), 1.0);
+    }
+
+    #[test]
+    fn density_slice() {
+        let d = new!(-1.0, 2.0);
+        let x = (-20..60).map(|i| i as f64 / 4.0).collect::<Vec<_>>();
+        let mut density = vec![0.0; x.len()];
+        let mut distribution = vec![0.0; x.len()];
+        d.density_slice(&x, &mut density);
+        d.distribution_slice(&x, &mut distribution);
+        for (i, &x) in x.iter().enumerate() {
+            assert::close(density[i], d.density(x), 1e-15);
+            assert::close(distribution[i], d.distribution(x), 1e-15);
+        }
+    }
+
     #[test]
     fn distribution() {
         let d = new!(-1.0, 1.0);
@@ -164,6 +285,13 @@ mod tests {
         assert_eq!(new!(0.0, E).entropy(), 1.0);
     }
 
+    #[test]
+    fn entropy_bits() {
+        use core::f64::consts::E;
+        assert_eq!(new!(0.0, 8.0).entropy_bits(), 3.0);
+        assert_eq!(new!(0.0, E).entropy_base(E), 1.0);
+    }
+
     #[test]
     fn inverse() {
         let d = new!(-1.0, 1.0);
@@ -188,6 +316,23 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).median(), 1.0);
     }
 
+    #[test]
+    fn mgf() {
+        use core::f64::consts::E;
+        let d = new!(0.0, 1.0);
+        assert_eq!(d.mgf(0.0), 1.0);
+        assert::close(d.mgf(1.0), E - 1.0, 1e-15);
+        assert::close(d.mgf(-1.0), 1.0 - E.recip(), 1e-15);
+    }
+
+    #[test]
+    fn moments() {
+        let d = estimate::moments::<Uniform>(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert::close(d.a(), 2.5 - 3.75f64.sqrt(), 1e-15);
+        assert::close(d.b(), 2.5 + 3.75f64.sqrt(), 1e-15);
+        assert!(estimate::moments::<Uniform>(&[1.0]).is_none());
+    }
+
     #[test]
     fn sample() {
         for x in Independent(&new!(7.0, 42.0), &mut source::default(42)).take(100) {
@@ -200,6 +345,20 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(-1.0, 1.0);
+        let x = vec![-1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5];
+
+        assert::close(
+            &x.iter()
+                .map(|&x| d.survival(x) + d.distribution(x))
+                .collect::<Vec<_>>(),
+            &vec![1.0; x.len()],
+            1e-14,
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.0, 12.0).variance(), 12.0);
@@ -0,0 +1,217 @@
+//! Statistical distances between distributions.
+//!
+//! For a pair of discrete distributions, the quantities are computed exactly
+//! by summing over the union of the supports. For a pair of continuous
+//! distributions, they are computed by numerical integration. A discrete and a
+//! continuous distribution are mutually singular, and the corresponding
+//! extreme values are returned.
+//!
+//! ## Example
+//!
+//! ```
+//! use probability::divergence;
+//! use probability::prelude::*;
+//!
+//! let p = Gaussian::new(0.0, 1.0);
+//! let q = Gaussian::new(1.0, 1.0);
+//! let distance = divergence::hellinger(&p, &q);
+//! ```
+
+#[allow(unused_imports)]
+use special::Primitive;
+
+use math;
+use quadrature;
+
+pub use distribution::Measure;
+
+const EPSILON: f64 = 1e-15;
+
+/// Compute the Hellinger distance.
+///
+/// The distance lies in `[0, 1]`.
+pub fn hellinger<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: Measure,
+    Q: Measure,
+{
+    let coefficient = reduce(p, q, |p, q| (p * q).sqrt()).unwrap_or(0.0);
+    (1.0 - coefficient).max(0.0).sqrt()
+}
+
+/// Compute the Jensen–Shannon divergence.
+///
+/// The divergence is computed in nats and lies in `[0, ln 2]`.
+pub fn jensen_shannon<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: Measure,
+    Q: Measure,
+{
+    use core::f64::consts::LN_2;
+    match reduce(p, q, |p, q| {
+        let m = 0.5 * (p + q);
+        0.5 * (entropy_term(p, m) + entropy_term(q, m))
+    }) {
+        Some(divergence) => divergence.clamp(0.0, LN_2),
+        _ => LN_2,
+    }
+}
+
+/// Compute the total variation distance.
+///
+/// The distance lies in `[0, 1]`.
+pub fn total_variation<P, Q>(p: &P, q: &Q) -> f64
+where
+    P: Measure,
+    Q: Measure,
+{
+    match reduce(p, q, |p, q| 0.5 * (p - q).abs()) {
+        Some(distance) => distance.clamp(0.0, 1.0),
+        _ => 1.0,
+    }
+}
+
+/// Sum or integrate a function of the two densities over the union of the
+/// supports.
+///
+/// If one distribution is discrete and the other is continuous, `None` is
+/// returned.
+pub(crate) fn reduce<P, Q, F>(p: &P, q: &Q, function: F) -> Option<f64>
+where
+    P: Measure,
+    Q: Measure,
+    F: Fn(f64, f64) -> f64,
+{
+    let (p_start, p_end) = p.support();
+    let (q_start, q_end) = q.support();
+    let (start, end) = (p_start.min(q_start), p_end.max(q_end));
+    match (p.is_discrete(), q.is_discrete()) {
+        (true, true) => {
+            let mut sum = 0.0;
+            let mut x = start;
+            while x <= end {
+                sum += function(p.weight(x), q.weight(x));
+                if p.distribution(x) >= 1.0 - EPSILON && q.distribution(x) >= 1.0 - EPSILON {
+                    break;
+                }
+                x += 1.0;
+            }
+            Some(sum)
+        }
+        (false, false) => {
+            let (p_bulk, q_bulk) = (p.bulk(), q.bulk());
+            let p_bulk = p_bulk.unwrap_or([end; 3]);
+            let q_bulk = q_bulk.unwrap_or([end; 3]);
+            let mut points = [
+                p_start, p_end, q_start, q_end, p_bulk[0], p_bulk[1], p_bulk[2], q_bulk[0],
+                q_bulk[1], q_bulk[2],
+            ];
+            Some(quadrature::integrate_pieces(
+                |x| function(p.weight(x), q.weight(x)),
+                start,
+                end,
+                &mut points,
+            ))
+        }
+        _ => None,
+    }
+}
+
+#[inline]
+fn entropy_term(p: f64, m: f64) -> f64 {
+    if p > 0.0 {
+        p * math::ln(p / m)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::{hellinger, jensen_shannon, total_variation};
+
+    #[test]
+    fn hellinger_continuous() {
+        let p = Gaussian::new(0.0, 1.0);
+        let q = Gaussian::new(1.0, 2.0);
+        let expected = (1.0 - (2.0 * 2.0 / 5.0f64).sqrt() * (-0.25 / 5.0f64).exp()).sqrt();
+        assert::close(hellinger(&p, &q), expected, 1e-10);
+        assert::close(hellinger(&p, &p), 0.0, 1e-6);
+
+        let p = Uniform::new(0.0, 1.0);
+        let q = Uniform::new(0.5, 2.5);
+        assert::close(hellinger(&p, &q), (1.0 - 0.5 * 0.5f64.sqrt()).sqrt(), 1e-10);
+    }
+
+    #[test]
+    fn hellinger_off_center() {
+        let p = Gaussian::new(100.0, 1.0);
+        assert::close(hellinger(&p, &p), 0.0, 1e-6);
+
+        let p = Gaussian::new(0.0, 1e-3);
+        let q = Gaussian::new(1e-3, 2e-3);
+        let expected = (1.0 - (2.0 * 2.0 / 5.0f64).sqrt() * (-0.25 / 5.0f64).exp()).sqrt();
+        assert::close(hellinger(&p, &q), expected, 1e-8);
+    }
+
+    #[test]
+    fn hellinger_discrete() {
+        let p = Bernoulli::new(0.25);
+        let q = Bernoulli::new(0.5);
+        let expected = (1.0 - (0.75f64 * 0.5).sqrt() - (0.25f64 * 0.5).sqrt()).sqrt();
+        assert::close(hellinger(&p, &q), expected, 1e-15);
+    }
+
+    #[test]
+    fn jensen_shannon_continuous() {
+        use core::f64::consts::LN_2;
+        let p = Uniform::new(0.0, 1.0);
+        let q = Uniform::new(1.0, 2.0);
+        assert::close(jensen_shannon(&p, &q), LN_2, 1e-10);
+        let q = Uniform::new(0.0, 2.0);
+        let expected = 0.5 * ((4.0f64 / 3.0).ln() + 0.5 * (2.0f64 / 3.0).ln() + 0.5 * LN_2);
+        assert::close(jensen_shannon(&p, &q), expected, 1e-10);
+    }
+
+    #[test]
+    fn jensen_shannon_discrete() {
+        use core::f64::consts::LN_2;
+        let p = Categorical::new(&[0.5, 0.5, 0.0]);
+        let q = Categorical::new(&[0.0, 0.5, 0.5]);
+        assert::close(jensen_shannon(&p, &q), 0.5 * LN_2, 1e-15);
+        assert_eq!(jensen_shannon(&p, &Gaussian::new(0.0, 1.0)), LN_2);
+    }
+
+    #[test]
+    fn total_variation_continuous() {
+        let p = Exponential::new(1.0);
+        let q = Exponential::new(2.0);
+        let x = 2f64.ln();
+        let expected = (-x).exp() - (-2.0 * x).exp();
+        assert::close(total_variation(&p, &q), expected, 1e-10);
+    }
+
+    #[test]
+    fn total_variation_off_center() {
+        let p = Gaussian::new(100.0, 1.0);
+        let q = Gaussian::new(101.0, 1.0);
+        let expected = 2.0 * Gaussian::new(0.0, 1.0).distribution(0.5) - 1.0;
+        assert::close(total_variation(&p, &q), expected, 1e-10);
+
+        let p = Gaussian::new(0.0, 1e-3);
+        let q = Gaussian::new(1e-3, 1e-3);
+        assert::close(total_variation(&p, &q), expected, 1e-10);
+    }
+
+    #[test]
+    fn total_variation_discrete() {
+        let p = Binomial::new(10, 0.25);
+        let q = Binomial::new(10, 0.5);
+        let expected = (0..11).fold(0.0, |sum, x| sum + 0.5 * (p.mass(x) - q.mass(x)).abs());
+        assert::close(total_variation(&p, &q), expected, 1e-15);
+        assert_eq!(total_variation(&p, &Uniform::new(0.0, 1.0)), 1.0);
+    }
+}
@@ -0,0 +1,93 @@
+//! Errors.
+
+use core::fmt;
+
+/// An error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A parameter is not a finite number.
+    NotFinite(&'static str),
+    /// A parameter is not strictly positive.
+    NotPositive(&'static str),
+    /// A probability is outside the admissible range.
+    NotProbability(&'static str),
+    /// A relation between parameters does not hold.
+    Violated(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotFinite(name) => write!(formatter, "`{}` should be finite", name),
+            Error::NotPositive(name) => write!(formatter, "`{}` should be positive", name),
+            Error::NotProbability(name) => {
+                write!(formatter, "`{}` should be a valid probability", name)
+            }
+            Error::Violated(requirement) => write!(formatter, "`{}` should hold", requirement),
+        }
+    }
+}
+
+/// Check that a parameter is finite.
+#[inline]
+pub(crate) fn finite(name: &'static str, value: f64) -> Result<(), Error> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::NotFinite(name))
+    }
+}
+
+/// Check that a parameter is finite and strictly positive.
+#[inline]
+pub(crate) fn positive(name: &'static str, value: f64) -> Result<(), Error> {
+    finite(name, value)?;
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::NotPositive(name))
+    }
+}
+
+/// Check that a probability lies strictly between zero and one.
+#[inline]
+pub(crate) fn probability(name: &'static str, value: f64) -> Result<(), Error> {
+    if value > 0.0 && value < 1.0 {
+        Ok(())
+    } else {
+        Err(Error::NotProbability(name))
+    }
+}
+
+/// Check that a relation between parameters holds.
+#[inline]
+pub(crate) fn require(requirement: &'static str, holds: bool) -> Result<(), Error> {
+    if holds {
+        Ok(())
+    } else {
+        Err(Error::Violated(requirement))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::{positive, Error};
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Error::NotPositive("sigma").to_string(),
+            "`sigma` should be positive"
+        );
+        assert_eq!(Error::Violated("a < b").to_string(), "`a < b` should hold");
+    }
+
+    #[test]
+    fn positive_nan() {
+        assert_eq!(positive("sigma", f64::NAN), Err(Error::NotFinite("sigma")));
+        assert_eq!(positive("sigma", 0.0), Err(Error::NotPositive("sigma")));
+        assert_eq!(positive("sigma", 1.0), Ok(()));
+    }
+}
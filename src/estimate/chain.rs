@@ -0,0 +1,221 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Gaussian, Inverse};
+
+/// Compute the autocorrelation of a chain at lags from zero to `max_lag`.
+///
+/// The autocovariances are normalized by the length of the chain, which makes
+/// the sequence positive semidefinite. The lags are capped at the length of
+/// the chain minus one. If the chain has fewer than two distinct values, the
+/// autocorrelations are `NaN`.
+pub fn autocorrelation(chain: &[f64], max_lag: usize) -> Vec<f64> {
+    let mean = super::mean(chain);
+    let variance = autocovariance(chain, mean, 0);
+    (0..=max_lag.min(chain.len().saturating_sub(1)))
+        .map(|lag| autocovariance(chain, mean, lag) / variance)
+        .collect()
+}
+
+/// Estimate the effective sample size of a chain.
+///
+/// The integrated autocorrelation time is computed by the initial monotone
+/// sequence estimator of Geyer, which sums pairs of consecutive
+/// autocorrelations while they stay positive and forces them to be
+/// nonincreasing. The time is bounded from below by `1 / log10(n)` so that
+/// antithetic chains do not produce an unbounded estimate. If the chain has
+/// fewer than four observations or fewer than two distinct values, `NaN` is
+/// returned.
+///
+/// ## References
+///
+/// 1. C. J. Geyer, “Practical Markov chain Monte Carlo,” Statistical Science,
+///    1992.
+pub fn effective_sample_size(chain: &[f64]) -> f64 {
+    let n = chain.len();
+    if n < 4 {
+        return f64::NAN;
+    }
+    let mean = super::mean(chain);
+    let variance = autocovariance(chain, mean, 0);
+    if variance == 0.0 {
+        return f64::NAN;
+    }
+    let rho = |lag: usize| autocovariance(chain, mean, lag) / variance;
+    let mut sum = 0.0;
+    let mut previous = f64::INFINITY;
+    let mut lag = 0;
+    while lag + 1 < n {
+        let pair = rho(lag) + rho(lag + 1);
+        if pair <= 0.0 {
+            break;
+        }
+        previous = pair.min(previous);
+        sum += previous;
+        lag += 2;
+    }
+    let time = (2.0 * sum - 1.0).max(1.0 / libm::log10(n as f64));
+    n as f64 / time
+}
+
+/// Compute the rank-normalized split potential scale reduction factor of
+/// several chains.
+///
+/// Each chain stores its draws of `dimension` quantities in row-major order,
+/// and a factor is computed for each quantity. The chains are split in halves,
+/// and the draws are replaced with the normal scores of their ranks in the
+/// pooled sample. The factor is the larger of the classical one computed for
+/// the normalized draws and the one computed for the normalized absolute
+/// deviations from the median, which detects chains with differing scales.
+/// Values close to one indicate convergence. The chains should have the same
+/// length; if the halves have fewer than two draws, `NaN` is returned.
+///
+/// ## References
+///
+/// 1. A. Vehtari, A. Gelman, D. Simpson, B. Carpenter, and P.-C. Bürkner,
+///    “Rank-normalization, folding, and localization: An improved R-hat for
+///    assessing convergence of MCMC,” Bayesian Analysis, 2021.
+pub fn r_hat(chains: &[&[f64]], dimension: usize) -> Vec<f64> {
+    should!(dimension > 0);
+    should!(chains.iter().all(|chain| chain.len() == chains[0].len()));
+    let n = chains.first().map_or(0, |chain| chain.len() / dimension) / 2;
+    (0..dimension)
+        .map(|j| {
+            if n < 2 {
+                return f64::NAN;
+            }
+            let mut draws = Vec::with_capacity(2 * n * chains.len());
+            for chain in chains {
+                let m = chain.len() / dimension;
+                draws.extend((0..n).map(|i| chain[i * dimension + j]));
+                draws.extend((m - n..m).map(|i| chain[i * dimension + j]));
+            }
+            let mut sorted = draws.clone();
+            sorted.sort_by(|one, other| one.total_cmp(other));
+            let median = super::quantile(&sorted, 0.5);
+            let folded = draws
+                .iter()
+                .map(|&x| (x - median).abs())
+                .collect::<Vec<_>>();
+            let bulk = classical(&normalize(&draws), n);
+            let tail = classical(&normalize(&folded), n);
+            bulk.max(tail)
+        })
+        .collect()
+}
+
+fn autocovariance(chain: &[f64], mean: f64, lag: usize) -> f64 {
+    let n = chain.len();
+    chain[..n - lag]
+        .iter()
+        .zip(&chain[lag..])
+        .fold(0.0, |sum, (&x, &y)| sum + (x - mean) * (y - mean))
+        / n as f64
+}
+
+/// Compute the classical potential scale reduction factor of consecutive
+/// chains of length `n`.
+fn classical(draws: &[f64], n: usize) -> f64 {
+    let means = draws.chunks(n).map(super::mean).collect::<Vec<_>>();
+    let within = draws
+        .chunks(n)
+        .zip(&means)
+        .fold(0.0, |sum, (chain, &mean)| {
+            sum + super::variance(chain, mean)
+        })
+        / means.len() as f64
+        * n as f64
+        / (n - 1) as f64;
+    let m = means.len() as f64;
+    let between = super::variance(&means, super::mean(&means)) * m / (m - 1.0);
+    (((n - 1) as f64 / n as f64 * within + between) / within).sqrt()
+}
+
+/// Replace the draws with the normal scores of their ranks.
+fn normalize(draws: &[f64]) -> Vec<f64> {
+    let gaussian = Gaussian::new(0.0, 1.0);
+    let count = draws.len() as f64;
+    super::correlation::ranks(draws)
+        .into_iter()
+        .map(|rank| gaussian.inverse((rank - 0.375) / (count + 0.25)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{autocorrelation, effective_sample_size, r_hat};
+
+    #[test]
+    fn autocorrelation_exact() {
+        assert::close(
+            &autocorrelation(&[1.0, 2.0, 3.0, 4.0], 5),
+            &[1.0, 0.25, -0.3, -0.45],
+            1e-15,
+        );
+        assert!(autocorrelation(&[1.0, 1.0], 1)[0].is_nan());
+    }
+
+    #[test]
+    fn effective_sample_size_independent() {
+        let mut source = source::default(42);
+        let data = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        assert::close(effective_sample_size(&data) / 10000.0, 1.0, 0.1);
+    }
+
+    #[test]
+    fn effective_sample_size_autoregressive() {
+        let mut source = source::default(42);
+        let noise = Gaussian::new(0.0, 1.0);
+        let mut x = 0.0;
+        let data = (0..20000)
+            .map(|_| {
+                x = 0.5 * x + noise.sample(&mut source);
+                x
+            })
+            .collect::<Vec<_>>();
+        assert::close(effective_sample_size(&data) / 20000.0, 1.0 / 3.0, 0.05);
+        assert!(effective_sample_size(&[1.0, 2.0]).is_nan());
+    }
+
+    #[test]
+    fn r_hat_converged() {
+        let mut source = source::default(42);
+        let gaussian = Gaussian::new(0.0, 1.0);
+        let chains = (0..4)
+            .map(|_| {
+                Independent(&gaussian, &mut source)
+                    .take(2 * 1000)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let chains = chains.iter().map(|chain| &chain[..]).collect::<Vec<_>>();
+        let values = r_hat(&chains, 2);
+        assert_eq!(values.len(), 2);
+        assert!(values.iter().all(|&value| value > 0.99 && value < 1.01));
+    }
+
+    #[test]
+    fn r_hat_diverged() {
+        let mut source = source::default(42);
+        let chains = [(0.0, 1.0), (0.0, 1.0), (0.0, 1.0), (2.0, 1.0), (0.0, 5.0)]
+            .iter()
+            .map(|&(mu, sigma)| {
+                Independent(&Gaussian::new(mu, sigma), &mut source)
+                    .take(1000)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let chains = chains.iter().map(|chain| &chain[..]).collect::<Vec<_>>();
+        assert!(r_hat(&chains[..4], 1)[0] > 1.2);
+        let value = r_hat(&[chains[0], chains[1], chains[2], chains[4]], 1)[0];
+        assert!(value > 1.05);
+        assert!(r_hat(&[&[1.0, 2.0, 3.0]], 1)[0].is_nan());
+    }
+}
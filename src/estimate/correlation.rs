@@ -0,0 +1,217 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Distribution, Gaussian};
+use test::student;
+
+/// An estimate of a correlation coefficient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Correlation {
+    /// The estimated coefficient.
+    pub coefficient: f64,
+    /// The two-sided p-value for the null hypothesis of no correlation.
+    pub p: f64,
+}
+
+/// Estimate Pearson’s correlation coefficient.
+///
+/// The p-value is based on Student’s t-distribution with `n - 2` degrees of
+/// freedom, which is exact for Gaussian data. The samples should have the
+/// same length of at least three, and neither should be constant.
+pub fn pearson(x: &[f64], y: &[f64]) -> Correlation {
+    should!(x.len() == y.len() && x.len() > 2);
+    let coefficient = coefficient(x, y);
+    Correlation {
+        coefficient,
+        p: significance(coefficient, x.len()),
+    }
+}
+
+/// Estimate Spearman’s rank correlation coefficient.
+///
+/// The coefficient is Pearson’s coefficient of the ranks, with tied values
+/// receiving the average of their ranks, and the p-value is computed as for
+/// Pearson’s coefficient, which is an approximation. The samples should have
+/// the same length of at least three, and neither should be constant.
+pub fn spearman(x: &[f64], y: &[f64]) -> Correlation {
+    should!(x.len() == y.len() && x.len() > 2);
+    let coefficient = coefficient(&ranks(x), &ranks(y));
+    Correlation {
+        coefficient,
+        p: significance(coefficient, x.len()),
+    }
+}
+
+/// Estimate Kendall’s rank correlation coefficient.
+///
+/// The coefficient is the tau-b variant, which accounts for ties, and the
+/// p-value is based on the normal approximation with the variance corrected
+/// for ties. The samples should have the same length of at least three, and
+/// neither should be constant.
+///
+/// ## References
+///
+/// 1. M. G. Kendall, “The treatment of ties in ranking problems,”
+///    Biometrika, 1945.
+pub fn kendall_tau(x: &[f64], y: &[f64]) -> Correlation {
+    should!(x.len() == y.len() && x.len() > 2);
+    let n = x.len();
+    let mut sum = 0.0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            sum += sign(x[i] - x[j]) * sign(y[i] - y[j]);
+        }
+    }
+    let (x_ties, y_ties) = (ties(x), ties(y));
+    let pairs = (n * (n - 1)) as f64 / 2.0;
+    let coefficient = sum / ((pairs - x_ties[0] / 2.0) * (pairs - y_ties[0] / 2.0)).sqrt();
+    let n = n as f64;
+    let variance = (n * (n - 1.0) * (2.0 * n + 5.0) - x_ties[2] - y_ties[2]) / 18.0
+        + x_ties[0] * y_ties[0] / (2.0 * n * (n - 1.0))
+        + x_ties[1] * y_ties[1] / (9.0 * n * (n - 1.0) * (n - 2.0));
+    let z = sum.abs() / variance.sqrt();
+    Correlation {
+        coefficient,
+        p: 2.0 * (1.0 - Gaussian::default().distribution(z)),
+    }
+}
+
+fn coefficient(x: &[f64], y: &[f64]) -> f64 {
+    let (x_mean, y_mean) = (super::mean(x), super::mean(y));
+    let (mut xy, mut xx, mut yy) = (0.0, 0.0, 0.0);
+    for (&x, &y) in x.iter().zip(y) {
+        let (x, y) = (x - x_mean, y - y_mean);
+        xy += x * y;
+        xx += x * x;
+        yy += y * y;
+    }
+    (xy / (xx * yy).sqrt()).clamp(-1.0, 1.0)
+}
+
+fn significance(coefficient: f64, n: usize) -> f64 {
+    let degrees = (n - 2) as f64;
+    let remainder = 1.0 - coefficient * coefficient;
+    if remainder <= 0.0 {
+        return 0.0;
+    }
+    student::tail(coefficient * (degrees / remainder).sqrt(), degrees)
+}
+
+/// Compute the ranks with tied values receiving the average of their ranks.
+pub(crate) fn ranks(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| data[i].total_cmp(&data[j]));
+    let mut ranks = vec![0.0; n];
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j < n && data[order[j]] == data[order[i]] {
+            j += 1;
+        }
+        let rank = (i + j + 1) as f64 / 2.0;
+        for &k in &order[i..j] {
+            ranks[k] = rank;
+        }
+        i = j;
+    }
+    ranks
+}
+
+/// Compute the sums of `t(t - 1)`, `t(t - 1)(t - 2)`, and `t(t - 1)(2t + 5)`
+/// over the sizes `t` of the groups of tied values.
+fn ties(data: &[f64]) -> [f64; 3] {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|one, other| one.total_cmp(other));
+    let mut sums = [0.0; 3];
+    let mut i = 0;
+    while i < sorted.len() {
+        let mut j = i;
+        while j < sorted.len() && sorted[j] == sorted[i] {
+            j += 1;
+        }
+        let t = (j - i) as f64;
+        sums[0] += t * (t - 1.0);
+        sums[1] += t * (t - 1.0) * (t - 2.0);
+        sums[2] += t * (t - 1.0) * (2.0 * t + 5.0);
+        i = j;
+    }
+    sums
+}
+
+#[inline]
+fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{kendall_tau, pearson, spearman};
+
+    const X: [f64; 6] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    const Y: [f64; 6] = [2.0, 1.0, 4.0, 3.0, 7.0, 5.0];
+
+    #[test]
+    fn pearson_coefficient() {
+        let correlation = pearson(&X, &Y);
+        let expected = 16.0 / (17.5f64 * 70.0 / 3.0).sqrt();
+        assert::close(correlation.coefficient, expected, 1e-14);
+        let t = expected * (4.0 / (1.0 - expected * expected)).sqrt();
+        assert::close(correlation.p, ::test::student::tail(t, 4.0), 1e-15);
+        assert::close(pearson(&X, &X).coefficient, 1.0, 1e-15);
+        assert_eq!(pearson(&X, &X).p, 0.0);
+    }
+
+    #[test]
+    fn spearman_coefficient() {
+        let correlation = spearman(&X, &Y);
+        assert::close(correlation.coefficient, 1.0 - 6.0 * 6.0 / 210.0, 1e-14);
+        let y = X.iter().map(|&x| x * x * x).collect::<Vec<_>>();
+        assert::close(spearman(&X, &y).coefficient, 1.0, 1e-15);
+    }
+
+    #[test]
+    fn kendall_coefficient() {
+        let correlation = kendall_tau(&X, &Y);
+        assert::close(correlation.coefficient, 9.0 / 15.0, 1e-14);
+        let z = 9.0 / (6.0f64 * 5.0 * 17.0 / 18.0).sqrt();
+        assert::close(
+            correlation.p,
+            2.0 * (1.0 - Gaussian::default().distribution(z)),
+            1e-14,
+        );
+
+        let x = [1.0, 1.0, 2.0, 3.0];
+        let y = [1.0, 2.0, 2.0, 3.0];
+        assert::close(kendall_tau(&x, &y).coefficient, 4.0 / 5.0, 1e-14);
+    }
+
+    #[test]
+    fn independent() {
+        let mut source = source::default(42);
+        let x = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(200)
+            .collect::<Vec<_>>();
+        let y = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(200)
+            .collect::<Vec<_>>();
+        assert!(pearson(&x, &y).p > 0.05);
+        assert!(spearman(&x, &y).p > 0.05);
+        assert!(kendall_tau(&x, &y).p > 0.05);
+        let z = x.iter().zip(&y).map(|(&x, &y)| x + y).collect::<Vec<_>>();
+        assert!(pearson(&x, &z).p < 1e-6);
+        assert!(spearman(&x, &z).p < 1e-6);
+        assert!(kendall_tau(&x, &z).p < 1e-6);
+    }
+}
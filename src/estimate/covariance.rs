@@ -0,0 +1,135 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use math;
+
+/// Estimate the covariance matrix of a sample.
+///
+/// The observations are stored by rows with `dimension` values each, and the
+/// result is stored by rows. The estimate is unbiased. There should be at
+/// least two observations.
+pub fn covariance(data: &[f64], dimension: usize) -> Vec<f64> {
+    let (centered, n) = center(data, dimension);
+    let mut matrix = scatter(&centered, dimension);
+    for value in matrix.iter_mut() {
+        *value /= (n - 1) as f64;
+    }
+    matrix
+}
+
+/// Estimate the covariance matrix of a sample with Ledoit–Wolf shrinkage.
+///
+/// The sample covariance matrix is shrunk toward a multiple of the identity
+/// matrix with the intensity chosen to minimize the expected squared error,
+/// which yields a well-conditioned estimate even when the number of
+/// observations is small relative to the dimension. The observations are
+/// stored by rows with `dimension` values each, and the result is stored by
+/// rows together with the shrinkage intensity in `[0, 1]`. There should be at
+/// least two observations, which should not all be equal.
+///
+/// ## References
+///
+/// 1. O. Ledoit and M. Wolf, “A well-conditioned estimator for
+///    large-dimensional covariance matrices,” Journal of Multivariate
+///    Analysis, 2004.
+pub fn ledoit_wolf(data: &[f64], dimension: usize) -> (Vec<f64>, f64) {
+    let (centered, n) = center(data, dimension);
+    let mut matrix = scatter(&centered, dimension);
+    for value in matrix.iter_mut() {
+        *value /= n as f64;
+    }
+    let p = dimension;
+    let mu = (0..p).fold(0.0, |sum, i| sum + matrix[i * p + i]) / p as f64;
+    let mut distance = 0.0;
+    for i in 0..p {
+        for j in 0..p {
+            let target = if i == j { mu } else { 0.0 };
+            distance += math::powi(matrix[i * p + j] - target, 2);
+        }
+    }
+    distance /= p as f64;
+    let mut spread = 0.0;
+    for row in centered.chunks(p) {
+        for i in 0..p {
+            for j in 0..p {
+                spread += math::powi(row[i] * row[j] - matrix[i * p + j], 2);
+            }
+        }
+    }
+    spread /= (n * n * p) as f64;
+    should!(distance > 0.0);
+    let shrinkage = spread.min(distance) / distance;
+    for value in matrix.iter_mut() {
+        *value *= 1.0 - shrinkage;
+    }
+    for i in 0..p {
+        matrix[i * p + i] += shrinkage * mu;
+    }
+    (matrix, shrinkage)
+}
+
+fn center(data: &[f64], dimension: usize) -> (Vec<f64>, usize) {
+    should!(dimension > 0 && data.len() % dimension == 0);
+    let n = data.len() / dimension;
+    should!(n > 1);
+    let mut mean = vec![0.0; dimension];
+    for row in data.chunks(dimension) {
+        for (mean, &x) in mean.iter_mut().zip(row) {
+            *mean += x / n as f64;
+        }
+    }
+    let centered = data
+        .chunks(dimension)
+        .flat_map(|row| row.iter().zip(&mean).map(|(&x, &mean)| x - mean))
+        .collect();
+    (centered, n)
+}
+
+fn scatter(centered: &[f64], dimension: usize) -> Vec<f64> {
+    let p = dimension;
+    let mut matrix = vec![0.0; p * p];
+    for row in centered.chunks(p) {
+        for i in 0..p {
+            for j in i..p {
+                matrix[i * p + j] += row[i] * row[j];
+            }
+        }
+    }
+    for i in 0..p {
+        for j in 0..i {
+            matrix[i * p + j] = matrix[j * p + i];
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::{covariance, ledoit_wolf};
+
+    const DATA: [f64; 10] = [1.0, 2.0, 2.0, 1.0, 3.0, 5.0, 4.0, 3.0, 6.0, 4.0];
+
+    #[test]
+    fn unbiased() {
+        assert::close(&covariance(&DATA, 2), &[3.7, 1.75, 1.75, 2.5], 1e-14);
+    }
+
+    #[test]
+    fn shrunk() {
+        let (matrix, shrinkage) = ledoit_wolf(&DATA, 2);
+        assert::close(shrinkage, 0.6863403944485026, 1e-14);
+        assert::close(
+            &matrix,
+            &[
+                2.6305566106647187,
+                0.43912344777209633,
+                0.43912344777209633,
+                2.3294433893352813,
+            ],
+            1e-14,
+        );
+    }
+}
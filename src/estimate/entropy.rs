@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use math;
+
+/// Estimate the differential entropy of a continuous sample.
+///
+/// The estimate is the one of Kozachenko and Leonenko based on the distance
+/// from each observation to its nearest neighbor, and it is computed in nats
+/// for direct comparison with `Entropy`. If the sample has fewer than two
+/// observations, `NaN` is returned; if it contains repeated values, negative
+/// infinity is returned.
+///
+/// ## References
+///
+/// 1. L. F. Kozachenko and N. N. Leonenko, “Sample estimate of the entropy of
+///    a random vector,” Problems of Information Transmission, 1987.
+pub fn entropy(data: &[f64]) -> f64 {
+    use special::Gamma as SpecialGamma;
+
+    let n = data.len();
+    if n < 2 {
+        return f64::NAN;
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|one, other| one.total_cmp(other));
+    let sum = (0..n).fold(0.0, |sum, i| {
+        let distance = match i {
+            0 => sorted[1] - sorted[0],
+            i if i + 1 == n => sorted[i] - sorted[i - 1],
+            i => (sorted[i] - sorted[i - 1]).min(sorted[i + 1] - sorted[i]),
+        };
+        sum + math::ln(distance)
+    });
+    SpecialGamma::digamma(n as f64) - SpecialGamma::digamma(1.0) + math::ln(2f64) + sum / n as f64
+}
+
+/// Estimate the entropy of a discrete sample.
+///
+/// The estimate is the plug-in one computed from the observed frequencies
+/// with the bias correction of Miller and Madow, and it is computed in nats
+/// for direct comparison with `Entropy`. If the sample is empty, `NaN` is
+/// returned.
+///
+/// ## References
+///
+/// 1. G. A. Miller, “Note on the bias of information estimates,” Information
+///    Theory in Psychology, 1955.
+pub fn entropy_discrete(data: &[usize]) -> f64 {
+    let n = data.len();
+    if n == 0 {
+        return f64::NAN;
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable();
+    let mut counts = Vec::new();
+    let mut count = 1;
+    for i in 1..n {
+        if sorted[i] == sorted[i - 1] {
+            count += 1;
+        } else {
+            counts.push(count);
+            count = 1;
+        }
+    }
+    counts.push(count);
+    let plugin = counts.iter().fold(0.0, |sum, &count| {
+        let p = count as f64 / n as f64;
+        sum - p * math::ln(p)
+    });
+    plugin + (counts.len() - 1) as f64 / (2 * n) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{entropy, entropy_discrete};
+
+    #[test]
+    fn continuous() {
+        let mut source = source::default(42);
+        let distribution = Gaussian::new(1.0, 2.0);
+        let data = Independent(&distribution, &mut source)
+            .take(20000)
+            .collect::<Vec<_>>();
+        assert::close(entropy(&data), distribution.entropy(), 0.05);
+
+        let distribution = Uniform::new(0.0, 4.0);
+        let data = Independent(&distribution, &mut source)
+            .take(20000)
+            .collect::<Vec<_>>();
+        assert::close(entropy(&data), distribution.entropy(), 0.05);
+
+        assert!(entropy(&[1.0]).is_nan());
+        assert_eq!(entropy(&[1.0, 2.0, 2.0]), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn discrete() {
+        let plugin = -(0.5 * 0.5f64.ln() + 2.0 * 0.25 * 0.25f64.ln());
+        assert::close(entropy_discrete(&[0, 0, 1, 2]), plugin + 2.0 / 8.0, 1e-15);
+        assert_eq!(entropy_discrete(&[3, 3, 3]), 0.0);
+        assert!(entropy_discrete(&[]).is_nan());
+    }
+}
@@ -0,0 +1,454 @@
+use alloc::vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution;
+use linear;
+use math;
+use quadrature;
+use source::Source;
+
+const ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-10;
+
+/// Constraints on a maximum-entropy distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+    lower: f64,
+    upper: f64,
+    mean: Option<f64>,
+    variance: Option<f64>,
+}
+
+/// A maximum-entropy distribution.
+///
+/// The density is proportional to `exp(λ₁ x + λ₂ x²)` over the support, which
+/// covers the uniform, truncated exponential, and truncated Gaussian
+/// distributions along with their unbounded counterparts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MaxEntropy {
+    lower: f64,
+    upper: f64,
+    center: f64,
+    scale: f64,
+    lambda: [f64; 2],
+    norm: f64,
+    mean: f64,
+    variance: f64,
+}
+
+impl Constraints {
+    /// Create constraints with support `[lower, upper]`.
+    ///
+    /// The bounds are allowed to be infinite. It should hold that `lower <
+    /// upper`.
+    #[inline]
+    pub fn new(lower: f64, upper: f64) -> Self {
+        should!(lower < upper);
+        Constraints {
+            lower,
+            upper,
+            mean: None,
+            variance: None,
+        }
+    }
+
+    /// Constrain the mean.
+    #[inline]
+    pub fn mean(mut self, mean: f64) -> Self {
+        self.mean = Some(mean);
+        self
+    }
+
+    /// Constrain the variance, which requires the mean to be constrained too.
+    #[inline]
+    pub fn variance(mut self, variance: f64) -> Self {
+        self.variance = Some(variance);
+        self
+    }
+}
+
+/// Fit the distribution with the largest entropy satisfying constraints.
+///
+/// The Lagrange multipliers are found by Newton’s method applied to the dual
+/// problem, which is convex, with the required expectations computed by
+/// numerical integration. If the constraints are inconsistent, the
+/// distribution does not exist, or the iterations do not converge, `None` is
+/// returned. In particular, an unbounded support requires the mean to be
+/// constrained, and a support unbounded in both directions requires the
+/// variance to be constrained as well.
+///
+/// ## References
+///
+/// 1. E. T. Jaynes, “Information theory and statistical mechanics,” Physical
+///    Review, 1957.
+pub fn max_entropy(constraints: Constraints) -> Option<MaxEntropy> {
+    let Constraints {
+        lower,
+        upper,
+        mean,
+        variance,
+    } = constraints;
+    let (center, scale, target, free) = match (mean, variance) {
+        (None, None) if lower.is_finite() && upper.is_finite() => (
+            0.5 * (lower + upper),
+            0.5 * (upper - lower),
+            [0.0; 2],
+            [false; 2],
+        ),
+        (Some(mean), None) if mean > lower && mean < upper => {
+            let scale = if lower.is_finite() && upper.is_finite() {
+                0.5 * (upper - lower)
+            } else if lower.is_finite() {
+                mean - lower
+            } else if upper.is_finite() {
+                upper - mean
+            } else {
+                return None;
+            };
+            (mean, scale, [0.0; 2], [true, false])
+        }
+        (Some(mean), Some(variance)) if mean > lower && mean < upper && variance > 0.0 => {
+            if (mean - lower) * (upper - mean) <= variance {
+                return None;
+            }
+            (mean, variance.sqrt(), [0.0, 1.0], [true, true])
+        }
+        _ => return None,
+    };
+    let support = [(lower - center) / scale, (upper - center) / scale];
+    let mut lambda = match (support[0].is_finite(), support[1].is_finite()) {
+        (true, true) => [0.0; 2],
+        _ if free[1] => [0.0, -0.5],
+        (true, false) => [-1.0, 0.0],
+        _ => [1.0, 0.0],
+    };
+    let mut state = evaluate(support, lambda)?;
+    let mut iterations = 0;
+    loop {
+        let gradient = [state.moments[0] - target[0], state.moments[1] - target[1]];
+        let norm = (0..2)
+            .filter(|&i| free[i])
+            .fold(0.0f64, |norm, i| norm.max(gradient[i].abs()));
+        if norm < TOLERANCE {
+            break;
+        }
+        if iterations == ITERATIONS {
+            return None;
+        }
+        iterations += 1;
+        let step = if free[1] {
+            let covariance = state.covariance();
+            let mut matrix = vec![
+                covariance[0],
+                covariance[1],
+                -gradient[0],
+                covariance[1],
+                covariance[2],
+                -gradient[1],
+            ];
+            let step = linear::solve(&mut matrix, 2);
+            [step[0], step[1]]
+        } else {
+            [-gradient[0] / state.covariance()[0], 0.0]
+        };
+        let objective = state.norm - lambda[0] * target[0] - lambda[1] * target[1];
+        let mut factor = 1.0;
+        loop {
+            let candidate = [lambda[0] + factor * step[0], lambda[1] + factor * step[1]];
+            if let Some(next) = evaluate(support, candidate) {
+                let value = next.norm - candidate[0] * target[0] - candidate[1] * target[1];
+                if value <= objective + 1e-12 * objective.abs() {
+                    lambda = candidate;
+                    state = next;
+                    break;
+                }
+            }
+            factor *= 0.5;
+            if factor < 1e-10 {
+                return None;
+            }
+        }
+    }
+    let variance = state.moments[1] - state.moments[0] * state.moments[0];
+    Some(MaxEntropy {
+        lower,
+        upper,
+        center,
+        scale,
+        lambda,
+        norm: state.norm + math::ln(scale),
+        mean: center + scale * state.moments[0],
+        variance: scale * scale * variance,
+    })
+}
+
+impl MaxEntropy {
+    /// Return the Lagrange multipliers of the linear and quadratic terms of
+    /// the exponent with respect to the original variable.
+    pub fn multipliers(&self) -> (f64, f64) {
+        let [one, other] = self.lambda;
+        let (c, h) = (self.center, self.scale);
+        (one / h - 2.0 * other * c / (h * h), other / (h * h))
+    }
+
+    #[inline]
+    fn exponent(&self, x: f64) -> f64 {
+        let t = (x - self.center) / self.scale;
+        self.lambda[0] * t + self.lambda[1] * t * t - self.norm
+    }
+}
+
+impl distribution::Continuous for MaxEntropy {
+    fn density(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            return 0.0;
+        }
+        math::exp(self.exponent(x))
+    }
+}
+
+impl distribution::Distribution for MaxEntropy {
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        use distribution::Continuous;
+
+        if x <= self.lower {
+            return 0.0;
+        }
+        if x >= self.upper {
+            return 1.0;
+        }
+        if x <= self.mean {
+            quadrature::integrate(|x| self.density(x), self.lower, x).clamp(0.0, 1.0)
+        } else {
+            (1.0 - quadrature::integrate(|x| self.density(x), x, self.upper)).clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl distribution::Entropy for MaxEntropy {
+    #[inline]
+    fn entropy(&self) -> f64 {
+        let first = (self.mean - self.center) / self.scale;
+        let second = first * first + self.variance / (self.scale * self.scale);
+        self.norm - self.lambda[0] * first - self.lambda[1] * second
+    }
+}
+
+impl distribution::Inverse for MaxEntropy {
+    fn inverse(&self, p: f64) -> f64 {
+        use distribution::{Continuous, Distribution};
+
+        should!((0.0..=1.0).contains(&p));
+        if p == 0.0 {
+            return self.lower;
+        }
+        if p == 1.0 {
+            return self.upper;
+        }
+        let mut step = self.variance.sqrt();
+        let (mut a, mut b) = (self.lower, self.upper);
+        if !a.is_finite() {
+            a = self.mean - step;
+            while self.distribution(a) > p {
+                a -= step;
+                step *= 2.0;
+            }
+        }
+        if !b.is_finite() {
+            b = self.mean + step;
+            while self.distribution(b) < p {
+                b += step;
+                step *= 2.0;
+            }
+        }
+        let mut x = 0.5 * (a + b);
+        for _ in 0..ITERATIONS {
+            let value = self.distribution(x) - p;
+            if value == 0.0 {
+                break;
+            }
+            if value < 0.0 {
+                a = x;
+            } else {
+                b = x;
+            }
+            let previous = x;
+            let next = x - value / self.density(x);
+            x = if next > a && next < b {
+                next
+            } else {
+                0.5 * (a + b)
+            };
+            let tolerance = TOLERANCE * x.abs().max(1.0);
+            if b - a <= tolerance || (x - previous).abs() <= tolerance {
+                break;
+            }
+        }
+        x
+    }
+}
+
+impl distribution::Mean for MaxEntropy {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+}
+
+impl distribution::Sample for MaxEntropy {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        use distribution::Inverse;
+        self.inverse(source.read::<f64>())
+    }
+}
+
+impl distribution::Support for MaxEntropy {
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        (self.lower, self.upper)
+    }
+}
+
+impl distribution::Variance for MaxEntropy {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.variance
+    }
+}
+
+/// The normalizing constant and the moments of the standardized variable.
+struct State {
+    norm: f64,
+    moments: [f64; 4],
+}
+
+impl State {
+    #[inline]
+    fn covariance(&self) -> [f64; 3] {
+        let [m1, m2, m3, m4] = self.moments;
+        [m2 - m1 * m1, m3 - m1 * m2, m4 - m2 * m2]
+    }
+}
+
+/// Compute the normalizing constant and the moments given the multipliers.
+///
+/// If the density is not integrable, `None` is returned.
+fn evaluate(support: [f64; 2], lambda: [f64; 2]) -> Option<State> {
+    let [a, b] = support;
+    let [one, other] = lambda;
+    let decaying = |sign: f64| other < 0.0 || (other == 0.0 && sign * one < 0.0);
+    if (!a.is_finite() && !decaying(-1.0)) || (!b.is_finite() && !decaying(1.0)) {
+        return None;
+    }
+    let exponent = |t: f64| one * t + other * t * t;
+    let mut shift = f64::NEG_INFINITY;
+    for &t in &support {
+        if t.is_finite() {
+            shift = shift.max(exponent(t));
+        }
+    }
+    if other < 0.0 {
+        let vertex = -one / (2.0 * other);
+        if vertex > a && vertex < b {
+            shift = shift.max(exponent(vertex));
+        }
+    }
+    let weight = |t: f64| math::exp(exponent(t) - shift);
+    let norm = quadrature::integrate(weight, a, b);
+    if !(norm > 0.0 && norm.is_finite()) {
+        return None;
+    }
+    let mut moments = [0.0; 4];
+    for (k, moment) in moments.iter_mut().enumerate() {
+        *moment = quadrature::integrate(|t| math::powi(t, k as i32 + 1) * weight(t), a, b) / norm;
+    }
+    Some(State {
+        norm: math::ln(norm) + shift,
+        moments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::{max_entropy, Constraints};
+
+    #[test]
+    fn uniform() {
+        let distribution = max_entropy(Constraints::new(1.0, 3.0)).unwrap();
+        assert::close(distribution.density(2.5), 0.5, 1e-12);
+        assert::close(distribution.distribution(1.5), 0.25, 1e-12);
+        assert::close(distribution.inverse(0.75), 2.5, 1e-10);
+        assert::close(distribution.mean(), 2.0, 1e-12);
+        assert::close(distribution.variance(), 1.0 / 3.0, 1e-12);
+        assert::close(distribution.entropy(), 2f64.ln(), 1e-12);
+    }
+
+    #[test]
+    fn exponential() {
+        let distribution = max_entropy(Constraints::new(1.0, f64::INFINITY).mean(3.0)).unwrap();
+        let expected = Exponential::new(0.5);
+        for &x in &[0.5, 2.0, 4.0] {
+            assert::close(distribution.density(x + 1.0), expected.density(x), 1e-10);
+            assert::close(
+                distribution.distribution(x + 1.0),
+                expected.distribution(x),
+                1e-10,
+            );
+        }
+        assert::close(distribution.inverse(0.9), expected.inverse(0.9) + 1.0, 1e-8);
+        assert::close(distribution.variance(), 4.0, 1e-9);
+        assert::close(distribution.entropy(), expected.entropy(), 1e-9);
+    }
+
+    #[test]
+    fn gaussian() {
+        let constraints = Constraints::new(f64::NEG_INFINITY, f64::INFINITY)
+            .mean(1.0)
+            .variance(4.0);
+        let distribution = max_entropy(constraints).unwrap();
+        let expected = Gaussian::new(1.0, 2.0);
+        assert::close(distribution.density(2.0), expected.density(2.0), 1e-10);
+        assert::close(
+            distribution.distribution(-1.0),
+            expected.distribution(-1.0),
+            1e-10,
+        );
+        assert::close(distribution.entropy(), expected.entropy(), 1e-9);
+        let (one, other) = distribution.multipliers();
+        assert::close(&[one, other], &[0.25, -0.125], 1e-10);
+    }
+
+    #[test]
+    fn truncated() {
+        let constraints = Constraints::new(0.0, 1.0).mean(0.3).variance(0.05);
+        let distribution = max_entropy(constraints).unwrap();
+        assert::close(distribution.mean(), 0.3, 1e-10);
+        assert::close(distribution.variance(), 0.05, 1e-10);
+        let mut source = source::default(42);
+        let sum = Independent(&distribution, &mut source)
+            .take(1000)
+            .inspect(|&x| assert!((0.0..=1.0).contains(&x)))
+            .sum::<f64>();
+        assert::close(sum / 1000.0, 0.3, 0.02);
+    }
+
+    #[test]
+    fn infeasible() {
+        let infinity = f64::INFINITY;
+        assert!(max_entropy(Constraints::new(0.0, infinity)).is_none());
+        assert!(max_entropy(Constraints::new(-infinity, infinity).mean(0.0)).is_none());
+        assert!(max_entropy(Constraints::new(0.0, 1.0).mean(2.0)).is_none());
+        assert!(max_entropy(Constraints::new(0.0, 1.0).mean(0.5).variance(0.3)).is_none());
+        assert!(max_entropy(Constraints::new(0.0, infinity).mean(1.0).variance(2.0)).is_none());
+    }
+}
@@ -0,0 +1,294 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{self, Categorical, Gaussian};
+use estimate::{self, Report};
+use math;
+use source::Source;
+
+/// A mixture of Gaussian distributions fitted to a sample.
+///
+/// The mixture is fitted by the expectation–maximization algorithm with the
+/// means initialized via the k-means++ seeding. The result is a distribution
+/// itself, and the components are ordered by their means.
+///
+/// ## References
+///
+/// 1. A. P. Dempster, N. M. Laird, and D. B. Rubin, “Maximum likelihood from
+///    incomplete data via the EM algorithm,” Journal of the Royal Statistical
+///    Society, 1977.
+///
+/// 2. D. Arthur and S. Vassilvitskii, “k-means++: The advantages of careful
+///    seeding,” ACM-SIAM Symposium on Discrete Algorithms, 2007.
+#[derive(Clone, Debug)]
+pub struct GaussianMixture {
+    components: Vec<Gaussian>,
+    weights: Categorical,
+    report: Report,
+}
+
+impl GaussianMixture {
+    /// Fit a mixture of `k` components to a sample.
+    ///
+    /// The standard deviations of the components are kept above a millionth of
+    /// the one of the sample, and a component that is left with no
+    /// responsibility for any value is moved to the value explained worst. If
+    /// `k` is zero, the sample has fewer than `k` distinct values, or all the
+    /// values are equal, `None` is returned.
+    pub fn fit<S>(data: &[f64], k: usize, source: &mut S) -> Option<Self>
+    where
+        S: Source,
+    {
+        const ITERATIONS: usize = 1000;
+        const TOLERANCE: f64 = 1e-10;
+
+        let n = data.len();
+        if k == 0 || n < k {
+            return None;
+        }
+        let mut means = seed(data, k, source)?;
+        let mean = estimate::mean(data);
+        let deviation = estimate::variance(data, mean).sqrt();
+        if !deviation.is_finite() || deviation <= 0.0 {
+            return None;
+        }
+        let floor = (1e-6 * deviation).max(f64::MIN_POSITIVE);
+        let mut deviations = vec![deviation; k];
+        let mut weights = vec![1.0 / k as f64; k];
+        let mut responsibilities = vec![0.0; n * k];
+        let mut log_likelihood = f64::NEG_INFINITY;
+        let mut iterations = 0;
+        let mut converged = false;
+        while iterations < ITERATIONS {
+            iterations += 1;
+            let mut current = 0.0;
+            let mut worst = (0, f64::INFINITY);
+            for (i, &x) in data.iter().enumerate() {
+                let row = &mut responsibilities[(i * k)..((i + 1) * k)];
+                for j in 0..k {
+                    let z = (x - means[j]) / deviations[j];
+                    row[j] = math::ln(weights[j]) - math::ln(deviations[j]) - 0.5 * z * z;
+                }
+                let maximum = row.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+                let sum = row.iter().fold(0.0, |sum, &r| sum + math::exp(r - maximum));
+                let normalization = maximum + math::ln(sum);
+                for r in row.iter_mut() {
+                    *r = math::exp(*r - normalization);
+                }
+                current += normalization;
+                if normalization < worst.1 {
+                    worst = (i, normalization);
+                }
+            }
+            current -= 0.5 * n as f64 * math::ln(2.0 * core::f64::consts::PI);
+            let mut reseeded = false;
+            for j in 0..k {
+                let (mut total, mut sum) = (0.0, 0.0);
+                for (i, &x) in data.iter().enumerate() {
+                    total += responsibilities[i * k + j];
+                    sum += responsibilities[i * k + j] * x;
+                }
+                if total <= 0.0 {
+                    means[j] = data[worst.0];
+                    deviations[j] = deviation;
+                    weights[j] = 1.0 / n as f64;
+                    reseeded = true;
+                    continue;
+                }
+                means[j] = sum / total;
+                let mut sum = 0.0;
+                for (i, &x) in data.iter().enumerate() {
+                    sum += responsibilities[i * k + j] * math::powi(x - means[j], 2);
+                }
+                deviations[j] = (sum / total).sqrt().max(floor);
+                weights[j] = total / n as f64;
+            }
+            if reseeded {
+                let total = weights.iter().sum::<f64>();
+                weights.iter_mut().for_each(|weight| *weight /= total);
+                log_likelihood = f64::NEG_INFINITY;
+                continue;
+            }
+            let change = current - log_likelihood;
+            log_likelihood = current;
+            if change.abs() <= TOLERANCE * log_likelihood.abs() {
+                converged = true;
+                break;
+            }
+        }
+        let mut order = (0..k).collect::<Vec<_>>();
+        order.sort_by(|&i, &j| means[i].total_cmp(&means[j]));
+        let weights = order.iter().map(|&j| weights[j]).collect::<Vec<_>>();
+        Some(GaussianMixture {
+            components: order
+                .iter()
+                .map(|&j| Gaussian::new(means[j], deviations[j]))
+                .collect(),
+            weights: Categorical::new(&weights),
+            report: Report {
+                iterations,
+                log_likelihood,
+                converged,
+            },
+        })
+    }
+
+    /// Return the components.
+    #[inline(always)]
+    pub fn components(&self) -> &[Gaussian] {
+        &self.components
+    }
+
+    /// Return the weights of the components.
+    #[inline(always)]
+    pub fn weights(&self) -> &[f64] {
+        self.weights.p()
+    }
+
+    /// Return the convergence report, whose log-likelihood is the one of the
+    /// last iteration.
+    #[inline(always)]
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+}
+
+impl distribution::Continuous for GaussianMixture {
+    fn density(&self, x: f64) -> f64 {
+        self.components
+            .iter()
+            .zip(self.weights())
+            .fold(0.0, |sum, (component, &weight)| {
+                sum + weight * component.density(x)
+            })
+    }
+}
+
+impl distribution::Distribution for GaussianMixture {
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        self.components
+            .iter()
+            .zip(self.weights())
+            .fold(0.0, |sum, (component, &weight)| {
+                sum + weight * component.distribution(x)
+            })
+    }
+}
+
+impl distribution::Mean for GaussianMixture {
+    fn mean(&self) -> f64 {
+        self.components
+            .iter()
+            .zip(self.weights())
+            .fold(0.0, |sum, (component, &weight)| {
+                sum + weight * component.mean()
+            })
+    }
+}
+
+impl distribution::Sample for GaussianMixture {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        self.components[self.weights.sample(source)].sample(source)
+    }
+}
+
+/// Choose the initial means via the k-means++ seeding.
+fn seed<S: Source>(data: &[f64], k: usize, source: &mut S) -> Option<Vec<f64>> {
+    let n = data.len();
+    let mut means = Vec::with_capacity(k);
+    means.push(data[((source.read::<f64>() * n as f64) as usize).min(n - 1)]);
+    let mut distances = data
+        .iter()
+        .map(|&x| math::powi(x - means[0], 2))
+        .collect::<Vec<_>>();
+    while means.len() < k {
+        let total = distances.iter().fold(0.0, |sum, &d| sum + d);
+        if total == 0.0 {
+            return None;
+        }
+        let mut target = source.read::<f64>() * total;
+        let mut index = n - 1;
+        for (i, &distance) in distances.iter().enumerate() {
+            if distance > 0.0 && target <= distance {
+                index = i;
+                break;
+            }
+            target -= distance;
+        }
+        let mean = data[index];
+        means.push(mean);
+        for (distance, &x) in distances.iter_mut().zip(data) {
+            *distance = distance.min(math::powi(x - mean, 2));
+        }
+    }
+    Some(means)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::GaussianMixture;
+
+    #[test]
+    fn fit() {
+        let mut source = source::default(42);
+        let one = Gaussian::new(-2.0, 0.5);
+        let other = Gaussian::new(3.0, 1.0);
+        let mut data = Independent(&one, &mut source)
+            .take(3000)
+            .collect::<Vec<_>>();
+        data.extend(Independent(&other, &mut source).take(7000));
+        let mixture = GaussianMixture::fit(&data, 2, &mut source).unwrap();
+        assert!(mixture.report().converged);
+        assert::close(mixture.weights(), &[0.3, 0.7], 0.02);
+        let components = mixture.components();
+        assert::close(
+            &[components[0].mu(), components[1].mu()],
+            &[-2.0, 3.0],
+            0.05,
+        );
+        assert::close(
+            &[components[0].sigma(), components[1].sigma()],
+            &[0.5, 1.0],
+            0.05,
+        );
+        assert::close(
+            mixture.report().log_likelihood,
+            data.iter()
+                .fold(0.0, |sum, &x| sum + mixture.density(x).ln()),
+            1e-3,
+        );
+        assert::close(mixture.mean(), 1.5, 0.05);
+        assert!(GaussianMixture::fit(&[1.0, 1.0, 1.0], 2, &mut source).is_none());
+    }
+
+    #[test]
+    fn fit_degenerate() {
+        let mut source = source::default(42);
+        assert!(GaussianMixture::fit(&[1.0; 10], 1, &mut source).is_none());
+
+        let data = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 5.0, 6.0, 7.0];
+        let mixture = GaussianMixture::fit(&data, 2, &mut source).unwrap();
+        let components = mixture.components();
+        assert::close(components[0].mu(), 1.0, 1e-12);
+        assert!(components[0].sigma() > 0.0);
+        assert::close(components[1].mu(), 6.0, 1e-6);
+        assert!(data.iter().all(|&x| mixture.density(x).is_finite()));
+        assert!(mixture.weights().iter().all(|weight| weight.is_finite()));
+
+        let data = [0.0, 0.0, 0.0, 0.0, 1e6];
+        let mixture = GaussianMixture::fit(&data, 2, &mut source).unwrap();
+        assert!(mixture.components().iter().all(|c| c.mu().is_finite()));
+        assert!(mixture.components().iter().all(|c| c.sigma() > 0.0));
+    }
+}
@@ -0,0 +1,177 @@
+//! Parameter estimation.
+//!
+//! ## Example
+//!
+//! ```
+//! use probability::estimate;
+//! use probability::prelude::*;
+//!
+//! let data = [0.5, 1.2, -0.3, 0.8, 2.1];
+//! let estimate = estimate::maximum_likelihood::<Gaussian>(&data).unwrap();
+//! let (mu, sigma) = (estimate.distribution.mu(), estimate.distribution.sigma());
+//! let errors = estimate.errors;
+//! ```
+
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::Distribution;
+use math;
+
+mod chain;
+mod correlation;
+mod covariance;
+mod entropy;
+mod max_entropy;
+mod mixture;
+mod online;
+mod p2;
+mod qq;
+mod summary;
+mod survival;
+mod tail;
+
+pub use self::chain::{autocorrelation, effective_sample_size, r_hat};
+pub use self::correlation::{kendall_tau, pearson, spearman, Correlation};
+pub use self::covariance::{covariance, ledoit_wolf};
+pub use self::entropy::{entropy, entropy_discrete};
+pub use self::max_entropy::{max_entropy, Constraints, MaxEntropy};
+pub use self::mixture::GaussianMixture;
+pub use self::online::Online;
+pub use self::p2::P2Quantile;
+pub use self::qq::qq;
+pub use self::summary::Summary;
+pub use self::survival::{KaplanMeier, SurvivalStep};
+pub use self::tail::{hill, Hill};
+
+/// A distribution capable of estimating its parameters via maximum
+/// likelihood.
+pub trait MaximumLikelihood: Distribution + Sized {
+    /// Estimate the parameters from a sample.
+    ///
+    /// If the sample is too small or degenerate for the estimate to exist,
+    /// `None` is returned.
+    fn maximum_likelihood(data: &[Self::Value]) -> Option<Estimate<Self>>;
+}
+
+/// A distribution capable of estimating its parameters via the method of
+/// moments.
+pub trait MethodOfMoments: Distribution + Sized {
+    /// Estimate the parameters from a sample by matching the sample moments.
+    ///
+    /// If the sample is too small or its moments are inconsistent with the
+    /// distribution, `None` is returned.
+    fn moments(data: &[Self::Value]) -> Option<Self>;
+}
+
+/// The result of an estimation.
+#[derive(Clone, Debug)]
+pub struct Estimate<D> {
+    /// The fitted distribution.
+    pub distribution: D,
+    /// The estimated parameters in the order of the arguments of the
+    /// constructor.
+    pub parameters: Vec<f64>,
+    /// The standard errors of the parameters derived from the observed
+    /// information.
+    pub errors: Vec<f64>,
+    /// The convergence report.
+    pub report: Report,
+}
+
+/// A convergence report.
+#[derive(Clone, Copy, Debug)]
+pub struct Report {
+    /// The number of iterations performed, which is zero for estimators in
+    /// closed form.
+    pub iterations: usize,
+    /// The log-likelihood at the estimate.
+    pub log_likelihood: f64,
+    /// Indicate whether the iterations have converged.
+    pub converged: bool,
+}
+
+/// Estimate the parameters of a distribution via maximum likelihood.
+#[inline]
+pub fn maximum_likelihood<D>(data: &[D::Value]) -> Option<Estimate<D>>
+where
+    D: MaximumLikelihood,
+{
+    D::maximum_likelihood(data)
+}
+
+/// Estimate the parameters of a distribution via the method of moments.
+///
+/// The estimate is in closed form and can serve as a starting point for an
+/// iterative maximum-likelihood estimation.
+#[inline]
+pub fn moments<D>(data: &[D::Value]) -> Option<D>
+where
+    D: MethodOfMoments,
+{
+    D::moments(data)
+}
+
+impl Report {
+    /// Create a report for an estimator in closed form.
+    #[inline]
+    pub(crate) fn closed(log_likelihood: f64) -> Self {
+        Report {
+            iterations: 0,
+            log_likelihood,
+            converged: true,
+        }
+    }
+}
+
+/// Compute the standard errors of two parameters given the Hessian of the
+/// log-likelihood.
+pub(crate) fn errors(hessian: [[f64; 2]; 2]) -> Vec<f64> {
+    let [[a, b], [_, d]] = hessian;
+    let determinant = a * d - b * b;
+    vec![(-d / determinant).sqrt(), (-a / determinant).sqrt()]
+}
+
+/// Compute the sample mean.
+#[inline]
+pub(crate) fn mean(data: &[f64]) -> f64 {
+    data.iter().fold(0.0, |sum, &x| sum + x) / data.len() as f64
+}
+
+/// Compute the sample variance with respect to the number of observations.
+#[inline]
+pub(crate) fn variance(data: &[f64], mean: f64) -> f64 {
+    data.iter()
+        .fold(0.0, |sum, &x| sum + math::powi(x - mean, 2))
+        / data.len() as f64
+}
+
+/// Compute a quantile of a sorted sample via linear interpolation between
+/// order statistics.
+///
+/// If the sample is empty, `NaN` is returned.
+pub(crate) fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        return f64::NAN;
+    }
+    let h = p * (n - 1) as f64;
+    let i = libm::floor(h) as usize;
+    if i + 1 >= n {
+        return sorted[n - 1];
+    }
+    sorted[i] + (h - i as f64) * (sorted[i + 1] - sorted[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::errors;
+
+    #[test]
+    fn errors_diagonal() {
+        assert::close(&errors([[-4.0, 0.0], [0.0, -16.0]]), &[0.5, 0.25], 1e-15);
+    }
+}
@@ -0,0 +1,160 @@
+use core::iter::FromIterator;
+#[allow(unused_imports)]
+use special::Primitive;
+
+/// An online estimator of the mean and variance.
+///
+/// The estimator processes one observation at a time using Welford’s
+/// algorithm, which is numerically stable. Estimators that have processed
+/// different parts of a sample can be merged, which makes it suitable for
+/// parallel computations.
+///
+/// ## References
+///
+/// 1. B. P. Welford, “Note on a method for calculating corrected sums of
+///    squares and products,” Technometrics, 1962.
+///
+/// 2. T. F. Chan, G. H. Golub, and R. J. LeVeque, “Updating formulae and a
+///    pairwise algorithm for computing sample variances,” Stanford University,
+///    1979.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Online {
+    count: usize,
+    mean: f64,
+    sum: f64,
+}
+
+impl Online {
+    /// Create an estimator without observations.
+    #[inline]
+    pub fn new() -> Self {
+        Online::default()
+    }
+
+    /// Process an observation.
+    #[inline]
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.sum += delta * (x - self.mean);
+    }
+
+    /// Incorporate the observations processed by another estimator.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let weight = other.count as f64 / count as f64;
+        self.mean += delta * weight;
+        self.sum += other.sum + delta * delta * self.count as f64 * weight;
+        self.count = count;
+    }
+
+    /// Return the number of observations.
+    #[inline(always)]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Compute the sample mean.
+    ///
+    /// If there are no observations, `NaN` is returned.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            return f64::NAN;
+        }
+        self.mean
+    }
+
+    /// Compute the unbiased sample variance.
+    ///
+    /// If there are fewer than two observations, `NaN` is returned.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            return f64::NAN;
+        }
+        self.sum / (self.count - 1) as f64
+    }
+
+    /// Compute the sample standard deviation.
+    #[inline]
+    pub fn deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+impl Extend<f64> for Online {
+    #[inline]
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl FromIterator<f64> for Online {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = f64>>(iter: T) -> Self {
+        let mut online = Online::new();
+        online.extend(iter);
+        online
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::Online;
+
+    #[test]
+    fn push() {
+        let online = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .iter()
+            .cloned()
+            .collect::<Online>();
+        assert_eq!(online.count(), 8);
+        assert::close(online.mean(), 5.0, 1e-15);
+        assert::close(online.variance(), 32.0 / 7.0, 1e-14);
+        assert::close(online.deviation(), (32.0f64 / 7.0).sqrt(), 1e-14);
+    }
+
+    #[test]
+    fn merge() {
+        let data = (0..100)
+            .map(|i| 1e6 + (i * i % 17) as f64)
+            .collect::<Online>();
+        let mut one = (0..30)
+            .map(|i| 1e6 + (i * i % 17) as f64)
+            .collect::<Online>();
+        let other = (30..100)
+            .map(|i| 1e6 + (i * i % 17) as f64)
+            .collect::<Online>();
+        one.merge(&other);
+        assert_eq!(one.count(), 100);
+        assert::close(one.mean(), data.mean(), 1e-9);
+        assert::close(one.variance(), data.variance(), 1e-9);
+
+        let mut empty = Online::new();
+        empty.merge(&data);
+        assert_eq!(empty, data);
+    }
+
+    #[test]
+    fn degenerate() {
+        let mut online = Online::new();
+        assert!(online.mean().is_nan());
+        online.push(1.0);
+        assert_eq!(online.mean(), 1.0);
+        assert!(online.variance().is_nan());
+    }
+}
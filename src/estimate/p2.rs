@@ -0,0 +1,159 @@
+#[allow(unused_imports)]
+use special::Primitive;
+
+/// A streaming estimator of a quantile.
+///
+/// The estimator implements the P² algorithm, which tracks a quantile using
+/// five markers whose heights are adjusted via piecewise-parabolic
+/// interpolation as observations arrive. The memory usage is constant, and no
+/// observations are stored beyond the first five.
+///
+/// ## References
+///
+/// 1. R. Jain and I. Chlamtac, “The P² algorithm for dynamic calculation of
+///    quantiles and histograms without storing observations,” Communications
+///    of the ACM, 1985.
+#[derive(Clone, Copy, Debug)]
+pub struct P2Quantile {
+    p: f64,
+    count: usize,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    /// Create an estimator of the quantile at probability `p`.
+    ///
+    /// It should hold that `p > 0` and `p < 1`.
+    #[inline]
+    pub fn new(p: f64) -> Self {
+        should!(p > 0.0 && p < 1.0);
+        P2Quantile {
+            p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    /// Return the probability.
+    #[inline(always)]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Return the number of observations.
+    #[inline(always)]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Process an observation.
+    pub fn push(&mut self, x: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(|one, other| one.total_cmp(other));
+            }
+            return;
+        }
+        self.count += 1;
+        let k = if x < self.heights[0] {
+            self.heights[0] = x;
+            0
+        } else if x >= self.heights[4] {
+            self.heights[4] = x;
+            3
+        } else {
+            (1..5).find(|&i| x < self.heights[i]).unwrap() - 1
+        };
+        for position in &mut self.positions[(k + 1)..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(&self.increments) {
+            *desired += increment;
+        }
+        for i in 1..4 {
+            let delta = self.desired[i] - self.positions[i];
+            if (delta >= 1.0 && self.positions[i + 1] - self.positions[i] > 1.0)
+                || (delta <= -1.0 && self.positions[i - 1] - self.positions[i] < -1.0)
+            {
+                let sign = delta.signum();
+                let height = self.parabolic(i, sign);
+                self.heights[i] = if self.heights[i - 1] < height && height < self.heights[i + 1] {
+                    height
+                } else {
+                    self.linear(i, sign)
+                };
+                self.positions[i] += sign;
+            }
+        }
+    }
+
+    /// Return the estimate of the quantile.
+    ///
+    /// With fewer than five observations, the quantile of the observations
+    /// themselves is returned. If there are no observations, `NaN` is
+    /// returned.
+    pub fn value(&self) -> f64 {
+        if self.count >= 5 {
+            return self.heights[2];
+        }
+        let mut sorted = self.heights;
+        let sorted = &mut sorted[..self.count];
+        sorted.sort_by(|one, other| one.total_cmp(other));
+        super::quantile(sorted, self.p)
+    }
+
+    fn parabolic(&self, i: usize, sign: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + sign / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + sign) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - sign) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, sign: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        let j = if sign > 0.0 { i + 1 } else { i - 1 };
+        q[i] + sign * (q[j] - q[i]) / (n[j] - n[i])
+    }
+}
+
+impl Extend<f64> for P2Quantile {
+    #[inline]
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::P2Quantile;
+
+    #[test]
+    fn value() {
+        let mut estimator = P2Quantile::new(0.5);
+        assert!(estimator.value().is_nan());
+        estimator.extend([3.0, 1.0, 2.0]);
+        assert_eq!(estimator.value(), 2.0);
+
+        let mut source = source::default(42);
+        let distribution = Gaussian::new(1.0, 2.0);
+        for &p in &[0.1, 0.5, 0.9] {
+            let mut estimator = P2Quantile::new(p);
+            estimator.extend(Independent(&distribution, &mut source).take(100000));
+            assert_eq!(estimator.count(), 100000);
+            assert::close(estimator.value(), distribution.inverse(p), 0.05);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use alloc::vec::Vec;
+
+use distribution::Inverse;
+
+/// Compute the points of a quantile–quantile plot.
+///
+/// Each point pairs a theoretical quantile of `distribution` with the
+/// corresponding order statistic of the sample, and the points are sorted in
+/// increasing order. The `i`th order statistic of `n`, counting from one, is
+/// matched with the plotting position `(i - 0.5) / n` of Hazen.
+pub fn qq<D>(data: &[f64], distribution: &D) -> Vec<(f64, f64)>
+where
+    D: Inverse<Value = f64>,
+{
+    let n = data.len() as f64;
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|one, other| one.total_cmp(other));
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (distribution.inverse((i as f64 + 0.5) / n), x))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::qq;
+
+    #[test]
+    fn uniform() {
+        let points = qq(&[0.9, 0.1, 0.5, 0.3], &Uniform::new(0.0, 2.0));
+        let (theoretical, sample): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+        assert::close(&theoretical, &[0.25, 0.75, 1.25, 1.75], 1e-15);
+        assert_eq!(sample, [0.1, 0.3, 0.5, 0.9]);
+        assert!(qq(&[], &Uniform::new(0.0, 1.0)).is_empty());
+    }
+}
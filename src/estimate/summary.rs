@@ -0,0 +1,164 @@
+use alloc::vec::Vec;
+use core::fmt;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use estimate::{self, Online};
+
+const PROBABILITIES: [f64; 5] = [0.05, 0.25, 0.5, 0.75, 0.95];
+
+/// A descriptive summary of a sample.
+///
+/// The count, extrema, mean, and variance are computed in a single pass over
+/// the data, and the quantiles are computed exactly in a second pass over the
+/// sorted data using linear interpolation between order statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    /// The number of observations.
+    pub count: usize,
+    /// The smallest observation.
+    pub minimum: f64,
+    /// The largest observation.
+    pub maximum: f64,
+    /// The sample mean.
+    pub mean: f64,
+    /// The unbiased sample variance.
+    pub variance: f64,
+    /// The standard error of the mean.
+    pub error: f64,
+    /// The quantiles as pairs of probabilities and values.
+    pub quantiles: Vec<(f64, f64)>,
+}
+
+impl Summary {
+    /// Summarize a sample computing the quantiles at given probabilities.
+    ///
+    /// If the sample is empty, all the statistics but the count are `NaN`.
+    pub fn with_probabilities<T>(data: T, probabilities: &[f64]) -> Self
+    where
+        T: IntoIterator<Item = f64>,
+    {
+        let mut online = Online::new();
+        let mut minimum = f64::INFINITY;
+        let mut maximum = f64::NEG_INFINITY;
+        let mut sorted = Vec::new();
+        for x in data {
+            online.push(x);
+            minimum = minimum.min(x);
+            maximum = maximum.max(x);
+            sorted.push(x);
+        }
+        sorted.sort_by(|one, other| one.total_cmp(other));
+        let quantiles = probabilities
+            .iter()
+            .map(|&p| {
+                should!((0.0..=1.0).contains(&p));
+                (p, estimate::quantile(&sorted, p))
+            })
+            .collect();
+        let count = online.count();
+        if count == 0 {
+            minimum = f64::NAN;
+            maximum = f64::NAN;
+        }
+        Summary {
+            count,
+            minimum,
+            maximum,
+            mean: online.mean(),
+            variance: online.variance(),
+            error: (online.variance() / count as f64).sqrt(),
+            quantiles,
+        }
+    }
+
+    /// Compute the sample standard deviation.
+    #[inline]
+    pub fn deviation(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// Return the quantile at a probability if it has been computed.
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        self.quantiles
+            .iter()
+            .find(|&&(probability, _)| probability == p)
+            .map(|&(_, value)| value)
+    }
+}
+
+impl<T> From<T> for Summary
+where
+    T: IntoIterator<Item = f64>,
+{
+    /// Summarize a sample computing the quantiles at 5%, 25%, 50%, 75%, and
+    /// 95%.
+    #[inline]
+    fn from(data: T) -> Self {
+        Summary::with_probabilities(data, &PROBABILITIES)
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(formatter, "count     {}", self.count)?;
+        writeln!(formatter, "mean      {}", self.mean)?;
+        writeln!(formatter, "deviation {}", self.deviation())?;
+        writeln!(formatter, "error     {}", self.error)?;
+        writeln!(formatter, "minimum   {}", self.minimum)?;
+        for &(p, value) in &self.quantiles {
+            writeln!(
+                formatter,
+                "{:<9} {}",
+                alloc::format!("{}%", p * 100.0),
+                value
+            )?;
+        }
+        write!(formatter, "maximum   {}", self.maximum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::{vec, vec::Vec};
+    use assert;
+
+    use super::Summary;
+
+    #[test]
+    fn from() {
+        let summary = Summary::from((1..=11).map(|i| i as f64));
+        assert_eq!(summary.count, 11);
+        assert_eq!((summary.minimum, summary.maximum), (1.0, 11.0));
+        assert::close(summary.mean, 6.0, 1e-15);
+        assert::close(summary.variance, 11.0, 1e-14);
+        assert::close(summary.error, 1.0, 1e-15);
+        assert::close(
+            &summary
+                .quantiles
+                .iter()
+                .map(|&(_, x)| x)
+                .collect::<Vec<_>>(),
+            &[1.5, 3.5, 6.0, 8.5, 10.5],
+            1e-14,
+        );
+        assert_eq!(summary.quantile(0.5), Some(6.0));
+        assert_eq!(summary.quantile(0.1), None);
+    }
+
+    #[test]
+    fn display() {
+        let summary = Summary::with_probabilities(vec![4.0, 0.0, 4.0, 4.0], &[0.5]);
+        assert_eq!(
+            summary.to_string(),
+            "count     4\n\
+             mean      3\n\
+             deviation 2\n\
+             error     1\n\
+             minimum   0\n\
+             50%       4\n\
+             maximum   4",
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Gaussian, Inverse};
+use error::{self, Error};
+
+/// The Kaplan–Meier estimator of a survival function.
+///
+/// The estimator is built from right-censored observations given as pairs of
+/// times and event indicators, where `false` means that the observation was
+/// censored at the corresponding time. The standard errors are given by
+/// Greenwood’s formula.
+///
+/// ## References
+///
+/// 1. E. L. Kaplan and P. Meier, “Nonparametric estimation from incomplete
+///    observations,” Journal of the American Statistical Association, 1958.
+#[derive(Clone, Debug)]
+pub struct KaplanMeier {
+    steps: Vec<SurvivalStep>,
+}
+
+/// A step of the Kaplan–Meier estimator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurvivalStep {
+    /// The time of the events.
+    pub time: f64,
+    /// The number of observations at risk just before the time.
+    pub at_risk: usize,
+    /// The number of events at the time.
+    pub events: usize,
+    /// The survival probability right after the time.
+    pub survival: f64,
+    /// The standard error of the survival probability.
+    pub error: f64,
+}
+
+impl KaplanMeier {
+    /// Create an estimator from pairs of times and event indicators.
+    ///
+    /// The times should be finite and nonnegative.
+    pub fn new(data: &[(f64, bool)]) -> Result<Self, Error> {
+        for &(time, _) in data {
+            error::finite("time", time)?;
+            error::require("time >= 0", time >= 0.0)?;
+        }
+        let mut data = data.to_vec();
+        data.sort_by(|one, other| one.0.total_cmp(&other.0));
+        let mut steps = Vec::new();
+        let (mut survival, mut sum) = (1.0, 0.0);
+        let mut at_risk = data.len();
+        let mut i = 0;
+        while i < data.len() {
+            let time = data[i].0;
+            let (mut events, mut count) = (0, 0);
+            while i < data.len() && data[i].0 == time {
+                events += data[i].1 as usize;
+                count += 1;
+                i += 1;
+            }
+            if events > 0 {
+                survival *= 1.0 - events as f64 / at_risk as f64;
+                if at_risk > events {
+                    sum += events as f64 / (at_risk * (at_risk - events)) as f64;
+                }
+                steps.push(SurvivalStep {
+                    time,
+                    at_risk,
+                    events,
+                    survival,
+                    error: survival * sum.sqrt(),
+                });
+            }
+            at_risk -= count;
+        }
+        Ok(KaplanMeier { steps })
+    }
+
+    /// Return the steps, which are ordered by time.
+    #[inline(always)]
+    pub fn steps(&self) -> &[SurvivalStep] {
+        &self.steps
+    }
+
+    /// Compute the survival probability at a time.
+    #[inline]
+    pub fn survival(&self, time: f64) -> f64 {
+        self.step(time).map_or(1.0, |step| step.survival)
+    }
+
+    /// Compute the standard error of the survival probability at a time.
+    #[inline]
+    pub fn error(&self, time: f64) -> f64 {
+        self.step(time).map_or(0.0, |step| step.error)
+    }
+
+    /// Compute a pointwise confidence interval for the survival probability at
+    /// a time with confidence level `level`.
+    ///
+    /// The interval is based on the normal approximation and is clipped to
+    /// `[0, 1]`. It should hold that `level > 0` and `level < 1`.
+    pub fn interval(&self, time: f64, level: f64) -> (f64, f64) {
+        should!(level > 0.0 && level < 1.0);
+        let (survival, error) = (self.survival(time), self.error(time));
+        let width = Gaussian::default().inverse(0.5 + 0.5 * level) * error;
+        ((survival - width).max(0.0), (survival + width).min(1.0))
+    }
+
+    /// Compute the median survival time.
+    ///
+    /// If the survival probability does not fall to one half, `None` is
+    /// returned.
+    pub fn median(&self) -> Option<f64> {
+        self.steps
+            .iter()
+            .find(|step| step.survival <= 0.5)
+            .map(|step| step.time)
+    }
+
+    fn step(&self, time: f64) -> Option<&SurvivalStep> {
+        let count = self.steps.partition_point(|step| step.time <= time);
+        if count == 0 {
+            None
+        } else {
+            Some(&self.steps[count - 1])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::KaplanMeier;
+
+    #[test]
+    fn new() {
+        let data = [
+            (3.0, true),
+            (1.0, true),
+            (2.0, false),
+            (5.0, true),
+            (3.0, true),
+            (4.0, false),
+        ];
+        let estimator = KaplanMeier::new(&data).unwrap();
+        let steps = estimator.steps();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(
+            (steps[1].time, steps[1].at_risk, steps[1].events),
+            (3.0, 4, 2)
+        );
+        assert::close(estimator.survival(0.5), 1.0, 0.0);
+        assert::close(estimator.survival(2.0), 5.0 / 6.0, 1e-15);
+        assert::close(estimator.survival(3.5), 5.0 / 12.0, 1e-15);
+        assert::close(estimator.survival(5.0), 0.0, 0.0);
+        assert::close(
+            estimator.error(3.0),
+            5.0 / 12.0 * (17.0f64 / 60.0).sqrt(),
+            1e-15,
+        );
+        assert_eq!(estimator.median(), Some(3.0));
+    }
+
+    #[test]
+    fn interval() {
+        let data = [(1.0, true), (2.0, true), (3.0, false), (4.0, true)];
+        let estimator = KaplanMeier::new(&data).unwrap();
+        let (lower, upper) = estimator.interval(1.0, 0.95);
+        let width = 1.959963984540054 * 0.75 * (1.0f64 / 12.0).sqrt();
+        assert::close(lower, 0.75 - width, 1e-12);
+        assert::close(upper, 1.0, 0.0);
+        assert!(KaplanMeier::new(&[(-1.0, true)]).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use math;
+
+/// The result of the Hill estimation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hill {
+    /// The estimated tail index.
+    pub index: f64,
+    /// The standard error of the tail index.
+    pub error: f64,
+    /// The threshold above which the tail is modeled, which is the largest
+    /// value not used in the estimation.
+    pub threshold: f64,
+    /// The fraction of the sample above the threshold.
+    pub fraction: f64,
+}
+
+/// Estimate the tail index of a heavy-tailed sample using the `k` largest
+/// values.
+///
+/// The estimate assumes that the tail above the threshold behaves as the one
+/// of a Pareto distribution. If `k` is zero, `k` is not smaller than the size
+/// of the sample, or the threshold is not positive, `None` is returned.
+///
+/// ## References
+///
+/// 1. B. M. Hill, “A simple general approach to inference about the tail of a
+///    distribution,” The Annals of Statistics, 1975.
+pub fn hill(data: &[f64], k: usize) -> Option<Hill> {
+    let n = data.len();
+    if k == 0 || k >= n {
+        return None;
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|one, other| other.total_cmp(one));
+    let threshold = sorted[k];
+    if threshold.is_nan() || threshold <= 0.0 {
+        return None;
+    }
+    let logarithms = sorted[..k].iter().map(|&x| math::ln(x)).collect::<Vec<_>>();
+    let mean = super::mean(&logarithms) - math::ln(threshold);
+    if mean == 0.0 {
+        return None;
+    }
+    let index = 1.0 / mean;
+    Some(Hill {
+        index,
+        error: index / (k as f64).sqrt(),
+        threshold,
+        fraction: k as f64 / n as f64,
+    })
+}
+
+impl Hill {
+    /// Compute the probability of exceeding a value above the threshold
+    /// according to the fitted tail.
+    #[inline]
+    pub fn survival(&self, x: f64) -> f64 {
+        should!(x >= self.threshold);
+        self.fraction * math::powf(x / self.threshold, -self.index)
+    }
+
+    /// Compute the value exceeded with probability `p` according to the
+    /// fitted tail.
+    ///
+    /// It should hold that `p > 0` and `p <= fraction`.
+    #[inline]
+    pub fn quantile(&self, p: f64) -> f64 {
+        should!(p > 0.0 && p <= self.fraction);
+        self.threshold * math::powf(p / self.fraction, -1.0 / self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::hill;
+
+    #[test]
+    fn estimate() {
+        let alpha = 2.5;
+        let mut source = source::default(42);
+        let data = Independent(&Uniform::new(0.0, 1.0), &mut source)
+            .take(100000)
+            .map(|u| (1.0 - u).powf(-1.0 / alpha))
+            .collect::<Vec<_>>();
+        let estimate = hill(&data, 10000).unwrap();
+        assert::close(estimate.index, alpha, 3.0 * estimate.error);
+        assert::close(estimate.error, alpha / 100.0, 0.005);
+        assert::close(estimate.survival(estimate.threshold), 0.1, 1e-15);
+        assert::close(estimate.quantile(0.01), 100f64.powf(1.0 / alpha), 0.2);
+        assert!(hill(&data, 0).is_none());
+        assert!(hill(&[1.0, 2.0], 2).is_none());
+    }
+}
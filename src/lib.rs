@@ -17,11 +17,22 @@
 extern crate assert;
 
 extern crate alloc;
+#[cfg(feature = "approx")]
+extern crate approx;
+#[cfg(feature = "getrandom")]
+extern crate getrandom;
+extern crate libm;
+#[cfg(feature = "rand")]
+extern crate rand_core;
 extern crate random;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate special;
+#[cfg(feature = "std")]
+extern crate std;
 
 macro_rules! nonnan(
-    ($argument:ident) => (if $argument.is_nan() { return ::core::f64::NAN; });
+    ($argument:ident) => (if $argument.is_nan() { return f64::NAN; });
 );
 
 macro_rules! should(
@@ -29,7 +40,20 @@ macro_rules! should(
     ($requirement:expr, $code:expr) => (debug_assert!($code, stringify!($requirement)));
 );
 
+pub mod bayes;
 pub mod distribution;
+pub mod divergence;
+pub mod estimate;
 pub mod prelude;
+pub mod process;
 pub mod sampler;
 pub mod source;
+pub mod test;
+pub mod variable;
+
+mod error;
+mod linear;
+mod math;
+mod quadrature;
+
+pub use error::Error;
@@ -0,0 +1,39 @@
+//! Linear algebra.
+
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+/// Solve a system of linear equations given as an augmented matrix stored by
+/// rows using Gaussian elimination with partial pivoting.
+pub fn solve(matrix: &mut [f64], n: usize) -> Vec<f64> {
+    let width = n + 1;
+    for k in 0..n {
+        let mut pivot = k;
+        for i in (k + 1)..n {
+            if matrix[i * width + k].abs() > matrix[pivot * width + k].abs() {
+                pivot = i;
+            }
+        }
+        if pivot != k {
+            for j in 0..width {
+                matrix.swap(k * width + j, pivot * width + j);
+            }
+        }
+        for i in (k + 1)..n {
+            let factor = matrix[i * width + k] / matrix[k * width + k];
+            for j in k..width {
+                matrix[i * width + j] -= factor * matrix[k * width + j];
+            }
+        }
+    }
+    let mut solution = vec![0.0; n];
+    for k in (0..n).rev() {
+        let mut sum = matrix[k * width + n];
+        for j in (k + 1)..n {
+            sum -= matrix[k * width + j] * solution[j];
+        }
+        solution[k] = sum / matrix[k * width + k];
+    }
+    solution
+}
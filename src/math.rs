@@ -0,0 +1,39 @@
+//! Elementary functions.
+//!
+//! With the `strict` feature, the functions are evaluated by `libm`, which
+//! gives identical results on all targets regardless of whether the standard
+//! library is linked. Otherwise, the methods of `f64` are used, which resolve
+//! to the standard library when it is linked and to `libm` via
+//! `special::Primitive` when it is not. Square roots, rounding, and absolute
+//! values are exact in both cases and are not routed here.
+
+#[allow(unused_imports)]
+use special::Primitive;
+
+macro_rules! implement(
+    ($(($name:ident, $function:path $(, $argument:ident: $kind:ty as $inner:ty)*),)*) => ($(
+        #[cfg(feature = "strict")]
+        #[inline(always)]
+        pub fn $name(x: f64 $(, $argument: $kind)*) -> f64 {
+            $function(x $(, $argument as $inner)*)
+        }
+
+        #[cfg(not(feature = "strict"))]
+        #[inline(always)]
+        pub fn $name(x: f64 $(, $argument: $kind)*) -> f64 {
+            x.$name($($argument),*)
+        }
+    )*);
+);
+
+implement!(
+    (atan, libm::atan),
+    (exp, libm::exp),
+    (exp_m1, libm::expm1),
+    (ln, libm::log),
+    (ln_1p, libm::log1p),
+    (powf, libm::pow, y: f64 as f64),
+    (powi, libm::pow, n: i32 as f64),
+    (sin, libm::sin),
+    (tan, libm::tan),
+);
@@ -5,14 +5,21 @@ pub use distribution::Distribution;
 pub use distribution::Continuous;
 pub use distribution::Discrete;
 
+pub use distribution::CharacteristicFunction;
 pub use distribution::Entropy;
 pub use distribution::Inverse;
+pub use distribution::InverseBracket;
 pub use distribution::Kurtosis;
 pub use distribution::Mean;
 pub use distribution::Median;
+pub use distribution::Mgf;
 pub use distribution::Modes;
+pub use distribution::ModesIter;
+pub use distribution::Pgf;
 pub use distribution::Sample;
 pub use distribution::Skewness;
+pub use distribution::Support;
+pub use distribution::Survival;
 pub use distribution::Variance;
 
 pub use distribution::Bernoulli;
@@ -26,10 +33,27 @@ pub use distribution::Gaussian;
 pub use distribution::Laplace;
 pub use distribution::Logistic;
 pub use distribution::Lognormal;
+pub use distribution::Metalog;
+pub use distribution::NegativeBinomial;
 pub use distribution::Pert;
+pub use distribution::Poisson;
 pub use distribution::Triangular;
 pub use distribution::Uniform;
 
+pub use sampler::Antithetic;
+pub use sampler::Gibbs;
+pub use sampler::Hamiltonian;
+pub use sampler::Importance;
 pub use sampler::Independent;
+pub use sampler::OwnedIndependent;
+pub use sampler::ParticleFilter;
+pub use sampler::PoissonProcess;
+pub use sampler::Stratified;
+pub use sampler::Tabulated;
+pub use sampler::WeightedReservoir;
+pub use sampler::WithLogDensity;
 
 pub use source;
+
+pub use variable::Scaled;
+pub use variable::Shifted;
@@ -0,0 +1,192 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Gaussian, Sample};
+use math;
+use source::Source;
+
+/// A first-order autoregressive process.
+///
+/// The process satisfies `X[t] = phi X[t - 1] + e[t]`, where `e[t]` are
+/// independent Gaussian innovations with mean zero and standard deviation
+/// `sigma`. The process is stationary if `|phi| < 1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ar1 {
+    phi: f64,
+    sigma: f64,
+}
+
+/// An autoregressive moving-average process.
+///
+/// The process satisfies `X[t] = phi[0] X[t - 1] + … + phi[p - 1] X[t - p] +
+/// e[t] + theta[0] e[t - 1] + … + theta[q - 1] e[t - q]`, where `e[t]` are
+/// independent innovations drawn from a given distribution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arma {
+    phi: Vec<f64>,
+    theta: Vec<f64>,
+}
+
+impl Ar1 {
+    /// Create a process with coefficient `phi` and innovation standard
+    /// deviation `sigma`.
+    ///
+    /// It should hold that `|phi| < 1` and `sigma > 0`.
+    #[inline]
+    pub const fn new(phi: f64, sigma: f64) -> Self {
+        should!(-1.0 < phi && phi < 1.0 && sigma > 0.0);
+        Ar1 { phi, sigma }
+    }
+
+    /// Return the coefficient.
+    #[inline(always)]
+    pub const fn phi(&self) -> f64 {
+        self.phi
+    }
+
+    /// Return the innovation standard deviation.
+    #[inline(always)]
+    pub const fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Compute the stationary variance.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.sigma * self.sigma / (1.0 - self.phi * self.phi)
+    }
+
+    /// Compute the autocorrelation at lag `lag`.
+    #[inline]
+    pub fn autocorrelation(&self, lag: usize) -> f64 {
+        math::powf(self.phi, lag as f64)
+    }
+
+    /// Draw a path with `steps` steps.
+    ///
+    /// The initial value is drawn from the stationary distribution, and hence
+    /// the whole path is stationary.
+    pub fn path<S>(&self, steps: usize, source: &mut S) -> Vec<f64>
+    where
+        S: Source,
+    {
+        let mut path = vec![0.0; steps + 1];
+        Gaussian::new(0.0, self.sigma).sample_into(&mut path, source);
+        path[0] *= self.variance().sqrt() / self.sigma;
+        for i in 1..(steps + 1) {
+            path[i] += self.phi * path[i - 1];
+        }
+        path
+    }
+}
+
+impl From<Ar1> for Arma {
+    #[inline]
+    fn from(process: Ar1) -> Self {
+        Arma::new(vec![process.phi], vec![])
+    }
+}
+
+impl Arma {
+    /// Create a process with autoregressive coefficients `phi` and
+    /// moving-average coefficients `theta`.
+    #[inline]
+    pub fn new(phi: Vec<f64>, theta: Vec<f64>) -> Self {
+        Arma { phi, theta }
+    }
+
+    /// Return the autoregressive coefficients.
+    #[inline(always)]
+    pub fn phi(&self) -> &[f64] {
+        &self.phi
+    }
+
+    /// Return the moving-average coefficients.
+    #[inline(always)]
+    pub fn theta(&self) -> &[f64] {
+        &self.theta
+    }
+
+    /// Draw a path with `steps` steps and innovations from `noise`.
+    ///
+    /// The values and innovations before the start are taken to be zero, and
+    /// so is the initial value. If the process is stationary, the path
+    /// approaches stationarity as it goes, which is commonly addressed by
+    /// discarding a number of initial values.
+    pub fn path<D, S>(&self, noise: &D, steps: usize, source: &mut S) -> Vec<f64>
+    where
+        D: Sample<Value = f64>,
+        S: Source,
+    {
+        let mut path = vec![0.0; steps + 1];
+        let mut innovations = vec![0.0; steps + 1];
+        noise.sample_into(&mut innovations[1..], source);
+        for t in 1..(steps + 1) {
+            let mut value = innovations[t];
+            for (i, phi) in self.phi.iter().enumerate().take(t) {
+                value += phi * path[t - i - 1];
+            }
+            for (j, theta) in self.theta.iter().enumerate().take(t) {
+                value += theta * innovations[t - j - 1];
+            }
+            path[t] = value;
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert;
+    use prelude::*;
+
+    use super::{Ar1, Arma};
+
+    fn autocorrelation(data: &[f64], lag: usize) -> f64 {
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().fold(0.0, |sum, x| sum + (x - mean).powi(2));
+        let covariance = data
+            .iter()
+            .zip(data[lag..].iter())
+            .fold(0.0, |sum, (x, y)| sum + (x - mean) * (y - mean));
+        covariance / variance
+    }
+
+    #[test]
+    fn ar1() {
+        let mut source = source::default(42);
+        let process = Ar1::new(0.7, 2.0);
+        assert::close(process.variance(), 4.0 / 0.51, 1e-12);
+        assert_eq!(process.autocorrelation(1 << 31), 0.0);
+        assert::close(process.autocorrelation(2), 0.49, 1e-12);
+        let path = process.path(100000, &mut source);
+        assert_eq!(path.len(), 100001);
+        let variance = path.iter().fold(0.0, |sum, x| sum + x * x) / 100001.0;
+        assert::close(variance / process.variance(), 1.0, 0.05);
+        for lag in 1..4 {
+            assert::close(
+                autocorrelation(&path, lag),
+                process.autocorrelation(lag),
+                0.01,
+            );
+        }
+    }
+
+    #[test]
+    fn arma() {
+        let mut source = source::default(42);
+        let noise = Uniform::new(-1.0, 1.0);
+        let process = Arma::new(vec![], vec![0.5]);
+        let path = process.path(&noise, 100000, &mut source);
+        assert_eq!(path[0], 0.0);
+        assert::close(autocorrelation(&path, 1), 0.4, 0.01);
+        assert::close(autocorrelation(&path, 2), 0.0, 0.01);
+
+        let process = Arma::from(Ar1::new(-0.5, 1.0));
+        let path = process.path(&Gaussian::new(0.0, 1.0), 100000, &mut source);
+        assert::close(autocorrelation(&path, 1), -0.5, 0.01);
+    }
+}
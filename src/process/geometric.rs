@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::Lognormal;
+use math;
+use process::Wiener;
+use source::Source;
+
+/// A geometric Brownian motion.
+///
+/// The process starts at `s0` and satisfies `dS = mu S dt + sigma S dW`, where
+/// `W` is the standard Wiener process. Its logarithm is a Wiener process with
+/// drift `mu - sigma^2 / 2` and volatility `sigma` shifted by `ln(s0)`, which
+/// makes the increments exactly lognormal and the paths free of
+/// discretization error.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeometricBrownian {
+    mu: f64,
+    sigma: f64,
+    s0: f64,
+}
+
+impl GeometricBrownian {
+    /// Create a process with drift `mu`, volatility `sigma`, and initial value
+    /// `s0`.
+    ///
+    /// It should hold that `sigma > 0` and `s0 > 0`.
+    #[inline]
+    pub const fn new(mu: f64, sigma: f64, s0: f64) -> Self {
+        should!(sigma > 0.0 && s0 > 0.0);
+        GeometricBrownian { mu, sigma, s0 }
+    }
+
+    /// Return the drift.
+    #[inline(always)]
+    pub const fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Return the volatility.
+    #[inline(always)]
+    pub const fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Return the initial value.
+    #[inline(always)]
+    pub const fn s0(&self) -> f64 {
+        self.s0
+    }
+
+    /// Return the distribution of the value at time `t`.
+    ///
+    /// It should hold that `t > 0`.
+    pub fn terminal(&self, t: f64) -> Lognormal {
+        should!(t > 0.0);
+        Lognormal::new(
+            math::ln(self.s0) + (self.mu - 0.5 * self.sigma * self.sigma) * t,
+            self.sigma * t.sqrt(),
+        )
+    }
+
+    /// Draw a path with `steps` steps of size `dt`.
+    ///
+    /// It should hold that `dt > 0`.
+    pub fn path<S>(&self, dt: f64, steps: usize, source: &mut S) -> Vec<f64>
+    where
+        S: Source,
+    {
+        let mut path = self.logarithm().path(dt, steps, source);
+        for value in path.iter_mut() {
+            *value = self.s0 * math::exp(*value);
+        }
+        path
+    }
+
+    /// Draw `count` independent paths with `steps` steps of size `dt`.
+    ///
+    /// It should hold that `dt > 0`.
+    pub fn paths<S>(&self, dt: f64, steps: usize, count: usize, source: &mut S) -> Vec<Vec<f64>>
+    where
+        S: Source,
+    {
+        (0..count).map(|_| self.path(dt, steps, source)).collect()
+    }
+
+    #[inline]
+    fn logarithm(&self) -> Wiener {
+        Wiener::new(self.mu - 0.5 * self.sigma * self.sigma, self.sigma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::GeometricBrownian;
+
+    #[test]
+    fn paths() {
+        let mut source = source::default(42);
+        let process = GeometricBrownian::new(0.05, 0.2, 100.0);
+        let paths = process.paths(1.0 / 12.0, 12, 50000, &mut source);
+        assert!(paths
+            .iter()
+            .all(|path| path.len() == 13 && path[0] == 100.0));
+        assert!(paths.iter().all(|path| path.iter().all(|&x| x > 0.0)));
+        let n = paths.len() as f64;
+        let mean = paths.iter().fold(0.0, |sum, path| sum + path[12]) / n;
+        assert::close(mean / (100.0 * 0.05f64.exp()), 1.0, 0.005);
+        let d = process.terminal(1.0);
+        let count = paths.iter().filter(|path| path[12] <= 100.0).count();
+        assert::close(count as f64 / n, d.distribution(100.0), 0.01);
+    }
+
+    #[test]
+    fn terminal() {
+        let process = GeometricBrownian::new(0.05, 0.2, 100.0);
+        let d = process.terminal(2.0);
+        assert::close(d.mean(), 100.0 * 0.1f64.exp(), 1e-10);
+        assert::close(
+            d.variance(),
+            1e4 * 0.2f64.exp() * (0.08f64.exp() - 1.0),
+            1e-8,
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::{vec, vec::Vec};
+
+use distribution::{Categorical, Sample};
+use linear;
+use source::Source;
+
+/// A discrete-time Markov chain with a finite number of states.
+///
+/// The chain is specified by its transition matrix, whose `i`th row is the
+/// distribution of the next state given that the current state is `i`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkovChain {
+    rows: Vec<Categorical>,
+}
+
+impl MarkovChain {
+    /// Create a chain with the transition matrix given by rows.
+    ///
+    /// It should hold that each row has as many categories as there are rows.
+    pub fn new(rows: Vec<Categorical>) -> Self {
+        should!(!rows.is_empty() && rows.iter().all(|row| row.k() == rows.len()));
+        MarkovChain { rows }
+    }
+
+    /// Return the number of states.
+    #[inline(always)]
+    pub fn states(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Return the distribution of the next state given the current state
+    /// `state`.
+    #[inline(always)]
+    pub fn row(&self, state: usize) -> &Categorical {
+        &self.rows[state]
+    }
+
+    /// Draw a path with `steps` steps starting from the state `initial`.
+    ///
+    /// The path consists of `steps + 1` states, the first of which is the
+    /// initial one.
+    pub fn path<S>(&self, initial: usize, steps: usize, source: &mut S) -> Vec<usize>
+    where
+        S: Source,
+    {
+        should!(initial < self.states());
+        let mut path = Vec::with_capacity(steps + 1);
+        let mut state = initial;
+        path.push(state);
+        for _ in 0..steps {
+            state = self.rows[state].sample(source);
+            path.push(state);
+        }
+        path
+    }
+
+    /// Compute the stationary distribution.
+    ///
+    /// The distribution is obtained by solving the balance equations, which
+    /// have a unique solution if the chain is irreducible.
+    pub fn stationary(&self) -> Vec<f64> {
+        let n = self.states();
+        let width = n + 1;
+        let mut matrix = vec![0.0; n * width];
+        for i in 0..(n - 1) {
+            for j in 0..n {
+                matrix[i * width + j] = self.rows[j].p()[i];
+            }
+            matrix[i * width + i] -= 1.0;
+        }
+        for j in 0..width {
+            matrix[(n - 1) * width + j] = 1.0;
+        }
+        linear::solve(&mut matrix, n)
+    }
+
+    /// Compute the transition probabilities over `steps` steps.
+    ///
+    /// The result is the corresponding power of the transition matrix stored
+    /// by rows, so that the element at `i * n + j`, where `n` is the number of
+    /// states, is the probability of moving from state `i` to state `j`.
+    pub fn transition(&self, steps: usize) -> Vec<f64> {
+        let n = self.states();
+        let mut result = vec![0.0; n * n];
+        for i in 0..n {
+            result[i * n + i] = 1.0;
+        }
+        let mut power = self
+            .rows
+            .iter()
+            .flat_map(|row| row.p().iter().cloned())
+            .collect::<Vec<_>>();
+        let mut steps = steps;
+        while steps > 0 {
+            if steps & 1 == 1 {
+                result = multiply(&result, &power, n);
+            }
+            steps >>= 1;
+            if steps > 0 {
+                power = multiply(&power, &power, n);
+            }
+        }
+        result
+    }
+}
+
+fn multiply(a: &[f64], b: &[f64], n: usize) -> Vec<f64> {
+    let mut c = vec![0.0; n * n];
+    for i in 0..n {
+        for k in 0..n {
+            let a = a[i * n + k];
+            for j in 0..n {
+                c[i * n + j] += a * b[k * n + j];
+            }
+        }
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert;
+    use prelude::*;
+
+    use super::MarkovChain;
+
+    fn chain() -> MarkovChain {
+        MarkovChain::new(vec![
+            Categorical::new(&[0.5, 0.5, 0.0]),
+            Categorical::new(&[0.25, 0.5, 0.25]),
+            Categorical::new(&[0.0, 0.5, 0.5]),
+        ])
+    }
+
+    #[test]
+    fn path() {
+        let mut source = source::default(42);
+        let chain = chain();
+        let path = chain.path(0, 100000, &mut source);
+        assert_eq!(path.len(), 100001);
+        assert_eq!(path[0], 0);
+        assert!(path
+            .windows(2)
+            .all(|pair| chain.row(pair[0]).p()[pair[1]] > 0.0));
+        let mut counts = [0.0; 3];
+        for &state in path.iter() {
+            counts[state] += 1.0 / 100001.0;
+        }
+        assert::close(&counts, &chain.stationary(), 0.01);
+    }
+
+    #[test]
+    fn stationary() {
+        assert::close(&chain().stationary(), &[0.25, 0.5, 0.25], 1e-12);
+        let chain = MarkovChain::new(vec![
+            Categorical::new(&[0.0, 1.0]),
+            Categorical::new(&[1.0, 0.0]),
+        ]);
+        assert::close(&chain.stationary(), &[0.5, 0.5], 1e-12);
+    }
+
+    #[test]
+    fn transition() {
+        let chain = chain();
+        assert_eq!(
+            chain.transition(0),
+            [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            chain.transition(1),
+            [0.5, 0.5, 0.0, 0.25, 0.5, 0.25, 0.0, 0.5, 0.5]
+        );
+        assert::close(
+            &chain.transition(2),
+            &[0.375, 0.5, 0.125, 0.25, 0.5, 0.25, 0.125, 0.5, 0.375],
+            1e-15,
+        );
+        let limit = chain.transition(100);
+        for i in 0..3 {
+            assert::close(&limit[(i * 3)..(i * 3 + 3)], &[0.25, 0.5, 0.25], 1e-12);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Stochastic processes.
+//!
+//! The continuous-time processes below generate sample paths on a uniform time
+//! grid. A path with `steps` steps of size `dt` consists of `steps + 1` values
+//! at times `0, dt, 2 dt, …, steps dt`, the first of which is the initial
+//! value. Discrete-time processes generate paths in the same way with no step
+//! size involved.
+//!
+//! ## Example
+//!
+//! ```
+//! use probability::prelude::*;
+//! use probability::process::Wiener;
+//!
+//! let mut source = source::default(42);
+//! let paths = Wiener::default().paths(0.01, 100, 10, &mut source);
+//! assert_eq!(paths.len(), 10);
+//! assert_eq!(paths[0].len(), 101);
+//! ```
+
+mod autoregressive;
+mod geometric;
+mod markov;
+mod wiener;
+
+pub use self::autoregressive::{Ar1, Arma};
+pub use self::geometric::GeometricBrownian;
+pub use self::markov::MarkovChain;
+pub use self::wiener::Wiener;
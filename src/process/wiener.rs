@@ -0,0 +1,147 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Gaussian, Sample};
+use source::Source;
+
+/// A Wiener process.
+///
+/// The process starts at zero and has independent Gaussian increments with
+/// mean `mu dt` and variance `sigma^2 dt` over a time interval of length `dt`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wiener {
+    mu: f64,
+    sigma: f64,
+}
+
+impl Wiener {
+    /// Create a process with drift `mu` and volatility `sigma`.
+    ///
+    /// It should hold that `sigma > 0`.
+    #[inline]
+    pub const fn new(mu: f64, sigma: f64) -> Self {
+        should!(sigma > 0.0);
+        Wiener { mu, sigma }
+    }
+
+    /// Return the drift.
+    #[inline(always)]
+    pub const fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Return the volatility.
+    #[inline(always)]
+    pub const fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Draw a path with `steps` steps of size `dt`.
+    ///
+    /// It should hold that `dt > 0`.
+    pub fn path<S>(&self, dt: f64, steps: usize, source: &mut S) -> Vec<f64>
+    where
+        S: Source,
+    {
+        should!(dt > 0.0);
+        let mut path = vec![0.0; steps + 1];
+        Gaussian::new(self.mu * dt, self.sigma * dt.sqrt()).sample_into(&mut path[1..], source);
+        for i in 1..(steps + 1) {
+            path[i] += path[i - 1];
+        }
+        path
+    }
+
+    /// Draw `count` independent paths with `steps` steps of size `dt`.
+    ///
+    /// It should hold that `dt > 0`.
+    pub fn paths<S>(&self, dt: f64, steps: usize, count: usize, source: &mut S) -> Vec<Vec<f64>>
+    where
+        S: Source,
+    {
+        (0..count).map(|_| self.path(dt, steps, source)).collect()
+    }
+
+    /// Draw a path with `steps` steps of size `dt` conditioned on ending at
+    /// `end`.
+    ///
+    /// The path is a Brownian bridge, which is constructed by drawing each
+    /// value from its distribution conditional on the previous value and the
+    /// end. The drift does not affect the result. It should hold that `dt >
+    /// 0` and `steps > 0`.
+    pub fn bridge<S>(&self, end: f64, dt: f64, steps: usize, source: &mut S) -> Vec<f64>
+    where
+        S: Source,
+    {
+        should!(dt > 0.0 && steps > 0);
+        let gaussian = Gaussian::default();
+        let mut path = Vec::with_capacity(steps + 1);
+        path.push(0.0);
+        let mut value = 0.0;
+        for i in 1..steps {
+            let remaining = (steps - i + 1) as f64;
+            let mean = value + (end - value) / remaining;
+            let deviation = self.sigma * (dt * (remaining - 1.0) / remaining).sqrt();
+            value = mean + deviation * gaussian.sample(source);
+            path.push(value);
+        }
+        path.push(end);
+        path
+    }
+}
+
+impl Default for Wiener {
+    #[inline]
+    fn default() -> Self {
+        Wiener::new(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::Wiener;
+
+    fn moments(paths: &[Vec<f64>], i: usize) -> (f64, f64) {
+        let n = paths.len() as f64;
+        let mean = paths.iter().fold(0.0, |sum, path| sum + path[i]) / n;
+        let variance = paths
+            .iter()
+            .fold(0.0, |sum, path| sum + (path[i] - mean).powi(2))
+            / n;
+        (mean, variance)
+    }
+
+    #[test]
+    fn bridge() {
+        let mut source = source::default(42);
+        let process = Wiener::new(3.0, 2.0);
+        let paths = (0..20000)
+            .map(|_| process.bridge(1.0, 0.1, 10, &mut source))
+            .collect::<Vec<_>>();
+        assert!(paths.iter().all(|path| path.len() == 11));
+        assert!(paths.iter().all(|path| path[0] == 0.0 && path[10] == 1.0));
+        let (mean, variance) = moments(&paths, 3);
+        assert::close(mean, 0.3, 0.02);
+        assert::close(variance, 4.0 * 0.3 * 0.7, 0.05);
+    }
+
+    #[test]
+    fn paths() {
+        let mut source = source::default(42);
+        let process = Wiener::new(1.0, 2.0);
+        let paths = process.paths(0.1, 20, 20000, &mut source);
+        assert_eq!(paths.len(), 20000);
+        assert!(paths.iter().all(|path| path.len() == 21 && path[0] == 0.0));
+        let (mean, variance) = moments(&paths, 20);
+        assert::close(mean, 2.0, 0.05);
+        assert::close(variance, 8.0, 0.2);
+        let (mean, variance) = moments(&paths, 5);
+        assert::close(mean, 0.5, 0.02);
+        assert::close(variance, 2.0, 0.05);
+    }
+}
@@ -0,0 +1,194 @@
+//! Numerical integration.
+
+#[allow(unused_imports)]
+use special::Primitive;
+
+use alloc::vec;
+
+const EPSILON: f64 = 1e-12;
+const LIMIT: usize = 1000;
+const TOLERANCE: f64 = 1e-12;
+
+/// Integrate a function over an interval.
+///
+/// The endpoints are allowed to be infinite, in which case the interval is
+/// mapped onto a finite one via a change of variable. The integration is
+/// carried out by an adaptive Gauss–Kronrod quadrature.
+pub fn integrate<F>(function: F, a: f64, b: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    if a == b {
+        return 0.0;
+    }
+    if a > b {
+        return -integrate(function, b, a);
+    }
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => adapt(&function, a, b),
+        (true, false) => adapt(
+            &|t: f64| {
+                let s = 1.0 - t;
+                function(a + t / s) / (s * s)
+            },
+            0.0,
+            1.0,
+        ),
+        (false, true) => adapt(
+            &|t: f64| {
+                let s = 1.0 - t;
+                function(b - t / s) / (s * s)
+            },
+            0.0,
+            1.0,
+        ),
+        (false, false) => adapt(
+            &|t: f64| {
+                let s = 1.0 - t * t;
+                function(t / s) * (1.0 + t * t) / (s * s)
+            },
+            -1.0,
+            1.0,
+        ),
+    }
+}
+
+/// Integrate a function over an interval split at the given points.
+///
+/// The points are sorted in place, and those outside the interval are ignored.
+pub(crate) fn integrate_pieces<F>(function: F, start: f64, end: f64, points: &mut [f64]) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    points.sort_by(f64::total_cmp);
+    let mut sum = 0.0;
+    let mut left = start;
+    for &right in points.iter().chain(Some(&end)) {
+        if right > left && right <= end {
+            sum += integrate(&function, left, right);
+            left = right;
+        }
+    }
+    sum
+}
+
+fn adapt<F>(function: &F, a: f64, b: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let (value, error) = kronrod(function, a, b);
+    let mut intervals = vec![(a, b, value, error)];
+    let (mut value, mut error) = (value, error);
+    while intervals.len() < LIMIT && error > TOLERANCE.max(EPSILON * value.abs()) {
+        let (i, _) = intervals
+            .iter()
+            .enumerate()
+            .fold((0, -1.0), |(j, max), (i, interval)| {
+                if interval.3 > max {
+                    (i, interval.3)
+                } else {
+                    (j, max)
+                }
+            });
+        let (a, b, old_value, old_error) = intervals.swap_remove(i);
+        let c = 0.5 * (a + b);
+        if c <= a || c >= b {
+            intervals.push((a, b, old_value, 0.0));
+            error -= old_error;
+            continue;
+        }
+        let (left_value, left_error) = kronrod(function, a, c);
+        let (right_value, right_error) = kronrod(function, c, b);
+        value += left_value + right_value - old_value;
+        error += left_error + right_error - old_error;
+        intervals.push((a, c, left_value, left_error));
+        intervals.push((c, b, right_value, right_error));
+        if !value.is_finite() {
+            break;
+        }
+    }
+    intervals.iter().fold(0.0, |sum, interval| sum + interval.2)
+}
+
+/// Evaluate the 15-point Kronrod rule along with an error estimate obtained
+/// from the embedded 7-point Gauss rule.
+#[allow(clippy::excessive_precision)]
+fn kronrod<F>(function: &F, a: f64, b: f64) -> (f64, f64)
+where
+    F: Fn(f64) -> f64,
+{
+    const X: [f64; 8] = [
+        0.991455371120812639206854697526329,
+        0.949107912342758524526189684047851,
+        0.864864423359769072789712788640926,
+        0.741531185599394439863864773280788,
+        0.586087235467691130294144845693013,
+        0.405845151377397166906606412076961,
+        0.207784955007898467600689403773245,
+        0.000000000000000000000000000000000,
+    ];
+    const WK: [f64; 8] = [
+        0.022935322010529224963732008058970,
+        0.063092092629978553290700663189204,
+        0.104790010322250183839876322541518,
+        0.140653259715525918745189590510238,
+        0.169004726639267902826583426598550,
+        0.190350578064785409913256402421014,
+        0.204432940075298892414161999234649,
+        0.209482141084727828012999174891714,
+    ];
+    const WG: [f64; 4] = [
+        0.129484966168869693270611432679082,
+        0.279705391489276667901467771423780,
+        0.381830050505118944950369775488975,
+        0.417959183673469387755102040816327,
+    ];
+
+    let center = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let value = function(center);
+    let mut kronrod = WK[7] * value;
+    let mut gauss = WG[3] * value;
+    for i in 0..7 {
+        let delta = half * X[i];
+        let value = function(center - delta) + function(center + delta);
+        kronrod += WK[i] * value;
+        if i % 2 == 1 {
+            gauss += WG[i / 2] * value;
+        }
+    }
+    (half * kronrod, (half * (kronrod - gauss)).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::integrate;
+
+    #[test]
+    fn finite() {
+        assert::close(integrate(|x| x * x, 0.0, 3.0), 9.0, 1e-12);
+        assert::close(integrate(|x| x * x, 3.0, 0.0), -9.0, 1e-12);
+        assert::close(
+            integrate(|x: f64| x.sin(), 0.0, core::f64::consts::PI),
+            2.0,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn infinite() {
+        use core::f64::consts::PI;
+        assert::close(
+            integrate(|x: f64| (-x).exp(), 0.0, f64::INFINITY),
+            1.0,
+            1e-12,
+        );
+        assert::close(
+            integrate(|x: f64| (-x * x).exp(), f64::NEG_INFINITY, f64::INFINITY),
+            PI.sqrt(),
+            1e-12,
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Gaussian, Sample};
+use error::{self, Error};
+use sampler::Independent;
+use source::Source;
+
+/// A sampler of correlated Gaussian vectors.
+///
+/// The sampler is created by `correlated_gaussian`. Each vector is obtained by
+/// transforming independent standard Gaussian samples via the Cholesky factor
+/// of the covariance matrix, which is computed only once.
+#[derive(Clone, Debug)]
+pub struct CorrelatedGaussian {
+    mean: Vec<f64>,
+    factor: Vec<f64>,
+}
+
+/// Create a sampler of Gaussian vectors with mean `mean` and covariance matrix
+/// `covariance`.
+///
+/// The covariance matrix is stored by rows and should be symmetric and
+/// positive definite.
+pub fn correlated_gaussian(mean: &[f64], covariance: &[f64]) -> Result<CorrelatedGaussian, Error> {
+    let n = mean.len();
+    error::require(
+        "covariance.len() == mean.len()^2",
+        covariance.len() == n * n,
+    )?;
+    let mut factor = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..(i + 1) {
+            let mut sum = covariance[i * n + j];
+            for k in 0..j {
+                sum -= factor[i * n + k] * factor[j * n + k];
+            }
+            if i == j {
+                error::require("is_positive_definite(covariance)", sum > 0.0)?;
+                factor[i * n + i] = sum.sqrt();
+            } else {
+                factor[i * n + j] = sum / factor[j * n + j];
+            }
+        }
+    }
+    Ok(CorrelatedGaussian {
+        mean: mean.to_vec(),
+        factor,
+    })
+}
+
+impl CorrelatedGaussian {
+    /// Return the dimension of the vectors.
+    #[inline(always)]
+    pub fn dimension(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Draw a vector.
+    pub fn sample<S>(&self, source: &mut S) -> Vec<f64>
+    where
+        S: Source,
+    {
+        let mut buffer = vec![0.0; self.dimension()];
+        self.sample_into(&mut buffer, source);
+        buffer
+    }
+
+    /// Draw a vector filling a buffer.
+    ///
+    /// It should hold that the length of `buffer` is equal to the dimension.
+    pub fn sample_into<S>(&self, buffer: &mut [f64], source: &mut S)
+    where
+        S: Source,
+    {
+        let n = self.dimension();
+        should!(buffer.len() == n);
+        Gaussian::default().sample_into(buffer, source);
+        for i in (0..n).rev() {
+            let row = &self.factor[(i * n)..(i * n + i + 1)];
+            let sum = row
+                .iter()
+                .zip(buffer.iter())
+                .fold(self.mean[i], |sum, (a, x)| sum + a * x);
+            buffer[i] = sum;
+        }
+    }
+}
+
+impl<'a, S> Iterator for Independent<&'a CorrelatedGaussian, &'a mut S>
+where
+    S: Source,
+{
+    type Item = Vec<f64>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Vec<f64>> {
+        Some(self.0.sample(self.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::correlated_gaussian;
+
+    #[test]
+    fn moments() {
+        let mut source = source::default(42);
+        let mean = [1.0, -1.0, 0.0];
+        let covariance = [4.0, 2.0, 0.4, 2.0, 2.0, -0.3, 0.4, -0.3, 1.0];
+        let sampler = correlated_gaussian(&mean, &covariance).unwrap();
+        assert_eq!(sampler.dimension(), 3);
+        let samples = Independent(&sampler, &mut source)
+            .take(100000)
+            .collect::<Vec<_>>();
+        let mut moments = [0.0; 9];
+        let mut averages = [0.0; 3];
+        for sample in samples.iter() {
+            for i in 0..3 {
+                averages[i] += sample[i] / 100000.0;
+                for j in 0..3 {
+                    moments[i * 3 + j] += (sample[i] - mean[i]) * (sample[j] - mean[j]) / 100000.0;
+                }
+            }
+        }
+        assert::close(&averages, &mean, 0.02);
+        assert::close(&moments, &covariance, 0.05);
+    }
+
+    #[test]
+    fn invalid() {
+        use Error;
+        assert_eq!(
+            correlated_gaussian(&[0.0, 0.0], &[1.0, 0.0, 0.0]).unwrap_err(),
+            Error::Violated("covariance.len() == mean.len()^2")
+        );
+        assert_eq!(
+            correlated_gaussian(&[0.0, 0.0], &[1.0, 2.0, 2.0, 1.0]).unwrap_err(),
+            Error::Violated("is_positive_definite(covariance)")
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Gaussian, Sample};
+use math;
+use source::Source;
+
+/// A means of drawing a sequence of states of a multivariate distribution via
+/// Hamiltonian Monte Carlo.
+///
+/// The distribution is specified by its log-density, which is allowed to be
+/// known only up to an additive constant. Each step of the iterator draws a
+/// standard Gaussian momentum, simulates the Hamiltonian dynamics via `steps`
+/// leapfrog steps of size `step`, accepts or rejects the proposal via the
+/// Metropolis criterion, and yields the resulting state. The gradient of the
+/// log-density is either supplied or approximated via central differences.
+///
+/// ## References
+///
+/// 1. R. M. Neal, “MCMC using Hamiltonian dynamics,” in Handbook of Markov
+///    Chain Monte Carlo, Chapman & Hall/CRC, 2011, pp. 113–162.
+pub struct Hamiltonian<'l, F, G, S: 'l> {
+    log_density: F,
+    gradient: Option<G>,
+    state: Vec<f64>,
+    step: f64,
+    steps: usize,
+    source: &'l mut S,
+    proposed: usize,
+    accepted: usize,
+}
+
+impl<'l, F, S> Hamiltonian<'l, F, fn(&[f64]) -> Vec<f64>, S>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    /// Create a sampler starting from the state `initial` with the gradient
+    /// approximated via central differences.
+    ///
+    /// It should hold that `step > 0` and `steps > 0`.
+    #[inline]
+    pub fn new(
+        log_density: F,
+        initial: Vec<f64>,
+        step: f64,
+        steps: usize,
+        source: &'l mut S,
+    ) -> Self {
+        should!(step > 0.0 && steps > 0);
+        Hamiltonian {
+            log_density,
+            gradient: None,
+            state: initial,
+            step,
+            steps,
+            source,
+            proposed: 0,
+            accepted: 0,
+        }
+    }
+}
+
+impl<'l, F, G, S> Hamiltonian<'l, F, G, S>
+where
+    F: Fn(&[f64]) -> f64,
+    G: Fn(&[f64]) -> Vec<f64>,
+{
+    /// Create a sampler starting from the state `initial` with the gradient
+    /// computed by `gradient`.
+    ///
+    /// It should hold that `step > 0` and `steps > 0`.
+    #[inline]
+    pub fn with_gradient(
+        log_density: F,
+        gradient: G,
+        initial: Vec<f64>,
+        step: f64,
+        steps: usize,
+        source: &'l mut S,
+    ) -> Self {
+        should!(step > 0.0 && steps > 0);
+        Hamiltonian {
+            log_density,
+            gradient: Some(gradient),
+            state: initial,
+            step,
+            steps,
+            source,
+            proposed: 0,
+            accepted: 0,
+        }
+    }
+
+    /// Return the fraction of accepted proposals so far.
+    pub fn acceptance(&self) -> f64 {
+        if self.proposed == 0 {
+            return 0.0;
+        }
+        self.accepted as f64 / self.proposed as f64
+    }
+
+    fn gradient(&self, x: &[f64]) -> Vec<f64> {
+        match self.gradient {
+            Some(ref gradient) => gradient(x),
+            None => difference(&self.log_density, x),
+        }
+    }
+}
+
+impl<'l, F, G, S> Iterator for Hamiltonian<'l, F, G, S>
+where
+    F: Fn(&[f64]) -> f64,
+    G: Fn(&[f64]) -> Vec<f64>,
+    S: Source,
+{
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        let step = self.step;
+        let mut q = self.state.clone();
+        let momentum = Gaussian::default();
+        let mut p = q
+            .iter()
+            .map(|_| momentum.sample(self.source))
+            .collect::<Vec<_>>();
+        let start = (self.log_density)(&q) - 0.5 * dot(&p, &p);
+
+        let mut gradient = self.gradient(&q);
+        for i in 0..self.steps {
+            for j in 0..q.len() {
+                p[j] += 0.5 * step * gradient[j];
+                q[j] += step * p[j];
+            }
+            gradient = self.gradient(&q);
+            for j in 0..q.len() {
+                p[j] += 0.5 * step * gradient[j];
+            }
+            if i + 1 < self.steps && !gradient.iter().all(|g| g.is_finite()) {
+                break;
+            }
+        }
+        let end = (self.log_density)(&q) - 0.5 * dot(&p, &p);
+
+        self.proposed += 1;
+        if end.is_finite() && math::ln(self.source.read::<f64>()) < end - start {
+            self.accepted += 1;
+            self.state = q;
+        }
+        Some(self.state.clone())
+    }
+}
+
+fn difference<F>(function: &F, x: &[f64]) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let mut x = x.to_vec();
+    let mut gradient = Vec::with_capacity(x.len());
+    for i in 0..x.len() {
+        let value = x[i];
+        let h = libm::cbrt(f64::EPSILON) * value.abs().max(1.0);
+        x[i] = value + h;
+        let upper = function(&x);
+        x[i] = value - h;
+        let lower = function(&x);
+        x[i] = value;
+        gradient.push((upper - lower) / (2.0 * h));
+    }
+    gradient
+}
+
+#[inline]
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y.iter()).fold(0.0, |sum, (x, y)| sum + x * y)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert;
+    use prelude::*;
+
+    use super::Hamiltonian;
+
+    fn moments(states: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+        let n = states.len() as f64;
+        let mut mean = vec![0.0; states[0].len()];
+        let mut variance = vec![0.0; states[0].len()];
+        for state in states.iter() {
+            for (j, &x) in state.iter().enumerate() {
+                mean[j] += x / n;
+            }
+        }
+        for state in states.iter() {
+            for (j, &x) in state.iter().enumerate() {
+                variance[j] += (x - mean[j]).powi(2) / n;
+            }
+        }
+        (mean, variance)
+    }
+
+    #[test]
+    fn numeric() {
+        let mut source = source::default(42);
+        let log_density = |x: &[f64]| -0.5 * (x[0] * x[0] + (x[1] - 1.0).powi(2) / 4.0);
+        let mut sampler = Hamiltonian::new(log_density, vec![3.0, -3.0], 0.3, 10, &mut source);
+        let states = sampler.by_ref().skip(100).take(20000).collect::<Vec<_>>();
+        assert!(sampler.acceptance() > 0.8);
+        let (mean, variance) = moments(&states);
+        assert::close(&mean, &[0.0, 1.0], 0.1);
+        assert::close(&variance, &[1.0, 4.0], 0.2);
+    }
+
+    #[test]
+    fn with_gradient() {
+        let mut source = source::default(42);
+        let log_density = |x: &[f64]| -0.5 * x[0] * x[0];
+        let gradient = |x: &[f64]| vec![-x[0]];
+        let states =
+            Hamiltonian::with_gradient(log_density, gradient, vec![0.0], 0.2, 15, &mut source)
+                .take(20000)
+                .collect::<Vec<_>>();
+        let (mean, variance) = moments(&states);
+        assert::close(mean[0], 0.0, 0.05);
+        assert::close(variance[0], 1.0, 0.05);
+    }
+}
@@ -1,11 +1,49 @@
 //! Samplers of random numbers.
 
-use distribution::Sample;
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Continuous, Inverse, Sample};
 use source::Source;
 
+mod correlated;
+mod hamiltonian;
+mod nonparametric;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod particle;
+mod poisson;
+mod reservoir;
+mod tabulated;
+
+pub mod resample;
+
+pub use self::correlated::{correlated_gaussian, CorrelatedGaussian};
+pub use self::hamiltonian::Hamiltonian;
+pub use self::nonparametric::{ChineseRestaurant, DirichletProcess};
+#[cfg(feature = "rayon")]
+pub use self::parallel::parallel;
+pub use self::particle::ParticleFilter;
+pub use self::poisson::{count_events, PoissonProcess};
+pub use self::reservoir::WeightedReservoir;
+pub use self::tabulated::Tabulated;
+
 /// A means of drawing a sequence of independent samples.
 pub struct Independent<D, S>(pub D, pub S);
 
+impl<'a, T, D, S> Independent<&'a D, &'a mut S>
+where
+    D: Sample<Value = T>,
+    S: Source,
+{
+    /// Draw a fixed number of samples into an array.
+    #[inline]
+    pub fn take_array<const N: usize>(&mut self) -> [T; N] {
+        self.0.sample_array(self.1)
+    }
+}
+
 impl<'a, T, D, S> Iterator for Independent<&'a D, &'a mut S>
 where
     D: Sample<Value = T>,
@@ -18,3 +56,600 @@ where
         Some(self.0.sample(self.1))
     }
 }
+
+/// A means of drawing a sequence of independent samples that owns its
+/// distribution and source.
+///
+/// Unlike `Independent`, which borrows its parts, the sampler can be returned
+/// from functions and moved into threads.
+pub struct OwnedIndependent<D, S>(pub D, pub S);
+
+impl<T, D, S> Iterator for OwnedIndependent<D, S>
+where
+    D: Sample<Value = T>,
+    S: Source,
+{
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<T> {
+        Some(self.0.sample(&mut self.1))
+    }
+}
+
+/// A means of drawing a sequence of antithetic pairs of samples.
+///
+/// Each pair is obtained by inverting the distribution function at a
+/// uniformly distributed point `u` and at `1 - u`. The two samples in a pair
+/// are negatively correlated, which reduces the variance of estimates of
+/// expected values of monotone functions.
+pub struct Antithetic<D, S>(pub D, pub S);
+
+impl<'a, T, D, S> Iterator for Antithetic<&'a D, &'a mut S>
+where
+    D: Inverse<Value = T>,
+    S: Source,
+{
+    type Item = (T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(T, T)> {
+        let u = self.1.read::<f64>();
+        Some((self.0.inverse(u), self.0.inverse(1.0 - u)))
+    }
+}
+
+/// A means of drawing a sequence of states of a multivariate distribution via
+/// Gibbs sampling.
+///
+/// The distribution is specified by its full conditionals: `conditional(i,
+/// state)` should return the distribution of the `i`th coordinate given the
+/// other coordinates of `state`. Each step of the iterator performs a full
+/// sweep, updating the coordinates one by one in order, and yields the
+/// resulting state. The yielded states are correlated, and the first ones
+/// depend on the initial state, which is commonly addressed by discarding a
+/// number of them.
+pub struct Gibbs<'l, F, S: 'l> {
+    conditional: F,
+    state: Vec<f64>,
+    source: &'l mut S,
+}
+
+impl<'l, F, S> Gibbs<'l, F, S> {
+    /// Create a sampler starting from the state `initial`.
+    #[inline]
+    pub fn new(conditional: F, initial: Vec<f64>, source: &'l mut S) -> Self {
+        Gibbs {
+            conditional,
+            state: initial,
+            source,
+        }
+    }
+}
+
+impl<'l, D, F, S> Iterator for Gibbs<'l, F, S>
+where
+    D: Sample<Value = f64>,
+    F: FnMut(usize, &[f64]) -> D,
+    S: Source,
+{
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        for i in 0..self.state.len() {
+            let distribution = (self.conditional)(i, &self.state);
+            self.state[i] = distribution.sample(self.source);
+        }
+        Some(self.state.clone())
+    }
+}
+
+/// A means of drawing a sequence of weighted samples via importance sampling.
+///
+/// The samples are drawn from the proposal distribution, and each one is
+/// accompanied by its importance weight, which is the ratio of the target
+/// density to the proposal density at the sample. The proposal density should
+/// be positive wherever the target density is.
+pub struct Importance<T, P, S> {
+    /// The distribution of interest.
+    pub target: T,
+    /// The distribution from which the samples are drawn.
+    pub proposal: P,
+    /// The source of randomness.
+    pub source: S,
+}
+
+impl<'a, T, P, S> Iterator for Importance<&'a T, &'a P, &'a mut S>
+where
+    T: Continuous,
+    P: Continuous + Sample<Value = f64>,
+    S: Source,
+{
+    type Item = (f64, f64);
+
+    #[inline]
+    fn next(&mut self) -> Option<(f64, f64)> {
+        let x = self.proposal.sample(self.source);
+        Some((x, self.target.density(x) / self.proposal.density(x)))
+    }
+}
+
+/// A means of drawing a sequence of independent samples along with the
+/// logarithm of the density at each of them.
+///
+/// The pairs can be used for importance reweighting and diagnostics without
+/// evaluating the density separately.
+pub struct WithLogDensity<D, S>(pub D, pub S);
+
+impl<'a, D, S> Iterator for WithLogDensity<&'a D, &'a mut S>
+where
+    D: Continuous + Sample<Value = f64>,
+    S: Source,
+{
+    type Item = (f64, f64);
+
+    #[inline]
+    fn next(&mut self) -> Option<(f64, f64)> {
+        let x = self.0.sample(self.1);
+        Some((x, self.0.log_density(x)))
+    }
+}
+
+/// A means of drawing a sequence of stratified samples.
+///
+/// The unit interval is partitioned into `n` strata of equal probability, and
+/// one sample is drawn from each stratum by inverting the distribution function
+/// at a uniformly distributed point within the stratum. The strata are visited
+/// in increasing order, and the iterator ends after `n` samples.
+pub struct Stratified<'l, D: 'l, S: 'l> {
+    distribution: &'l D,
+    source: &'l mut S,
+    count: usize,
+    next: usize,
+}
+
+impl<'l, D, S> Stratified<'l, D, S> {
+    /// Create a sampler drawing `count` samples.
+    #[inline]
+    pub fn new(distribution: &'l D, count: usize, source: &'l mut S) -> Self {
+        Stratified {
+            distribution,
+            source,
+            count,
+            next: 0,
+        }
+    }
+}
+
+impl<'l, T, D, S> Iterator for Stratified<'l, D, S>
+where
+    D: Inverse<Value = T>,
+    S: Source,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.next == self.count {
+            return None;
+        }
+        let p = (self.next as f64 + self.source.read::<f64>()) / self.count as f64;
+        self.next += 1;
+        Some(self.distribution.inverse(p))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'l, T, D, S> ExactSizeIterator for Stratified<'l, D, S>
+where
+    D: Inverse<Value = T>,
+    S: Source,
+{
+}
+
+/// The result of a Monte Carlo estimation.
+#[derive(Clone, Copy, Debug)]
+pub struct MonteCarlo {
+    /// The estimate of the expected value.
+    pub estimate: f64,
+    /// The standard error of the estimate.
+    pub error: f64,
+}
+
+impl MonteCarlo {
+    /// Compute a confidence interval with confidence level `level`.
+    ///
+    /// The interval is based on the normal approximation. It should hold that
+    /// `level > 0` and `level < 1`.
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        use distribution::{Gaussian, Inverse};
+        should!(level > 0.0 && level < 1.0);
+        let width = Gaussian::default().inverse(0.5 + 0.5 * level) * self.error;
+        (self.estimate - width, self.estimate + width)
+    }
+}
+
+/// Estimate the expected value of a function of a random variable using `n`
+/// independent samples.
+///
+/// It should hold that `n > 1`.
+pub fn monte_carlo<D, F, S>(distribution: &D, function: F, n: usize, source: &mut S) -> MonteCarlo
+where
+    D: Sample,
+    F: Fn(D::Value) -> f64,
+    S: Source,
+{
+    should!(n > 1);
+    let (mut mean, mut sum) = (0.0, 0.0);
+    for i in 0..n {
+        let x = function(distribution.sample(source));
+        let delta = x - mean;
+        mean += delta / (i + 1) as f64;
+        sum += delta * (x - mean);
+    }
+    MonteCarlo {
+        estimate: mean,
+        error: (sum / ((n - 1) * n) as f64).sqrt(),
+    }
+}
+
+/// Estimate the expected value of a function of a random variable using `n`
+/// independent samples and a control variate.
+///
+/// The control function `control` should have a known expected value
+/// `expected` and be correlated with `function`. The estimate is corrected by
+/// the deviation of the sample average of the control function from its
+/// expected value, scaled by a coefficient fitted via least squares, which is
+/// returned along with the result. It should hold that `n > 2`.
+pub fn control_variate<D, F, G, S>(
+    distribution: &D,
+    function: F,
+    control: G,
+    expected: f64,
+    n: usize,
+    source: &mut S,
+) -> (MonteCarlo, f64)
+where
+    D: Sample,
+    F: Fn(&D::Value) -> f64,
+    G: Fn(&D::Value) -> f64,
+    S: Source,
+{
+    should!(n > 2);
+    let (mut mean_f, mut mean_g) = (0.0, 0.0);
+    let (mut sum_ff, mut sum_fg, mut sum_gg) = (0.0, 0.0, 0.0);
+    for i in 0..n {
+        let x = distribution.sample(source);
+        let (f, g) = (function(&x), control(&x));
+        let (delta_f, delta_g) = (f - mean_f, g - mean_g);
+        mean_f += delta_f / (i + 1) as f64;
+        mean_g += delta_g / (i + 1) as f64;
+        sum_ff += delta_f * (f - mean_f);
+        sum_fg += delta_f * (g - mean_g);
+        sum_gg += delta_g * (g - mean_g);
+    }
+    let coefficient = if sum_gg > 0.0 { sum_fg / sum_gg } else { 0.0 };
+    let residual = (sum_ff - coefficient * sum_fg).max(0.0);
+    let result = MonteCarlo {
+        estimate: mean_f - coefficient * (mean_g - expected),
+        error: (residual / ((n - 2) * n) as f64).sqrt(),
+    };
+    (result, coefficient)
+}
+
+/// Estimate the expected value of a function of a random variable using `n`
+/// samples with the standard error computed via batch means.
+///
+/// The samples are split into `batches` consecutive batches of equal size,
+/// and the standard error is derived from the variability of the batch
+/// averages, which accounts for correlation between nearby samples. It should
+/// hold that `batches > 1` and `n >= batches`; the remainder of `n` modulo
+/// `batches` is not used.
+pub fn monte_carlo_batched<D, F, S>(
+    distribution: &D,
+    function: F,
+    n: usize,
+    batches: usize,
+    source: &mut S,
+) -> MonteCarlo
+where
+    D: Sample,
+    F: Fn(D::Value) -> f64,
+    S: Source,
+{
+    should!(batches > 1 && n >= batches);
+    let size = n / batches;
+    let (mut mean, mut sum) = (0.0, 0.0);
+    for i in 0..batches {
+        let mut average = 0.0;
+        for _ in 0..size {
+            average += function(distribution.sample(source));
+        }
+        average /= size as f64;
+        let delta = average - mean;
+        mean += delta / (i + 1) as f64;
+        sum += delta * (average - mean);
+    }
+    MonteCarlo {
+        estimate: mean,
+        error: (sum / ((batches - 1) * batches) as f64).sqrt(),
+    }
+}
+
+/// Estimate the expected value of a function with respect to the target
+/// distribution of importance sampling.
+///
+/// The estimate is the weighted average of the function over `samples`, which
+/// are pairs of samples and their importance weights as produced by
+/// `Importance`. Since the weights are normalized by their sum, the target
+/// density is allowed to be known only up to a constant factor.
+pub fn self_normalized<I, F>(samples: I, function: F) -> f64
+where
+    I: IntoIterator<Item = (f64, f64)>,
+    F: Fn(f64) -> f64,
+{
+    let (sum, total) = samples
+        .into_iter()
+        .fold((0.0, 0.0), |(sum, total), (x, weight)| {
+            (sum + weight * function(x), total + weight)
+        });
+    sum / total
+}
+
+/// Draw `n` joint samples of several distributions via Latin hypercube
+/// sampling.
+///
+/// The unit interval is partitioned into `n` strata of equal probability for
+/// each distribution, and the strata are randomly paired across distributions
+/// so that each stratum of each distribution is hit exactly once. Each of the
+/// returned samples contains one value per distribution, in the order of
+/// `distributions`.
+pub fn latin_hypercube<D, S>(distributions: &[D], n: usize, source: &mut S) -> Vec<Vec<D::Value>>
+where
+    D: Inverse,
+    S: Source,
+{
+    let mut samples = (0..n)
+        .map(|_| Vec::with_capacity(distributions.len()))
+        .collect::<Vec<_>>();
+    let mut strata = (0..n).collect::<Vec<_>>();
+    for distribution in distributions {
+        shuffle(&mut strata, source);
+        for (sample, &stratum) in samples.iter_mut().zip(strata.iter()) {
+            let p = (stratum as f64 + source.read::<f64>()) / n as f64;
+            sample.push(distribution.inverse(p));
+        }
+    }
+    samples
+}
+
+/// Draw a uniformly distributed permutation of `0..n`.
+pub fn permutation<S>(n: usize, source: &mut S) -> Vec<usize>
+where
+    S: Source,
+{
+    let mut data = (0..n).collect::<Vec<_>>();
+    shuffle(&mut data, source);
+    data
+}
+
+/// Shuffle a slice in place.
+///
+/// The shuffling is performed via the Fisher–Yates algorithm, and all the
+/// permutations are equally likely.
+pub fn shuffle<T, S>(data: &mut [T], source: &mut S)
+where
+    S: Source,
+{
+    for i in (1..data.len()).rev() {
+        let j = ((source.read::<f64>() * (i + 1) as f64) as usize).min(i);
+        data.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert;
+    use prelude::*;
+
+    use super::{
+        control_variate, latin_hypercube, monte_carlo, monte_carlo_batched, self_normalized, Gibbs,
+        Importance, OwnedIndependent, Stratified,
+    };
+
+    fn sampler(seed: u64) -> OwnedIndependent<Uniform, source::Default> {
+        OwnedIndependent(Uniform::new(0.0, 1.0), source::default(seed))
+    }
+
+    #[test]
+    fn sample_array() {
+        let d = Uniform::new(0.0, 1.0);
+        let mut source = source::default(42);
+        let expected = Independent(&d, &mut source).take(8).collect::<Vec<_>>();
+        let mut source = source::default(42);
+        let array = d.sample_array::<5>(&mut source);
+        assert_eq!(&array[..], &expected[..5]);
+        let array: [f64; 3] = Independent(&d, &mut source).take_array();
+        assert_eq!(&array[..], &expected[5..]);
+    }
+
+    #[test]
+    fn owned_independent() {
+        let d = Uniform::new(0.0, 1.0);
+        let mut source = source::default(42);
+        let expected = Independent(&d, &mut source).take(10).collect::<Vec<_>>();
+        let samples = sampler(42).take(10).collect::<Vec<_>>();
+        assert_eq!(samples, expected);
+    }
+
+    #[test]
+    fn gibbs() {
+        let mut source = source::default(42);
+        let rho: f64 = 0.8;
+        let conditional =
+            |i: usize, state: &[f64]| Gaussian::new(rho * state[1 - i], (1.0 - rho * rho).sqrt());
+        let states = Gibbs::new(conditional, vec![5.0, -5.0], &mut source)
+            .skip(100)
+            .take(100000)
+            .collect::<Vec<_>>();
+        assert!(states.iter().all(|state| state.len() == 2));
+        let (mut mean, mut product) = ([0.0, 0.0], 0.0);
+        for state in states.iter() {
+            mean[0] += state[0] / 100000.0;
+            mean[1] += state[1] / 100000.0;
+            product += state[0] * state[1] / 100000.0;
+        }
+        assert::close(&mean, &[0.0, 0.0], 0.05);
+        assert::close(product, rho, 0.05);
+    }
+
+    #[test]
+    fn importance() {
+        let mut source = source::default(42);
+        let target = Gaussian::new(1.0, 1.0);
+        let proposal = Gaussian::new(0.0, 2.0);
+        let samples = Importance {
+            target: &target,
+            proposal: &proposal,
+            source: &mut source,
+        }
+        .take(100000)
+        .collect::<Vec<_>>();
+        for &(x, weight) in samples.iter().take(10) {
+            assert::close(weight, target.density(x) / proposal.density(x), 1e-15);
+        }
+        assert::close(self_normalized(samples.iter().cloned(), |x| x), 1.0, 0.02);
+        assert::close(self_normalized(samples, |x| x * x), 2.0, 0.05);
+    }
+
+    #[test]
+    fn latin_hypercube_strata() {
+        let mut source = source::default(42);
+        let d = [Uniform::new(0.0, 1.0), Uniform::new(0.0, 10.0)];
+        let samples = latin_hypercube(&d, 100, &mut source);
+        assert_eq!(samples.len(), 100);
+        for (j, scale) in [1.0, 10.0].iter().enumerate() {
+            let mut strata = samples
+                .iter()
+                .map(|sample| (sample[j] / scale * 100.0) as usize)
+                .collect::<Vec<_>>();
+            strata.sort();
+            assert_eq!(strata, (0..100).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn antithetic() {
+        let mut source = source::default(42);
+        let d = Gaussian::new(1.0, 2.0);
+        let pairs = Antithetic(&d, &mut source).take(1000).collect::<Vec<_>>();
+        for &(x, y) in pairs.iter() {
+            assert::close(x + y, 2.0, 1e-6);
+        }
+
+        let d = Uniform::new(0.0, 1.0);
+        for (x, y) in Antithetic(&d, &mut source).take(1000) {
+            assert::close(x + y, 1.0, 1e-12);
+        }
+    }
+
+    #[test]
+    fn with_log_density() {
+        let d = Gamma::new(2.0, 3.0);
+        let mut source = source::default(42);
+        let expected = Independent(&d, &mut source).take(100).collect::<Vec<_>>();
+        let mut source = source::default(42);
+        let pairs = WithLogDensity(&d, &mut source)
+            .take(100)
+            .collect::<Vec<_>>();
+        for (&(x, log_density), &y) in pairs.iter().zip(expected.iter()) {
+            assert_eq!(x, y);
+            assert::close(log_density, d.density(x).ln(), 1e-15);
+        }
+    }
+
+    #[test]
+    fn stratified() {
+        let mut source = source::default(42);
+        let d = Exponential::new(2.0);
+        let sampler = Stratified::new(&d, 1000, &mut source);
+        assert_eq!(sampler.len(), 1000);
+        let samples = sampler.collect::<Vec<_>>();
+        assert_eq!(samples.len(), 1000);
+        for (i, &x) in samples.iter().enumerate() {
+            assert_eq!((d.distribution(x) * 1000.0) as usize, i);
+        }
+        let mean = samples.iter().sum::<f64>() / 1000.0;
+        assert::close(mean, 0.5, 3e-3);
+    }
+
+    #[test]
+    fn permutation() {
+        let mut source = source::default(42);
+        let mut counts = [[0usize; 3]; 3];
+        for _ in 0..30000 {
+            let data = super::permutation(3, &mut source);
+            let mut sorted = data.clone();
+            sorted.sort();
+            assert_eq!(sorted, [0, 1, 2]);
+            for (i, &j) in data.iter().enumerate() {
+                counts[i][j] += 1;
+            }
+        }
+        for count in counts.iter().flat_map(|counts| counts.iter()) {
+            assert::close(*count as f64 / 10000.0, 1.0, 0.05);
+        }
+        assert!(super::permutation(0, &mut source).is_empty());
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut source = source::default(42);
+        let mut data = ['a', 'b', 'c', 'd', 'e'];
+        super::shuffle(&mut data, &mut source);
+        data.sort();
+        assert_eq!(data, ['a', 'b', 'c', 'd', 'e']);
+        super::shuffle(&mut [0u8; 0], &mut source);
+    }
+
+    #[test]
+    fn control_variate_uniform() {
+        let mut source = source::default(42);
+        let d = Uniform::new(0.0, 1.0);
+        let (result, coefficient) =
+            control_variate(&d, |&x| x.exp(), |&x| x, 0.5, 10000, &mut source);
+        let (low, high) = result.interval(0.999);
+        let expected = 1.0f64.exp() - 1.0;
+        assert!(low < expected && expected < high);
+        assert::close(coefficient, 1.69, 0.05);
+
+        let mut source = source::default(42);
+        let plain = monte_carlo(&d, |x| x.exp(), 10000, &mut source);
+        assert!(result.error < 0.2 * plain.error);
+    }
+
+    #[test]
+    fn monte_carlo_gaussian() {
+        let mut source = source::default(42);
+        let d = Gaussian::new(1.0, 2.0);
+        let result = monte_carlo(&d, |x| x * x, 100000, &mut source);
+        let (low, high) = result.interval(0.999);
+        assert!(low < 5.0 && 5.0 < high);
+        assert::close(result.error, (48.0f64 / 100000.0).sqrt(), 1e-3);
+    }
+
+    #[test]
+    fn monte_carlo_batched_uniform() {
+        let mut source = source::default(42);
+        let d = Uniform::new(0.0, 1.0);
+        let result = monte_carlo_batched(&d, |x| x, 100000, 100, &mut source);
+        let (low, high) = result.interval(0.999);
+        assert!(low < 0.5 && 0.5 < high);
+        assert::close(result.error, (1.0f64 / 12.0 / 100000.0).sqrt(), 2e-4);
+    }
+}
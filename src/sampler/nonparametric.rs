@@ -0,0 +1,174 @@
+use alloc::vec::Vec;
+
+use distribution::{Beta, Sample};
+use source::Source;
+
+/// A means of drawing a random partition via the Chinese restaurant process.
+///
+/// Each step of the iterator seats a new customer and yields the index of the
+/// table: an occupied table is chosen with probability proportional to the
+/// number of its customers, and a new table, which gets the next index, with
+/// probability proportional to `alpha`.
+pub struct ChineseRestaurant<'l, S: 'l> {
+    alpha: f64,
+    counts: Vec<usize>,
+    customers: usize,
+    source: &'l mut S,
+}
+
+/// A Dirichlet process.
+///
+/// A realization of the process is a discrete distribution whose atoms are
+/// drawn from a base distribution and whose weights are obtained via stick
+/// breaking with concentration `alpha`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirichletProcess<D> {
+    alpha: f64,
+    base: D,
+}
+
+impl<'l, S> ChineseRestaurant<'l, S> {
+    /// Create a process with concentration `alpha`.
+    ///
+    /// It should hold that `alpha > 0`.
+    #[inline]
+    pub fn new(alpha: f64, source: &'l mut S) -> Self {
+        should!(alpha > 0.0);
+        ChineseRestaurant {
+            alpha,
+            counts: Vec::new(),
+            customers: 0,
+            source,
+        }
+    }
+
+    /// Return the number of customers at each table.
+    #[inline(always)]
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+}
+
+impl<'l, S> Iterator for ChineseRestaurant<'l, S>
+where
+    S: Source,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let mut u = self.source.read::<f64>() * (self.customers as f64 + self.alpha);
+        let mut table = self.counts.len();
+        for (i, &count) in self.counts.iter().enumerate() {
+            if u < count as f64 {
+                table = i;
+                break;
+            }
+            u -= count as f64;
+        }
+        if table == self.counts.len() {
+            self.counts.push(0);
+        }
+        self.counts[table] += 1;
+        self.customers += 1;
+        Some(table)
+    }
+}
+
+impl<D> DirichletProcess<D> {
+    /// Create a process with concentration `alpha` and base distribution
+    /// `base`.
+    ///
+    /// It should hold that `alpha > 0`.
+    #[inline]
+    pub fn new(alpha: f64, base: D) -> Self {
+        should!(alpha > 0.0);
+        DirichletProcess { alpha, base }
+    }
+
+    /// Return the concentration.
+    #[inline(always)]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Return the base distribution.
+    #[inline(always)]
+    pub fn base(&self) -> &D {
+        &self.base
+    }
+}
+
+impl<D> DirichletProcess<D>
+where
+    D: Sample,
+{
+    /// Draw a realization as a list of atoms and their weights.
+    ///
+    /// The sticks are broken until the remaining length falls below
+    /// `tolerance`, and the remainder is added to the weight of the last atom
+    /// so that the weights sum up to one. It should hold that `tolerance > 0`.
+    pub fn realization<S>(&self, tolerance: f64, source: &mut S) -> Vec<(D::Value, f64)>
+    where
+        S: Source,
+    {
+        should!(tolerance > 0.0);
+        let beta = Beta::new(1.0, self.alpha, 0.0, 1.0);
+        let mut atoms = Vec::new();
+        let mut remaining = 1.0;
+        loop {
+            let weight = remaining * beta.sample(source);
+            remaining -= weight;
+            atoms.push((self.base.sample(source), weight));
+            if remaining < tolerance {
+                break;
+            }
+        }
+        if let Some(last) = atoms.last_mut() {
+            last.1 += remaining;
+        }
+        atoms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{ChineseRestaurant, DirichletProcess};
+
+    #[test]
+    fn chinese_restaurant() {
+        let mut source = source::default(42);
+        let alpha = 2.0;
+        let mut tables = 0.0;
+        for _ in 0..2000 {
+            let mut process = ChineseRestaurant::new(alpha, &mut source);
+            let assignments = process.by_ref().take(100).collect::<Vec<_>>();
+            assert_eq!(assignments[0], 0);
+            assert!(assignments
+                .iter()
+                .enumerate()
+                .all(|(i, &table)| table <= assignments[..i].iter().max().map_or(0, |&k| k + 1)));
+            assert_eq!(process.counts().iter().sum::<usize>(), 100);
+            tables += process.counts().len() as f64 / 2000.0;
+        }
+        let expected = (0..100).fold(0.0, |sum, i| sum + alpha / (alpha + i as f64));
+        assert::close(tables, expected, 0.1);
+    }
+
+    #[test]
+    fn dirichlet_process() {
+        let mut source = source::default(42);
+        let process = DirichletProcess::new(5.0, Gaussian::new(0.0, 1.0));
+        let mut mean = 0.0;
+        for _ in 0..2000 {
+            let atoms = process.realization(1e-8, &mut source);
+            assert::close(atoms.iter().map(|atom| atom.1).sum::<f64>(), 1.0, 1e-12);
+            assert!(atoms.iter().all(|atom| atom.1 >= 0.0));
+            mean += atoms.iter().fold(0.0, |sum, &(x, w)| sum + w * x) / 2000.0;
+        }
+        assert::close(mean, 0.0, 0.03);
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+use distribution::Sample;
+use source::Xorshift128Plus;
+
+const CHUNK: usize = 1 << 12;
+
+/// Draw `n` independent samples in parallel.
+///
+/// The samples are drawn in chunks, which are distributed across threads.
+/// Each chunk has its own instance of the default source: the first one is
+/// `source::default(seed)`, and each subsequent one is obtained by jumping
+/// 2^64 steps ahead of the previous one, which keeps the streams
+/// non-overlapping. The result depends only on the seed and not on the
+/// number of threads.
+pub fn parallel<D>(distribution: &D, n: usize, seed: u64) -> Vec<D::Value>
+where
+    D: Sample + Sync,
+    D::Value: Send,
+{
+    let mut state = [seed.wrapping_sub(1), seed.wrapping_add(1)];
+    let states = (0..n.div_ceil(CHUNK))
+        .map(|_| {
+            let current = state;
+            state = jump(state);
+            current
+        })
+        .collect::<Vec<_>>();
+    states
+        .into_par_iter()
+        .enumerate()
+        .flat_map_iter(|(i, state)| {
+            let mut source = Xorshift128Plus::new(state);
+            let count = CHUNK.min(n - i * CHUNK);
+            let mut chunk = Vec::with_capacity(count);
+            for _ in 0..count {
+                chunk.push(distribution.sample(&mut source));
+            }
+            chunk
+        })
+        .collect()
+}
+
+/// Advance the state of the Xorshift128+ algorithm by 2^64 steps.
+///
+/// ## References
+///
+/// 1. S. Vigna, “Further scramblings of Marsaglia’s xorshift generators,”
+///    Journal of Computational and Applied Mathematics, vol. 315, pp. 175–181,
+///    2017.
+fn jump(state: [u64; 2]) -> [u64; 2] {
+    const JUMP: [u64; 2] = [0x8a5cd789635d2dff, 0x121fd2155c472f96];
+
+    let (mut s0, mut s1) = (state[0], state[1]);
+    let (mut t0, mut t1) = (0, 0);
+    for &word in JUMP.iter() {
+        for bit in 0..64 {
+            if word & (1 << bit) != 0 {
+                t0 ^= s0;
+                t1 ^= s1;
+            }
+            let x = s0 ^ (s0 << 23);
+            s0 = s1;
+            s1 = x ^ (x >> 17) ^ s1 ^ (s1 >> 26);
+        }
+    }
+    [t0, t1]
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use prelude::*;
+
+    use super::{parallel, CHUNK};
+
+    #[test]
+    fn deterministic() {
+        let d = Gaussian::new(0.0, 1.0);
+        let n = 3 * CHUNK + 42;
+        let samples = parallel(&d, n, 42);
+        assert_eq!(samples.len(), n);
+        assert_eq!(samples, parallel(&d, n, 42));
+
+        let mut source = source::default(42);
+        let expected = Independent(&d, &mut source).take(CHUNK).collect::<Vec<_>>();
+        assert_eq!(&samples[..CHUNK], &expected[..]);
+        assert!(samples[..CHUNK] != samples[CHUNK..(2 * CHUNK)]);
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::{vec, vec::Vec};
+
+use distribution::{Continuous, Sample};
+use sampler::resample;
+use source::Source;
+
+/// A particle filter for state-space models.
+///
+/// The filter approximates the distribution of a scalar hidden state by a set
+/// of weighted particles. Each time step consists of propagating the particles
+/// through the transition model, weighting them according to the likelihood
+/// of an observation, and, when the weights degenerate, resampling them.
+///
+/// ## References
+///
+/// 1. A. Doucet and A. M. Johansen, “A tutorial on particle filtering and
+///    smoothing: Fifteen years later,” in The Oxford Handbook of Nonlinear
+///    Filtering, Oxford University Press, 2011, pp. 656–704.
+#[derive(Clone, Debug)]
+pub struct ParticleFilter {
+    particles: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl ParticleFilter {
+    /// Create a filter with equally weighted particles.
+    ///
+    /// It should hold that `particles` is not empty.
+    pub fn new(particles: Vec<f64>) -> Self {
+        should!(!particles.is_empty());
+        let weights = vec![1.0 / particles.len() as f64; particles.len()];
+        ParticleFilter { particles, weights }
+    }
+
+    /// Return the particles.
+    #[inline(always)]
+    pub fn particles(&self) -> &[f64] {
+        &self.particles
+    }
+
+    /// Return the normalized weights of the particles.
+    #[inline(always)]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Propagate the particles through the transition model.
+    ///
+    /// Each particle `x` is replaced with `transition(x) + e`, where `e` is a
+    /// sample of `noise`.
+    pub fn propagate<F, D, S>(&mut self, transition: F, noise: &D, source: &mut S)
+    where
+        F: Fn(f64) -> f64,
+        D: Sample<Value = f64>,
+        S: Source,
+    {
+        for x in self.particles.iter_mut() {
+            *x = transition(*x) + noise.sample(source);
+        }
+    }
+
+    /// Weight the particles according to the likelihood of an observation.
+    ///
+    /// The observation is modeled as `observation(x) + e`, where `e` is
+    /// distributed according to `noise`. If the observation has zero
+    /// likelihood under all the particles, the weights are reset to be equal.
+    pub fn weight<G, D>(&mut self, value: f64, observation: G, noise: &D)
+    where
+        G: Fn(f64) -> f64,
+        D: Continuous,
+    {
+        let mut total = 0.0;
+        for (weight, &x) in self.weights.iter_mut().zip(self.particles.iter()) {
+            *weight *= noise.density(value - observation(x));
+            total += *weight;
+        }
+        let n = self.weights.len() as f64;
+        for weight in self.weights.iter_mut() {
+            *weight = if total > 0.0 {
+                *weight / total
+            } else {
+                1.0 / n
+            };
+        }
+    }
+
+    /// Resample the particles according to their weights.
+    ///
+    /// The resampling is systematic, and the weights are reset to be equal.
+    pub fn resample<S>(&mut self, source: &mut S)
+    where
+        S: Source,
+    {
+        let n = self.particles.len();
+        let particles = resample::systematic(&self.weights, n, source)
+            .into_iter()
+            .map(|i| self.particles[i])
+            .collect();
+        self.particles = particles;
+        self.weights = vec![1.0 / n as f64; n];
+    }
+
+    /// Resample the particles if the effective sample size falls below
+    /// `threshold`, and return whether resampling took place.
+    pub fn resample_below<S>(&mut self, threshold: f64, source: &mut S) -> bool
+    where
+        S: Source,
+    {
+        if self.effective_sample_size() < threshold {
+            self.resample(source);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Compute the effective sample size.
+    ///
+    /// The effective sample size ranges from one, when a single particle
+    /// carries all the weight, to the number of particles, when the weights are
+    /// equal.
+    pub fn effective_sample_size(&self) -> f64 {
+        1.0 / self.weights.iter().fold(0.0, |sum, w| sum + w * w)
+    }
+
+    /// Compute the weighted mean of the particles.
+    pub fn mean(&self) -> f64 {
+        self.particles
+            .iter()
+            .zip(self.weights.iter())
+            .fold(0.0, |sum, (x, w)| sum + x * w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert;
+    use prelude::*;
+
+    use super::ParticleFilter;
+
+    #[test]
+    fn effective_sample_size() {
+        let mut filter = ParticleFilter::new(vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(filter.effective_sample_size(), 4.0);
+        filter.weight(3.0, |x| x, &Gaussian::new(0.0, 1e-3));
+        assert::close(filter.effective_sample_size(), 1.0, 1e-12);
+        filter.resample(&mut source::default(42));
+        assert_eq!(filter.particles(), &[3.0, 3.0, 3.0, 3.0]);
+        assert_eq!(filter.effective_sample_size(), 4.0);
+    }
+
+    #[test]
+    fn random_walk() {
+        let mut source = source::default(42);
+        let (transition, observation) = (Gaussian::new(0.0, 1.0), Gaussian::new(0.0, 1.0));
+
+        let mut x = 0.0;
+        let values = (0..20)
+            .map(|_| {
+                x += transition.sample(&mut source);
+                x + observation.sample(&mut source)
+            })
+            .collect::<Vec<_>>();
+
+        let particles = Independent(&Gaussian::new(0.0, 1e-6), &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        let mut filter = ParticleFilter::new(particles);
+        let (mut mean, mut variance) = (0.0, 0.0);
+        for &value in values.iter() {
+            filter.propagate(|x| x, &transition, &mut source);
+            filter.weight(value, |x| x, &observation);
+            assert!(filter.effective_sample_size() > 1000.0);
+
+            variance += 1.0;
+            let gain = variance / (variance + 1.0);
+            mean += gain * (value - mean);
+            variance *= 1.0 - gain;
+            assert::close(filter.mean(), mean, 0.05);
+
+            filter.resample_below(5000.0, &mut source);
+        }
+    }
+}
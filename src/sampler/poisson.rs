@@ -0,0 +1,110 @@
+use alloc::{vec, vec::Vec};
+
+use distribution::{Exponential, Sample};
+use source::Source;
+
+/// A means of drawing the event times of a homogeneous Poisson process.
+///
+/// The times between consecutive events are independent and exponentially
+/// distributed with the given rate, and the first event occurs after such a
+/// time from zero. The iterator is infinite.
+pub struct PoissonProcess<'l, S: 'l> {
+    interarrival: Exponential,
+    time: f64,
+    source: &'l mut S,
+}
+
+impl<'l, S> PoissonProcess<'l, S> {
+    /// Create a process with rate `rate`.
+    ///
+    /// It should hold that `rate > 0`.
+    #[inline]
+    pub fn new(rate: f64, source: &'l mut S) -> Self {
+        PoissonProcess {
+            interarrival: Exponential::new(rate),
+            time: 0.0,
+            source,
+        }
+    }
+
+    /// Return the rate.
+    #[inline(always)]
+    pub fn rate(&self) -> f64 {
+        self.interarrival.lambda()
+    }
+}
+
+impl<'l, S> Iterator for PoissonProcess<'l, S>
+where
+    S: Source,
+{
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        self.time += self.interarrival.sample(self.source);
+        Some(self.time)
+    }
+}
+
+/// Count events in consecutive windows of width `width` starting from zero.
+///
+/// The event times should be nondecreasing, which is the case for those
+/// produced by `PoissonProcess`. The `i`th count corresponds to the window
+/// `[i * width, (i + 1) * width)`. The iteration stops at the first event past
+/// the last window, which allows `times` to be infinite.
+pub fn count_events<I>(times: I, width: f64, windows: usize) -> Vec<usize>
+where
+    I: IntoIterator<Item = f64>,
+{
+    should!(width > 0.0);
+    let mut counts = vec![0; windows];
+    for time in times {
+        if time < 0.0 {
+            continue;
+        }
+        let i = (time / width) as usize;
+        if i >= windows {
+            break;
+        }
+        counts[i] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use assert;
+    use prelude::*;
+
+    use super::{count_events, PoissonProcess};
+
+    #[test]
+    fn counts() {
+        let mut source = source::default(42);
+        let process = PoissonProcess::new(4.0, &mut source);
+        assert_eq!(process.rate(), 4.0);
+        let counts = count_events(process, 0.5, 10000);
+        let n = counts.len() as f64;
+        let mean = counts.iter().fold(0.0, |sum, &x| sum + x as f64) / n;
+        let variance = counts
+            .iter()
+            .fold(0.0, |sum, &x| sum + (x as f64 - mean).powi(2))
+            / n;
+        assert::close(mean, 2.0, 0.05);
+        assert::close(variance, 2.0, 0.1);
+    }
+
+    #[test]
+    fn times() {
+        let mut source = source::default(42);
+        let times = PoissonProcess::new(2.0, &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        assert!(times[0] >= 0.0);
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert::close(times[9999] / 10000.0, 0.5, 0.02);
+        assert_eq!(count_events(vec![-1.0, 0.1, 0.2, 1.5, 7.0], 1.0, 2), [2, 1]);
+    }
+}
@@ -0,0 +1,146 @@
+//! Resampling schemes.
+//!
+//! Each scheme draws `n` indices with probabilities proportional to `weights`,
+//! which should be nonnegative with a positive sum. The indices are returned
+//! in increasing order. The schemes differ in the variance of the number of
+//! times each index is drawn: the multinomial scheme has the highest one,
+//! whereas the residual, stratified, and systematic schemes reduce it.
+//!
+//! ## References
+//!
+//! 1. R. Douc, O. Cappé, and E. Moulines, “Comparison of resampling schemes
+//!    for particle filtering,” in Proceedings of the 4th International
+//!    Symposium on Image and Signal Processing and Analysis, 2005, pp. 64–69.
+
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use source::Source;
+
+/// Resample via independent draws.
+pub fn multinomial<S>(weights: &[f64], n: usize, source: &mut S) -> Vec<usize>
+where
+    S: Source,
+{
+    let mut points = (0..n).map(|_| source.read::<f64>()).collect::<Vec<_>>();
+    points.sort_by(|one, other| one.total_cmp(other));
+    select(weights, points)
+}
+
+/// Resample by deterministically replicating each index according to the
+/// integer part of its expected count and drawing the rest via the
+/// multinomial scheme.
+pub fn residual<S>(weights: &[f64], n: usize, source: &mut S) -> Vec<usize>
+where
+    S: Source,
+{
+    let total = sum(weights);
+    let mut counts = Vec::with_capacity(weights.len());
+    let mut residuals = Vec::with_capacity(weights.len());
+    for &weight in weights {
+        let expected = n as f64 * weight / total;
+        let count = expected.floor();
+        counts.push(count as usize);
+        residuals.push(expected - count);
+    }
+    let remaining = n - counts.iter().sum::<usize>();
+    if remaining > 0 {
+        for i in multinomial(&residuals, remaining, source) {
+            counts[i] += 1;
+        }
+    }
+    counts
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &count)| core::iter::repeat_n(i, count))
+        .collect()
+}
+
+/// Resample using one independent draw within each of `n` equal strata.
+pub fn stratified<S>(weights: &[f64], n: usize, source: &mut S) -> Vec<usize>
+where
+    S: Source,
+{
+    let points = (0..n).map(|i| (i as f64 + source.read::<f64>()) / n as f64);
+    select(weights, points)
+}
+
+/// Resample using a single draw shifted across `n` equal strata.
+pub fn systematic<S>(weights: &[f64], n: usize, source: &mut S) -> Vec<usize>
+where
+    S: Source,
+{
+    let offset = source.read::<f64>();
+    let points = (0..n).map(|i| (i as f64 + offset) / n as f64);
+    select(weights, points)
+}
+
+fn select<I>(weights: &[f64], points: I) -> Vec<usize>
+where
+    I: IntoIterator<Item = f64>,
+{
+    should!(!weights.is_empty());
+    let total = sum(weights);
+    let last = weights.len() - 1;
+    let (mut j, mut cumulative) = (0, weights[0] / total);
+    points
+        .into_iter()
+        .map(|point| {
+            while point >= cumulative && j < last {
+                j += 1;
+                cumulative += weights[j] / total;
+            }
+            j
+        })
+        .collect()
+}
+
+#[inline]
+fn sum(weights: &[f64]) -> f64 {
+    weights.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{multinomial, residual, stratified, systematic};
+
+    fn check<F>(resample: F, deviation: f64)
+    where
+        F: Fn(&[f64], usize, &mut source::Default) -> Vec<usize>,
+    {
+        let mut source = source::default(42);
+        let weights = [0.1, 0.0, 0.6, 0.3];
+        let mut counts = [0.0; 4];
+        for _ in 0..1000 {
+            let indices = resample(&weights, 10, &mut source);
+            assert_eq!(indices.len(), 10);
+            assert!(indices.windows(2).all(|pair| pair[0] <= pair[1]));
+            for &i in indices.iter() {
+                counts[i] += 1.0;
+            }
+        }
+        for (count, weight) in counts.iter().zip(weights.iter()) {
+            assert::close(count / 10000.0, *weight, deviation);
+        }
+    }
+
+    #[test]
+    fn schemes() {
+        check(multinomial, 0.02);
+        check(residual, 0.01);
+        check(stratified, 0.01);
+        check(systematic, 0.01);
+    }
+
+    #[test]
+    fn unnormalized() {
+        let mut source = source::default(42);
+        assert_eq!(systematic(&[0.0, 2.0, 0.0], 3, &mut source), [1, 1, 1]);
+        assert_eq!(residual(&[1.0, 3.0], 4, &mut source), [0, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use math;
+use source::Source;
+
+/// A weighted reservoir sampler.
+///
+/// The sampler consumes a stream of weighted items and maintains a sample of
+/// at most `capacity` of them drawn without replacement, where the
+/// probability of an item being drawn is proportional to its weight. Items
+/// with nonpositive weights are never drawn. The memory usage does not depend
+/// on the length of the stream.
+///
+/// ## References
+///
+/// 1. P. S. Efraimidis and P. G. Spirakis, “Weighted random sampling with a
+///    reservoir,” Information Processing Letters, vol. 97, no. 5, pp.
+///    181–185, 2006.
+pub struct WeightedReservoir<T> {
+    capacity: usize,
+    heap: BinaryHeap<Entry<T>>,
+}
+
+struct Entry<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> WeightedReservoir<T> {
+    /// Create a sampler holding at most `capacity` items.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        WeightedReservoir {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity),
+        }
+    }
+
+    /// Consume an item with weight `weight`.
+    pub fn push<S>(&mut self, item: T, weight: f64, source: &mut S)
+    where
+        S: Source,
+    {
+        if self.capacity == 0 || weight.is_nan() || weight <= 0.0 {
+            return;
+        }
+        let key = math::ln(source.read::<f64>()) / weight;
+        if self.heap.len() < self.capacity {
+            self.heap.push(Entry { key, item });
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            if key > smallest.key {
+                *smallest = Entry { key, item };
+            }
+        }
+    }
+
+    /// Return the number of items in the sample.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Check whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Return the sampled items.
+    ///
+    /// The order of the items is unspecified.
+    pub fn into_vec(self) -> Vec<T> {
+        self.heap.into_iter().map(|entry| entry.item).collect()
+    }
+}
+
+impl<T> PartialEq for Entry<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    // The order is reversed so that the heap keeps the smallest key on top.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::WeightedReservoir;
+
+    #[test]
+    fn capacity() {
+        let mut source = source::default(42);
+        let mut reservoir = WeightedReservoir::new(3);
+        assert!(reservoir.is_empty());
+        for i in 0..10 {
+            reservoir.push(i, 1.0, &mut source);
+        }
+        reservoir.push(10, 0.0, &mut source);
+        assert_eq!(reservoir.len(), 3);
+        let mut items = reservoir.into_vec();
+        items.sort();
+        items.dedup();
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(|&i| i < 10));
+    }
+
+    #[test]
+    fn proportional() {
+        let mut source = source::default(42);
+        let mut counts = [0usize; 4];
+        for _ in 0..20000 {
+            let mut reservoir = WeightedReservoir::new(1);
+            for (i, &weight) in [1.0, 2.0, 3.0, 4.0].iter().enumerate() {
+                reservoir.push(i, weight, &mut source);
+            }
+            counts[reservoir.into_vec()[0]] += 1;
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            assert::close(count as f64 / 20000.0, (i + 1) as f64 / 10.0, 0.01);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::vec::Vec;
+
+use distribution::{self, Inverse};
+use source::Source;
+
+/// A sampler drawing from a tabulated inverse distribution function.
+///
+/// The quantiles of the wrapped distribution are precomputed on a uniform
+/// grid of `size` cells over the unit interval, and each sample is obtained
+/// by looking up the cell of a uniformly distributed point and interpolating
+/// linearly between the quantiles at the boundaries of the cell. The first and
+/// last cells, where the quantile function is typically unbounded, are
+/// handled by inverting the distribution function directly. The sampler pays
+/// off when many samples are drawn from the same distribution and its inverse
+/// is expensive to evaluate.
+#[derive(Clone, Debug)]
+pub struct Tabulated<D> {
+    distribution: D,
+    quantiles: Vec<f64>,
+}
+
+impl<D> Tabulated<D>
+where
+    D: Inverse<Value = f64>,
+{
+    /// Create a sampler with a table of `size` cells.
+    ///
+    /// It should hold that `size > 2`.
+    pub fn new(distribution: D, size: usize) -> Self {
+        should!(size > 2);
+        let quantiles = (1..size)
+            .map(|i| distribution.inverse(i as f64 / size as f64))
+            .collect();
+        Tabulated {
+            distribution,
+            quantiles,
+        }
+    }
+
+    /// Return the wrapped distribution.
+    #[inline(always)]
+    pub fn distribution(&self) -> &D {
+        &self.distribution
+    }
+}
+
+impl<D> distribution::Distribution for Tabulated<D>
+where
+    D: Inverse<Value = f64>,
+{
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        self.distribution.distribution(x)
+    }
+}
+
+impl<D> distribution::Sample for Tabulated<D>
+where
+    D: Inverse<Value = f64>,
+{
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        let u = source.read::<f64>();
+        let position = u * (self.quantiles.len() + 1) as f64;
+        let i = position as usize;
+        if i == 0 || i >= self.quantiles.len() {
+            return self.distribution.inverse(u);
+        }
+        let (lower, upper) = (self.quantiles[i - 1], self.quantiles[i]);
+        lower + (upper - lower) * (position - i as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::Tabulated;
+
+    #[test]
+    fn sample() {
+        let d = Gaussian::new(1.0, 2.0);
+        let sampler = Tabulated::new(d, 1 << 12);
+        let mut source = source::default(42);
+        let uniform = Independent(&Uniform::new(0.0, 1.0), &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        let mut source = source::default(42);
+        let samples = Independent(&sampler, &mut source)
+            .take(10000)
+            .collect::<Vec<_>>();
+        for (&u, &x) in uniform.iter().zip(samples.iter()) {
+            if (0.01..0.99).contains(&u) {
+                assert::close(x, d.inverse(u), 1e-4);
+            } else {
+                assert::close(x, d.inverse(u), 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn sample_tails() {
+        let d = Exponential::new(2.0);
+        let sampler = Tabulated::new(d, 3);
+        let mut source = source::default(42);
+        let uniform = Independent(&Uniform::new(0.0, 1.0), &mut source)
+            .take(1000)
+            .collect::<Vec<_>>();
+        let mut source = source::default(42);
+        let samples = Independent(&sampler, &mut source)
+            .take(1000)
+            .collect::<Vec<_>>();
+        for (&u, &x) in uniform.iter().zip(samples.iter()) {
+            if !(1.0 / 3.0..2.0 / 3.0).contains(&u) {
+                assert_eq!(x, d.inverse(u));
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use source::{Source, SplittableSource};
+
+const SIZE: usize = 64;
+
+/// A source reading from another source in blocks.
+///
+/// The wrapped source is asked for a block of values at a time, which
+/// amortizes the overhead of individual calls and allows the block to be
+/// generated in a vectorized manner. The sequence of values is the same as the
+/// one of the wrapped source.
+#[derive(Clone)]
+pub struct Buffered<S> {
+    source: S,
+    buffer: [u64; SIZE],
+    index: usize,
+}
+
+impl<S> Buffered<S> {
+    /// Wrap a source.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        Buffered {
+            source,
+            buffer: [0; SIZE],
+            index: SIZE,
+        }
+    }
+
+    /// Return the wrapped source discarding the buffered values.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S> Buffered<S>
+where
+    S: Source,
+{
+    #[inline(never)]
+    fn refill(&mut self) {
+        for value in self.buffer.iter_mut() {
+            *value = self.source.read_u64();
+        }
+        self.index = 0;
+    }
+}
+
+impl<S> Source for Buffered<S>
+where
+    S: Source,
+{
+    #[inline(always)]
+    fn read_u64(&mut self) -> u64 {
+        if self.index == SIZE {
+            self.refill();
+        }
+        self.index += 1;
+        self.buffer[self.index - 1]
+    }
+}
+
+impl<S> SplittableSource for Buffered<S>
+where
+    S: SplittableSource,
+{
+    #[inline]
+    fn split(&mut self) -> Self {
+        Buffered::new(self.source.split())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use source::{self, Source};
+
+    use super::Buffered;
+
+    #[test]
+    fn read() {
+        let mut expected = source::default(42);
+        let mut source = Buffered::new(source::default(42));
+        for _ in 0..1000 {
+            assert_eq!(source.read_u64(), expected.read_u64());
+        }
+        let mut source = source.into_inner();
+        for _ in 0..24 {
+            expected.read_u64();
+        }
+        assert_eq!(source.read_u64(), expected.read_u64());
+    }
+}
@@ -0,0 +1,144 @@
+use source::{Source, SplittableSource};
+
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+/// An instance of the ChaCha algorithm with `R` rounds.
+///
+/// The algorithm is a stream cipher whose keystream serves as the output. In
+/// contrast to the other sources, the output is unpredictable for those who do
+/// not know the key, provided that the key itself is drawn from a
+/// cryptographically secure source. The variant with 20 rounds is the
+/// standard one, and those with 8 and 12 rounds trade the security margin for
+/// speed. The 64-bit block counter is combined with a 64-bit stream
+/// identifier as in the original design.
+///
+/// ## References
+///
+/// 1. D. J. Bernstein, “ChaCha, a variant of Salsa20,” in Workshop Record of
+///    SASC 2008: The State of the Art of Stream Ciphers, 2008.
+#[derive(Clone)]
+pub struct ChaCha<const R: usize> {
+    input: [u32; 16],
+    output: [u32; 16],
+    index: usize,
+}
+
+/// An instance of the ChaCha algorithm with 8 rounds.
+pub type ChaCha8 = ChaCha<8>;
+
+/// An instance of the ChaCha algorithm with 12 rounds.
+pub type ChaCha12 = ChaCha<12>;
+
+/// An instance of the ChaCha algorithm with 20 rounds.
+pub type ChaCha20 = ChaCha<20>;
+
+impl<const R: usize> ChaCha<R> {
+    /// Create an instance of the algorithm with key `key` on stream `stream`.
+    ///
+    /// The number of rounds should be even.
+    pub fn new(key: [u8; 32], stream: u64) -> Self {
+        should!(R & 1 == 0);
+        let mut input = [0; 16];
+        input[..4].copy_from_slice(&CONSTANTS);
+        for (i, chunk) in key.chunks(4).enumerate() {
+            input[4 + i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        input[14] = stream as u32;
+        input[15] = (stream >> 32) as u32;
+        ChaCha {
+            input,
+            output: [0; 16],
+            index: 16,
+        }
+    }
+
+    fn refill(&mut self) {
+        let mut x = self.input;
+        for _ in 0..(R / 2) {
+            quarter(&mut x, 0, 4, 8, 12);
+            quarter(&mut x, 1, 5, 9, 13);
+            quarter(&mut x, 2, 6, 10, 14);
+            quarter(&mut x, 3, 7, 11, 15);
+            quarter(&mut x, 0, 5, 10, 15);
+            quarter(&mut x, 1, 6, 11, 12);
+            quarter(&mut x, 2, 7, 8, 13);
+            quarter(&mut x, 3, 4, 9, 14);
+        }
+        for (output, (&x, &input)) in self.output.iter_mut().zip(x.iter().zip(self.input.iter())) {
+            *output = x.wrapping_add(input);
+        }
+        let (counter, carry) = self.input[12].overflowing_add(1);
+        self.input[12] = counter;
+        if carry {
+            self.input[13] = self.input[13].wrapping_add(1);
+        }
+        self.index = 0;
+    }
+}
+
+impl<const R: usize> Source for ChaCha<R> {
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        if self.index >= 16 {
+            self.refill();
+        }
+        let value = self.output[self.index] as u64 | (self.output[self.index + 1] as u64) << 32;
+        self.index += 2;
+        value
+    }
+}
+
+impl<const R: usize> SplittableSource for ChaCha<R> {
+    fn split(&mut self) -> Self {
+        let mut key = [0; 32];
+        for chunk in key.chunks_mut(8) {
+            chunk.copy_from_slice(&self.read_u64().to_le_bytes());
+        }
+        ChaCha::new(key, self.read_u64())
+    }
+}
+
+#[inline(always)]
+fn quarter(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+#[cfg(test)]
+mod tests {
+    use source::Source;
+
+    use super::{ChaCha12, ChaCha20, ChaCha8};
+
+    #[test]
+    fn reference() {
+        let mut source = ChaCha20::new([0; 32], 0);
+        let expected = [
+            0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86,
+            0xbd, 0x28, 0xbd, 0xd2, 0x19, 0xb8, 0xa0, 0x8d, 0xed, 0x1a, 0xa8, 0x36, 0xef, 0xcc,
+            0x8b, 0x77, 0x0d, 0xc7, 0xda, 0x41, 0x59, 0x7c, 0x51, 0x57, 0x48, 0x8d, 0x77, 0x24,
+            0xe0, 0x3f, 0xb8, 0xd8, 0x4a, 0x37, 0x6a, 0x43, 0xb8, 0xf4, 0x15, 0x18, 0xa1, 0x1c,
+            0xc3, 0x87, 0xb6, 0x69, 0xb2, 0xee, 0x65, 0x86,
+        ];
+        for chunk in expected.chunks(8) {
+            assert_eq!(&source.read_u64().to_le_bytes()[..], chunk);
+        }
+    }
+
+    #[test]
+    fn variants() {
+        let key = [7; 32];
+        let mut one = ChaCha8::new(key, 0);
+        let mut other = ChaCha12::new(key, 0);
+        assert!((0..10).any(|_| one.read_u64() != other.read_u64()));
+        let mut one = ChaCha20::new(key, 1);
+        let mut other = ChaCha20::new(key, 2);
+        assert!((0..10).any(|_| one.read_u64() != other.read_u64()));
+    }
+}
@@ -0,0 +1,141 @@
+use source::{Source, SplittableSource};
+
+/// A source reusing the unused bits of each value read from another source.
+///
+/// The methods `read_u32`, `read_f32`, and `fill_bytes` take as many bits as
+/// they need from a reservoir holding the rest of the last `u64` read, starting
+/// from the most significant ones, and read a new value only when the reservoir
+/// runs short. Hence, two `u32` are obtained from each `u64`, and no bytes are
+/// discarded between calls to `fill_bytes`, which yields the bytes of each
+/// `u64` in big-endian order. Reading `u64` bypasses the reservoir.
+#[derive(Clone)]
+pub struct Chunked<S> {
+    source: S,
+    word: u64,
+    count: u32,
+}
+
+impl<S> Chunked<S> {
+    /// Wrap a source.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        Chunked {
+            source,
+            word: 0,
+            count: 0,
+        }
+    }
+
+    /// Return the wrapped source discarding the unused bits.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
+
+impl<S> Chunked<S>
+where
+    S: Source,
+{
+    /// Read `u32` uniformly distributed over `{0, 1, …, u32::MAX}`.
+    #[inline]
+    pub fn read_u32(&mut self) -> u32 {
+        self.take(32) as u32
+    }
+
+    /// Read `f32` uniformly distributed over `[0, 1)`.
+    ///
+    /// The result has the full 24-bit precision of the type.
+    #[inline]
+    pub fn read_f32(&mut self) -> f32 {
+        self.take(24) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Fill a buffer with random bytes.
+    pub fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        let mut index = 0;
+        while index < buffer.len() && self.count >= 8 {
+            buffer[index] = self.take(8) as u8;
+            index += 1;
+        }
+        let mut chunks = buffer[index..].chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.source.read_u64().to_be_bytes());
+        }
+        for value in chunks.into_remainder() {
+            *value = self.take(8) as u8;
+        }
+    }
+
+    /// Take the `count` most significant unused bits.
+    #[inline]
+    fn take(&mut self, count: u32) -> u64 {
+        if self.count < count {
+            self.word = self.source.read_u64();
+            self.count = 64;
+        }
+        let value = self.word >> (64 - count);
+        self.word <<= count;
+        self.count -= count;
+        value
+    }
+}
+
+impl<S> Source for Chunked<S>
+where
+    S: Source,
+{
+    #[inline(always)]
+    fn read_u64(&mut self) -> u64 {
+        self.source.read_u64()
+    }
+}
+
+impl<S> SplittableSource for Chunked<S>
+where
+    S: SplittableSource,
+{
+    #[inline]
+    fn split(&mut self) -> Self {
+        Chunked::new(self.source.split())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use source::{self, Source};
+
+    use super::Chunked;
+
+    #[test]
+    fn fill_bytes() {
+        let mut expected = source::default(42);
+        let mut source = Chunked::new(source::default(42));
+        let mut bytes = [0; 3];
+        source.fill_bytes(&mut bytes);
+        let first = expected.read_u64().to_be_bytes();
+        assert_eq!(bytes, first[..3]);
+        let mut bytes = [0; 14];
+        source.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..5], first[3..]);
+        assert_eq!(bytes[5..13], expected.read_u64().to_be_bytes());
+        assert_eq!(bytes[13], expected.read_u64().to_be_bytes()[0]);
+    }
+
+    #[test]
+    fn read() {
+        let mut expected = source::default(42);
+        let mut source = Chunked::new(source::default(42));
+        let value = expected.read_u64();
+        assert_eq!(source.read_u32(), (value >> 32) as u32);
+        assert_eq!(source.read_u32(), value as u32);
+        let value = expected.read_u64();
+        let x = source.read_f32();
+        assert!((0.0..1.0).contains(&x));
+        assert_eq!(x, (value >> 40) as f32 / 16777216.0);
+        let x = ((value >> 16) & 0xffffff) as f32 / 16777216.0;
+        assert_eq!(source.read_f32(), x);
+        assert_eq!(source.read_u64(), expected.read_u64());
+        assert_eq!(source.read_u32(), (expected.read_u64() >> 32) as u32);
+    }
+}
@@ -0,0 +1,81 @@
+use core::cell::RefCell;
+use core::hash::{BuildHasher, Hasher};
+use core::marker::PhantomData;
+use std::collections::hash_map::RandomState;
+
+use source::{self, Default, Source};
+
+std::thread_local! {
+    static SOURCE: RefCell<Default> = RefCell::new(source::default(seed()));
+}
+
+/// A handle to the default source of the current thread.
+///
+/// The handle is created by `thread_local`. Each thread has its own instance
+/// of the default source, which is initially seeded nondeterministically and
+/// can be reseeded for reproducibility.
+#[derive(Clone, Debug)]
+pub struct ThreadLocal {
+    phantom: PhantomData<*const ()>,
+}
+
+/// Return a handle to the default source of the current thread.
+#[inline]
+pub fn thread_local() -> ThreadLocal {
+    ThreadLocal {
+        phantom: PhantomData,
+    }
+}
+
+impl ThreadLocal {
+    /// Reseed the source of the current thread.
+    pub fn reseed(&self, seed: u64) {
+        SOURCE.with(|source| *source.borrow_mut() = source::default(seed));
+    }
+}
+
+impl Source for ThreadLocal {
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        SOURCE.with(|source| source.borrow_mut().read_u64())
+    }
+}
+
+fn seed() -> u64 {
+    #[cfg(feature = "getrandom")]
+    if let Ok(seed) = source::seed() {
+        return seed;
+    }
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use std::thread;
+
+    use source::{self, Source};
+
+    use super::thread_local;
+
+    #[test]
+    fn reseed() {
+        let mut expected = source::default(42);
+        let mut source = thread_local();
+        source.reseed(42);
+        assert_eq!(source.read_u64(), expected.read_u64());
+        assert_eq!(thread_local().read_u64(), expected.read_u64());
+
+        let values = thread::spawn(|| {
+            let mut source = thread_local();
+            source.reseed(42);
+            (0..2).map(|_| source.read_u64()).collect::<Vec<_>>()
+        })
+        .join()
+        .unwrap();
+        let mut expected = source::default(42);
+        assert_eq!(values, [expected.read_u64(), expected.read_u64()]);
+    }
+}
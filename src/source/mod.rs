@@ -1,3 +1,191 @@
 //! Sources of randomness.
 
 pub use random::*;
+
+mod buffered;
+mod chacha;
+mod chunked;
+#[cfg(feature = "std")]
+mod local;
+mod mersenne;
+mod pcg;
+mod philox;
+#[cfg(feature = "rand")]
+mod rand;
+mod replay;
+
+pub use self::buffered::Buffered;
+pub use self::chacha::{ChaCha, ChaCha12, ChaCha20, ChaCha8};
+pub use self::chunked::Chunked;
+#[cfg(feature = "std")]
+pub use self::local::{thread_local, ThreadLocal};
+pub use self::mersenne::MersenneTwister64;
+pub use self::pcg::Pcg64;
+pub use self::philox::Philox4x64;
+#[cfg(feature = "rand")]
+pub use self::rand::{RngSource, SourceRng};
+pub use self::replay::{Recorder, Replay};
+
+/// Create an instance of the default source seeded from the entropy of the
+/// operating system.
+///
+/// The source is not reproducible across runs. An error is returned if the
+/// operating system fails to provide entropy.
+#[cfg(feature = "getrandom")]
+pub fn entropy() -> Result<Default, getrandom::Error> {
+    let mut bytes = [0; 16];
+    loop {
+        getrandom::fill(&mut bytes)?;
+        let mut seed = [0; 2];
+        for (seed, chunk) in seed.iter_mut().zip(bytes.chunks(8)) {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            *seed = u64::from_le_bytes(word);
+        }
+        if seed != [0, 0] {
+            return Ok(Xorshift128Plus::new(seed));
+        }
+    }
+}
+
+/// Obtain a seed from the entropy of the operating system.
+///
+/// The function does not rely on the standard library and works on every
+/// target supported by `getrandom`, including `wasm32-unknown-unknown` when the
+/// `wasm` feature is enabled. The seed can be passed to the constructor of any
+/// source.
+#[cfg(feature = "getrandom")]
+#[inline]
+pub fn seed() -> Result<u64, getrandom::Error> {
+    getrandom::u64()
+}
+
+/// Additional ways of reading from a source.
+///
+/// The methods are available for every source and are stateless: each call
+/// reads whole values of `u64`, and the bits not needed for the result are
+/// discarded. Wrap the source in `Chunked` to keep them for subsequent calls.
+pub trait SourceExt: Source {
+    /// Read `u32` uniformly distributed over `{0, 1, …, u32::MAX}`.
+    ///
+    /// The result is taken from the upper half of a `u64`, which has better
+    /// statistical properties for generators such as Xorshift128+.
+    #[inline(always)]
+    fn read_u32(&mut self) -> u32 {
+        (self.read_u64() >> 32) as u32
+    }
+
+    /// Read `f32` uniformly distributed over `[0, 1)`.
+    ///
+    /// The result has the full 24-bit precision of the type.
+    #[inline(always)]
+    fn read_f32(&mut self) -> f32 {
+        (self.read_u64() >> 40) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Fill a buffer with random bytes.
+    ///
+    /// Each `u64` read provides eight bytes in little-endian order, and only
+    /// the bytes of the last one might be left unused.
+    fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        let mut chunks = buffer.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.read_u64().to_le_bytes());
+        }
+        let remainder = chunks.into_remainder();
+        if !remainder.is_empty() {
+            let bytes = self.read_u64().to_le_bytes();
+            remainder.copy_from_slice(&bytes[..remainder.len()]);
+        }
+    }
+}
+
+impl<S: Source + ?Sized> SourceExt for S {}
+
+/// A source capable of deriving child sources.
+///
+/// The children are seeded from the output of the parent, which advances the
+/// parent. Hence, a given parent deterministically produces the same sequence
+/// of children, and the children are statistically independent of each other
+/// and of the parent for practical purposes.
+pub trait SplittableSource: Source + Sized {
+    /// Derive a child source.
+    fn split(&mut self) -> Self;
+}
+
+impl SplittableSource for Xorshift128Plus {
+    fn split(&mut self) -> Self {
+        let seed = [mix(self.read_u64()), mix(self.read_u64())];
+        if seed == [0, 0] {
+            return Xorshift128Plus::new([1, 0]);
+        }
+        Xorshift128Plus::new(seed)
+    }
+}
+
+/// Scramble a number via the finalizer of the SplitMix64 algorithm.
+#[inline]
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{
+        ChaCha20, MersenneTwister64, Pcg64, Philox4x64, Source, SourceExt, SplittableSource,
+    };
+
+    fn check<S: SplittableSource + Clone>(mut parent: S) {
+        let mut copy = parent.clone();
+        let mut one = parent.split();
+        let mut other = parent.split();
+        let first = (0..4).map(|_| one.read_u64()).collect::<Vec<_>>();
+        let second = (0..4).map(|_| other.read_u64()).collect::<Vec<_>>();
+        let third = (0..4).map(|_| parent.read_u64()).collect::<Vec<_>>();
+        assert!(first != second && first != third && second != third);
+        let mut again = copy.split();
+        assert_eq!((0..4).map(|_| again.read_u64()).collect::<Vec<_>>(), first);
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn entropy() {
+        let mut one = super::entropy().unwrap();
+        let mut other = super::entropy().unwrap();
+        assert!(one.read_u64() != other.read_u64());
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn seed() {
+        assert!(super::seed().unwrap() != super::seed().unwrap());
+    }
+
+    #[test]
+    fn read() {
+        let mut expected = super::default(42);
+        let mut source = super::default(42);
+        assert_eq!(source.read_u32(), (expected.read_u64() >> 32) as u32);
+        let x = source.read_f32();
+        assert!((0.0..1.0).contains(&x));
+        assert_eq!(x, (expected.read_u64() >> 40) as f32 / 16777216.0);
+        let mut bytes = [0; 11];
+        source.fill_bytes(&mut bytes);
+        assert_eq!(&bytes[..8], &expected.read_u64().to_le_bytes()[..]);
+        assert_eq!(&bytes[8..], &expected.read_u64().to_le_bytes()[..3]);
+        assert_eq!(source.read_u64(), expected.read_u64());
+    }
+
+    #[test]
+    fn split() {
+        check(super::default(42));
+        check(ChaCha20::new([42; 32], 0));
+        check(MersenneTwister64::new(42));
+        check(Pcg64::new(42, 0));
+        check(Philox4x64::new([42, 0]));
+    }
+}
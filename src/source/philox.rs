@@ -0,0 +1,156 @@
+use source::{Source, SplittableSource};
+
+const MULTIPLIERS: [u64; 2] = [0xd2e7470ee14c6c93, 0xca5a826395121157];
+const WEYL: [u64; 2] = [0x9e3779b97f4a7c15, 0xbb67ae8584caa73b];
+const ROUNDS: usize = 10;
+
+/// An instance of the Philox4x64 algorithm.
+///
+/// The algorithm is counter-based: each block of four outputs is a pure
+/// function of a 128-bit key and a 256-bit counter, which is incremented after
+/// each block. Hence, any position in the output can be reached directly by
+/// setting the counter, and parallel workers can share a key and process
+/// disjoint ranges of counters without communicating.
+///
+/// ## References
+///
+/// 1. J. K. Salmon, M. A. Moraes, R. O. Dror, and D. E. Shaw, “Parallel random
+///    numbers: As easy as 1, 2, 3,” in Proceedings of the International
+///    Conference for High Performance Computing, Networking, Storage and
+///    Analysis, 2011.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Philox4x64 {
+    key: [u64; 2],
+    counter: [u64; 4],
+    output: [u64; 4],
+    index: usize,
+}
+
+impl Philox4x64 {
+    /// Create an instance of the algorithm with key `key` and the counter set
+    /// to zero.
+    #[inline]
+    pub fn new(key: [u64; 2]) -> Philox4x64 {
+        Philox4x64::with_counter(key, [0; 4])
+    }
+
+    /// Create an instance of the algorithm with key `key` and counter
+    /// `counter`.
+    #[inline]
+    pub fn with_counter(key: [u64; 2], counter: [u64; 4]) -> Philox4x64 {
+        Philox4x64 {
+            key,
+            counter,
+            output: [0; 4],
+            index: 4,
+        }
+    }
+
+    /// Return the counter of the next block.
+    #[inline(always)]
+    pub fn counter(&self) -> [u64; 4] {
+        self.counter
+    }
+
+    /// Set the counter of the next block.
+    ///
+    /// The outputs remaining from the current block are discarded.
+    #[inline]
+    pub fn set_counter(&mut self, counter: [u64; 4]) {
+        self.counter = counter;
+        self.index = 4;
+    }
+
+    /// Compute the block of outputs for key `key` and counter `counter`.
+    pub fn block(key: [u64; 2], counter: [u64; 4]) -> [u64; 4] {
+        let (mut key, mut x) = (key, counter);
+        for round in 0..ROUNDS {
+            if round > 0 {
+                key[0] = key[0].wrapping_add(WEYL[0]);
+                key[1] = key[1].wrapping_add(WEYL[1]);
+            }
+            let (high0, low0) = multiply(MULTIPLIERS[0], x[0]);
+            let (high1, low1) = multiply(MULTIPLIERS[1], x[2]);
+            x = [high1 ^ x[1] ^ key[0], low1, high0 ^ x[3] ^ key[1], low0];
+        }
+        x
+    }
+}
+
+impl Source for Philox4x64 {
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        if self.index >= 4 {
+            self.output = Philox4x64::block(self.key, self.counter);
+            for word in self.counter.iter_mut() {
+                *word = word.wrapping_add(1);
+                if *word != 0 {
+                    break;
+                }
+            }
+            self.index = 0;
+        }
+        self.index += 1;
+        self.output[self.index - 1]
+    }
+}
+
+impl SplittableSource for Philox4x64 {
+    fn split(&mut self) -> Self {
+        Philox4x64::new([self.read_u64(), self.read_u64()])
+    }
+}
+
+#[inline(always)]
+fn multiply(a: u64, b: u64) -> (u64, u64) {
+    let product = a as u128 * b as u128;
+    ((product >> 64) as u64, product as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use source::Source;
+
+    use super::Philox4x64;
+
+    #[test]
+    fn reference() {
+        assert_eq!(
+            Philox4x64::block([0; 2], [0; 4]),
+            [
+                0x16554d9eca36314c,
+                0xdb20fe9d672d0fdc,
+                0xd7e772cee186176b,
+                0x7e68b68aec7ba23b,
+            ]
+        );
+        assert_eq!(
+            Philox4x64::block([!0; 2], [!0; 4]),
+            [
+                0x87b092c3013fe90b,
+                0x438c3c67be8d0224,
+                0x9cc7d7c69cd777b6,
+                0xa09caebf594f0ba0,
+            ]
+        );
+    }
+
+    #[test]
+    fn counter() {
+        let key = [42, 69];
+        let mut source = Philox4x64::new(key);
+        let values = (0..12).map(|_| source.read_u64()).collect::<Vec<_>>();
+        assert_eq!(source.counter(), [3, 0, 0, 0]);
+        assert_eq!(&values[8..], &Philox4x64::block(key, [2, 0, 0, 0])[..]);
+
+        let mut source = Philox4x64::with_counter(key, [1, 0, 0, 0]);
+        assert_eq!(source.read_u64(), values[4]);
+        source.set_counter([0; 4]);
+        assert_eq!(source.read_u64(), values[0]);
+
+        let mut source = Philox4x64::with_counter(key, [!0, 0, 0, 0]);
+        source.read_u64();
+        assert_eq!(source.counter(), [0, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,64 @@
+use rand_core::{impls, RngCore};
+
+use source::Source;
+
+/// A source backed by a generator implementing `rand_core::RngCore`.
+#[derive(Clone, Debug)]
+pub struct RngSource<R>(pub R);
+
+/// A generator implementing `rand_core::RngCore` backed by a source.
+#[derive(Clone, Debug)]
+pub struct SourceRng<S>(pub S);
+
+impl<R> Source for RngSource<R>
+where
+    R: RngCore,
+{
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+impl<S> RngCore for SourceRng<S>
+where
+    S: Source,
+{
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.0.read_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.read_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        impls::fill_bytes_via_next(self, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::RngCore;
+
+    use source::{Pcg64, Source};
+
+    use super::{RngSource, SourceRng};
+
+    #[test]
+    fn round_trip() {
+        let mut expected = Pcg64::new(42, 0);
+        let mut rng = SourceRng(Pcg64::new(42, 0));
+        assert_eq!(rng.next_u64(), expected.read_u64());
+        assert_eq!(rng.next_u32(), (expected.read_u64() >> 32) as u32);
+        let mut bytes = [0; 12];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(&bytes[..8], &expected.read_u64().to_le_bytes()[..]);
+        let mut source = RngSource(rng);
+        expected.read_u64();
+        assert_eq!(source.read_u64(), expected.read_u64());
+    }
+}
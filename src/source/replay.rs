@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+
+use source::Source;
+
+/// A source recording the values read from another source.
+///
+/// The recorded values can be played back via `Replay`, which makes it
+/// possible to reproduce a stochastic computation exactly without knowing the
+/// source or seed that was originally used.
+#[derive(Clone, Debug)]
+pub struct Recorder<S> {
+    source: S,
+    tape: Vec<u64>,
+}
+
+/// A source playing back previously recorded values.
+///
+/// The source panics when read beyond the end of the tape.
+#[derive(Clone, Debug)]
+pub struct Replay {
+    tape: Vec<u64>,
+    position: usize,
+}
+
+impl<S> Recorder<S> {
+    /// Wrap a source.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        Recorder {
+            source,
+            tape: Vec::new(),
+        }
+    }
+
+    /// Return the values recorded so far.
+    #[inline]
+    pub fn tape(&self) -> &[u64] {
+        &self.tape
+    }
+
+    /// Return the wrapped source and the recorded values.
+    #[inline]
+    pub fn into_inner(self) -> (S, Vec<u64>) {
+        (self.source, self.tape)
+    }
+}
+
+impl<S> Source for Recorder<S>
+where
+    S: Source,
+{
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        let value = self.source.read_u64();
+        self.tape.push(value);
+        value
+    }
+}
+
+impl Replay {
+    /// Create a source playing back a tape.
+    #[inline]
+    pub fn new(tape: Vec<u64>) -> Self {
+        Replay { tape, position: 0 }
+    }
+
+    /// Return the number of values that are yet to be played back.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.tape.len() - self.position
+    }
+
+    /// Start playing back from the beginning of the tape.
+    #[inline]
+    pub fn rewind(&mut self) {
+        self.position = 0;
+    }
+}
+
+impl<S> From<Recorder<S>> for Replay {
+    #[inline]
+    fn from(recorder: Recorder<S>) -> Self {
+        Replay::new(recorder.tape)
+    }
+}
+
+impl Source for Replay {
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        let value = match self.tape.get(self.position) {
+            Some(&value) => value,
+            _ => panic!("the tape has been exhausted"),
+        };
+        self.position += 1;
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use prelude::*;
+    use source::Source;
+
+    use super::{Recorder, Replay};
+
+    #[test]
+    fn replay() {
+        let distribution = Gaussian::new(1.0, 2.0);
+        let mut recorder = Recorder::new(source::default(42));
+        let expected = Independent(&distribution, &mut recorder)
+            .take(100)
+            .collect::<Vec<_>>();
+        let mut replay = Replay::from(recorder);
+        let actual = Independent(&distribution, &mut replay)
+            .take(100)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        assert_eq!(replay.remaining(), 0);
+        replay.rewind();
+        assert_eq!(distribution.sample(&mut replay), expected[0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn exhausted() {
+        let mut replay = Replay::new(vec![42]);
+        assert_eq!(replay.read_u64(), 42);
+        replay.read_u64();
+    }
+}
@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Continuous, Distribution, Exponential, Gaussian};
+use estimate;
+use math;
+use test::Outcome;
+
+const LEVELS: [f64; 5] = [0.15, 0.1, 0.05, 0.025, 0.01];
+
+/// The outcome of the Anderson–Darling test with estimated parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AndersonDarling {
+    /// The test statistic modified for the sample size.
+    pub statistic: f64,
+    /// The critical values of the modified statistic as pairs of significance
+    /// levels and values.
+    pub critical: [(f64, f64); 5],
+}
+
+impl AndersonDarling {
+    /// Indicate whether the null hypothesis is rejected at a significance
+    /// level.
+    ///
+    /// If the level is not among the tabulated ones, `None` is returned.
+    pub fn rejects(&self, level: f64) -> Option<bool> {
+        self.critical
+            .iter()
+            .find(|&&(alpha, _)| alpha == level)
+            .map(|&(_, value)| self.statistic > value)
+    }
+}
+
+/// Perform the Anderson–Darling test against a fully specified distribution.
+///
+/// The null hypothesis is that the sample is drawn from `distribution`. The
+/// statistic puts more weight on the tails than the one of the
+/// Kolmogorov–Smirnov test. The p-value is computed from the asymptotic
+/// distribution of the statistic. The sample should not be empty.
+///
+/// ## References
+///
+/// 1. G. Marsaglia and J. Marsaglia, “Evaluating the Anderson–Darling
+///    distribution,” Journal of Statistical Software, 2004.
+pub fn anderson_darling<D>(data: &[f64], distribution: &D) -> Outcome
+where
+    D: Continuous,
+{
+    should!(!data.is_empty());
+    let statistic = statistic(data, |x| distribution.distribution(x));
+    Outcome {
+        statistic,
+        p: 1.0 - asymptotic(statistic),
+    }
+}
+
+/// Perform the Anderson–Darling test for normality with the mean and the
+/// standard deviation estimated from the sample.
+///
+/// The sample should contain at least two distinct values.
+///
+/// ## References
+///
+/// 1. M. A. Stephens, “EDF statistics for goodness of fit and some
+///    comparisons,” Journal of the American Statistical Association, 1974.
+pub fn anderson_darling_gaussian(data: &[f64]) -> AndersonDarling {
+    should!(data.len() > 1);
+    let n = data.len() as f64;
+    let mu = estimate::mean(data);
+    let sigma = (estimate::variance(data, mu) * n / (n - 1.0)).sqrt();
+    let gaussian = Gaussian::new(mu, sigma);
+    let statistic = statistic(data, |x| gaussian.distribution(x));
+    composite(
+        statistic * (1.0 + 0.75 / n + 2.25 / (n * n)),
+        [0.576, 0.656, 0.787, 0.918, 1.092],
+    )
+}
+
+/// Perform the Anderson–Darling test for exponentiality with the rate
+/// estimated from the sample.
+///
+/// The values should be positive.
+pub fn anderson_darling_exponential(data: &[f64]) -> AndersonDarling {
+    should!(!data.is_empty());
+    let n = data.len() as f64;
+    let exponential = Exponential::new(1.0 / estimate::mean(data));
+    let statistic = statistic(data, |x| exponential.distribution(x));
+    composite(
+        statistic * (1.0 + 0.6 / n),
+        [0.922, 1.078, 1.341, 1.606, 1.957],
+    )
+}
+
+fn composite(statistic: f64, values: [f64; 5]) -> AndersonDarling {
+    let mut critical = [(0.0, 0.0); 5];
+    for (critical, (&level, &value)) in critical.iter_mut().zip(LEVELS.iter().zip(&values)) {
+        *critical = (level, value);
+    }
+    AndersonDarling {
+        statistic,
+        critical,
+    }
+}
+
+fn statistic<F>(data: &[f64], distribution: F) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let mut p = data.iter().map(|&x| distribution(x)).collect::<Vec<_>>();
+    p.sort_by(|one, other| one.total_cmp(other));
+    let n = p.len();
+    let sum = (0..n).fold(0.0, |sum, i| {
+        sum + (2 * i + 1) as f64 * (math::ln(p[i]) + math::ln_1p(-p[n - 1 - i]))
+    });
+    -(n as f64) - sum / n as f64
+}
+
+/// Compute the asymptotic distribution function of the statistic.
+fn asymptotic(z: f64) -> f64 {
+    if z <= 0.0 {
+        return 0.0;
+    }
+    if z < 2.0 {
+        math::exp(-1.2337141 / z) / z.sqrt()
+            * (2.00012
+                + (0.247105 - (0.0649821 - (0.0347962 - (0.011672 - 0.00168691 * z) * z) * z) * z)
+                    * z)
+    } else {
+        math::exp(-math::exp(
+            1.0776
+                - (2.30695 - (0.43424 - (0.082433 - (0.008056 - 0.0003146 * z) * z) * z) * z) * z,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{anderson_darling, anderson_darling_exponential, anderson_darling_gaussian};
+
+    #[test]
+    fn specified() {
+        let data = [0.1, 0.4, 0.7];
+        let outcome = anderson_darling(&data, &Uniform::new(0.0, 1.0));
+        let expected = -3.0
+            - (0.1f64.ln()
+                + 0.3f64.ln()
+                + 3.0 * (0.4f64.ln() + 0.6f64.ln())
+                + 5.0 * (0.7f64.ln() + 0.9f64.ln()))
+                / 3.0;
+        assert::close(outcome.statistic, expected, 1e-14);
+
+        let mut source = source::default(42);
+        let data = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(1000)
+            .collect::<Vec<_>>();
+        assert!(anderson_darling(&data, &Gaussian::new(0.0, 1.0)).p > 0.05);
+        assert!(anderson_darling(&data, &Gaussian::new(0.0, 1.3)).p < 1e-3);
+    }
+
+    #[test]
+    fn asymptotic() {
+        use super::asymptotic;
+        assert::close(1.0 - asymptotic(1.933), 0.1, 1e-4);
+        assert::close(1.0 - asymptotic(2.492), 0.05, 1e-4);
+        assert::close(1.0 - asymptotic(3.857), 0.01, 1e-3);
+    }
+
+    #[test]
+    fn composite() {
+        let mut source = source::default(42);
+        let gaussian = Independent(&Gaussian::new(5.0, 2.0), &mut source)
+            .take(500)
+            .collect::<Vec<_>>();
+        let exponential = Independent(&Exponential::new(2.0), &mut source)
+            .take(500)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            anderson_darling_gaussian(&gaussian).rejects(0.05),
+            Some(false)
+        );
+        assert_eq!(
+            anderson_darling_gaussian(&exponential).rejects(0.01),
+            Some(true)
+        );
+        assert_eq!(
+            anderson_darling_exponential(&exponential).rejects(0.05),
+            Some(false)
+        );
+        assert_eq!(
+            anderson_darling_exponential(&gaussian).rejects(0.01),
+            Some(true)
+        );
+        assert_eq!(anderson_darling_gaussian(&gaussian).rejects(0.2), None);
+    }
+}
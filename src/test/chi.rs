@@ -0,0 +1,195 @@
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Discrete, Gamma, Survival};
+use test::Outcome;
+
+const MINIMUM: f64 = 5.0;
+
+/// The outcome of the chi-squared test of independence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Independence {
+    /// The test statistic.
+    pub statistic: f64,
+    /// The number of degrees of freedom.
+    pub degrees: usize,
+    /// The p-value.
+    pub p: f64,
+    /// Cramér’s V, which measures the strength of the association in `[0, 1]`.
+    pub cramer: f64,
+}
+
+/// Perform Pearson’s chi-squared goodness-of-fit test.
+///
+/// The null hypothesis is that the counts of outcomes `0, 1, …` are drawn
+/// from `distribution`. The last count covers the whole upper tail so that
+/// the probabilities of the bins sum up to one. Adjacent bins are merged
+/// until each expected count is at least five, and the p-value is computed
+/// from the chi-squared distribution with one degree of freedom less than the
+/// number of merged bins. The counts should contain at least two bins and a
+/// positive total. The outcomes that the type of the values of the
+/// distribution cannot represent are assigned zero probability.
+pub fn chi_square_gof<D>(observed: &[usize], distribution: &D) -> Outcome
+where
+    D: Discrete,
+    D::Value: TryFrom<usize>,
+{
+    should!(observed.len() > 1);
+    let total = observed.iter().sum::<usize>() as f64;
+    should!(total > 0.0);
+    let k = observed.len();
+    let mut probabilities = (0..(k - 1))
+        .map(|x| D::Value::try_from(x).map_or(0.0, |x| distribution.mass(x)))
+        .collect::<Vec<_>>();
+    let head = probabilities.iter().sum::<f64>();
+    probabilities.push((1.0 - head).max(0.0));
+
+    let mut bins = Vec::<(f64, f64)>::new();
+    let (mut count, mut expected) = (0.0, 0.0);
+    for (&x, &p) in observed.iter().zip(&probabilities) {
+        count += x as f64;
+        expected += p * total;
+        if expected >= MINIMUM {
+            bins.push((count, expected));
+            count = 0.0;
+            expected = 0.0;
+        }
+    }
+    if expected > 0.0 || count > 0.0 {
+        match bins.last_mut() {
+            Some(last) => {
+                last.0 += count;
+                last.1 += expected;
+            }
+            _ => bins.push((count, expected)),
+        }
+    }
+    let statistic = bins.iter().fold(0.0, |sum, &(count, expected)| {
+        sum + (count - expected) * (count - expected) / expected
+    });
+    let p = if bins.len() > 1 {
+        let degrees = (bins.len() - 1) as f64;
+        Gamma::new(degrees / 2.0, 2.0).survival(statistic)
+    } else {
+        1.0
+    };
+    Outcome { statistic, p }
+}
+
+/// Perform Pearson’s chi-squared test of independence.
+///
+/// The contingency table is stored by rows with `columns` columns, and the
+/// null hypothesis is that the row and column classifications are
+/// independent. Rows and columns without counts are disregarded. The table
+/// should contain at least two rows and two columns with counts.
+pub fn chi_square_independence(table: &[usize], columns: usize) -> Independence {
+    should!(columns > 0 && table.len() % columns == 0);
+    let rows = table.len() / columns;
+    let mut row_sums = vec![0.0; rows];
+    let mut column_sums = vec![0.0; columns];
+    for i in 0..rows {
+        for j in 0..columns {
+            let count = table[i * columns + j] as f64;
+            row_sums[i] += count;
+            column_sums[j] += count;
+        }
+    }
+    let total = row_sums.iter().sum::<f64>();
+    let mut statistic = 0.0;
+    for i in 0..rows {
+        for j in 0..columns {
+            let expected = row_sums[i] * column_sums[j] / total;
+            if expected > 0.0 {
+                let difference = table[i * columns + j] as f64 - expected;
+                statistic += difference * difference / expected;
+            }
+        }
+    }
+    let rows = row_sums.iter().filter(|&&sum| sum > 0.0).count();
+    let columns = column_sums.iter().filter(|&&sum| sum > 0.0).count();
+    should!(rows > 1 && columns > 1);
+    let degrees = (rows - 1) * (columns - 1);
+    Independence {
+        statistic,
+        degrees,
+        p: Gamma::new(degrees as f64 / 2.0, 2.0).survival(statistic),
+        cramer: (statistic / (total * (rows.min(columns) - 1) as f64)).sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use assert;
+    use prelude::*;
+
+    use super::{chi_square_gof, chi_square_independence};
+
+    #[test]
+    fn binomial() {
+        let mut source = source::default(42);
+        let d = Binomial::new(10, 0.3);
+        let mut observed = vec![0; 11];
+        for x in Independent(&d, &mut source).take(1000) {
+            observed[x as usize] += 1;
+        }
+        assert!(chi_square_gof(&observed, &d).p > 0.05);
+        assert!(chi_square_gof(&observed, &Binomial::new(10, 0.4)).p < 1e-3);
+
+        let d = Bernoulli::new(0.5);
+        let outcome = chi_square_gof(&[40, 60, 0], &d);
+        assert::close(outcome.statistic, 4.0, 1e-12);
+    }
+
+    #[test]
+    fn categorical() {
+        let d = Categorical::new(&[0.25, 0.25, 0.5]);
+        let outcome = chi_square_gof(&[30, 20, 50], &d);
+        assert::close(outcome.statistic, 2.0, 1e-14);
+        assert::close(outcome.p, (-1.0f64).exp(), 1e-12);
+    }
+
+    #[test]
+    fn independence() {
+        let outcome = chi_square_independence(&[10, 20, 30, 40], 2);
+        let expected = [(10.0, 12.0), (20.0, 18.0), (30.0, 28.0), (40.0, 42.0)];
+        let statistic = expected
+            .iter()
+            .fold(0.0, |sum, &(o, e): &(f64, f64)| sum + (o - e).powi(2) / e);
+        assert::close(outcome.statistic, statistic, 1e-12);
+        assert_eq!(outcome.degrees, 1);
+        assert::close(outcome.cramer, (statistic / 100.0).sqrt(), 1e-12);
+
+        let outcome = chi_square_independence(&[10, 0, 20, 20, 0, 40], 3);
+        assert::close(outcome.statistic, 0.0, 1e-12);
+        assert_eq!(outcome.degrees, 1);
+        assert::close(outcome.p, 1.0, 1e-12);
+        assert::close(outcome.cramer, 0.0, 1e-12);
+
+        let outcome = chi_square_independence(&[50, 0, 0, 50], 2);
+        assert::close(outcome.cramer, 1.0, 1e-12);
+        assert!(outcome.p < 1e-20);
+    }
+
+    #[test]
+    fn merge() {
+        let d = Categorical::new(&[0.01, 0.01, 0.48, 0.5]);
+        let outcome = chi_square_gof(&[2, 0, 48, 50], &d);
+        assert::close(outcome.statistic, 0.0, 1e-14);
+        assert::close(outcome.p, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn poisson() {
+        let mut source = source::default(42);
+        let d = Poisson::new(3.0);
+        let mut observed = vec![0; 12];
+        for x in Independent(&d, &mut source).take(1000) {
+            observed[x.min(11)] += 1;
+        }
+        assert!(chi_square_gof(&observed, &d).p > 0.05);
+        assert!(chi_square_gof(&observed, &Poisson::new(3.3)).p < 1e-3);
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::Continuous;
+use math;
+use test::Outcome;
+
+/// Perform the one-sample Kolmogorov–Smirnov test.
+///
+/// The null hypothesis is that the sample is drawn from `distribution`. The
+/// p-value is computed from the asymptotic distribution of the statistic with
+/// Stephens’ correction for finite samples. The sample should not be empty.
+///
+/// ## References
+///
+/// 1. M. A. Stephens, “Use of the Kolmogorov–Smirnov, Cramér–von Mises and
+///    related statistics without extensive tables,” Journal of the Royal
+///    Statistical Society, 1970.
+pub fn ks_one_sample<D>(data: &[f64], distribution: &D) -> Outcome
+where
+    D: Continuous,
+{
+    should!(!data.is_empty());
+    let n = data.len() as f64;
+    let sorted = sort(data);
+    let statistic = sorted
+        .iter()
+        .enumerate()
+        .fold(0.0f64, |statistic, (i, &x)| {
+            let p = distribution.distribution(x);
+            statistic.max((i + 1) as f64 / n - p).max(p - i as f64 / n)
+        });
+    Outcome {
+        statistic,
+        p: survival(effective(n) * statistic),
+    }
+}
+
+/// Perform the two-sample Kolmogorov–Smirnov test.
+///
+/// The null hypothesis is that the two samples are drawn from the same
+/// continuous distribution. The p-value is computed from the asymptotic
+/// distribution of the statistic. The samples should not be empty.
+pub fn ks_two_sample(one: &[f64], other: &[f64]) -> Outcome {
+    should!(!one.is_empty() && !other.is_empty());
+    let (one, other) = (sort(one), sort(other));
+    let (n, m) = (one.len() as f64, other.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut statistic = 0.0f64;
+    while i < one.len() && j < other.len() {
+        let x = one[i].min(other[j]);
+        while i < one.len() && one[i] == x {
+            i += 1;
+        }
+        while j < other.len() && other[j] == x {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+    }
+    Outcome {
+        statistic,
+        p: survival(effective(n * m / (n + m)) * statistic),
+    }
+}
+
+#[inline]
+fn effective(n: f64) -> f64 {
+    let root = n.sqrt();
+    root + 0.12 + 0.11 / root
+}
+
+/// Compute the survival function of the Kolmogorov distribution.
+fn survival(x: f64) -> f64 {
+    use core::f64::consts::PI;
+    const TERMS: usize = 100;
+    const EPSILON: f64 = 1e-16;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < 1.18 {
+        let factor = -PI * PI / (8.0 * x * x);
+        let mut sum = 0.0;
+        for j in 1..TERMS {
+            let k = (2 * j - 1) as f64;
+            let term = math::exp(factor * k * k);
+            sum += term;
+            if term <= EPSILON * sum {
+                break;
+            }
+        }
+        (1.0 - (2.0 * PI).sqrt() / x * sum).clamp(0.0, 1.0)
+    } else {
+        let mut sum = 0.0;
+        let mut sign = 1.0;
+        for j in 1..TERMS {
+            let j = j as f64;
+            let term = math::exp(-2.0 * j * j * x * x);
+            sum += sign * term;
+            if term <= EPSILON * sum.abs() {
+                break;
+            }
+            sign = -sign;
+        }
+        (2.0 * sum).clamp(0.0, 1.0)
+    }
+}
+
+fn sort(data: &[f64]) -> Vec<f64> {
+    let mut data = data.to_vec();
+    data.sort_by(|one, other| one.total_cmp(other));
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::{ks_one_sample, ks_two_sample, survival};
+
+    #[test]
+    fn one_sample() {
+        let data = [0.1, 0.4, 0.7];
+        let outcome = ks_one_sample(&data, &Uniform::new(0.0, 1.0));
+        assert::close(outcome.statistic, 0.3, 1e-15);
+
+        let mut source = source::default(42);
+        let data = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(1000)
+            .collect::<Vec<_>>();
+        assert!(ks_one_sample(&data, &Gaussian::new(0.0, 1.0)).p > 0.05);
+        assert!(ks_one_sample(&data, &Gaussian::new(0.2, 1.0)).p < 1e-3);
+    }
+
+    #[test]
+    fn two_sample() {
+        let outcome = ks_two_sample(&[1.0, 2.0, 3.0, 4.0], &[3.0, 4.0, 5.0, 6.0]);
+        assert::close(outcome.statistic, 0.5, 1e-15);
+
+        let mut source = source::default(42);
+        let one = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(500)
+            .collect::<Vec<_>>();
+        let other = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(700)
+            .collect::<Vec<_>>();
+        let shifted = other.iter().map(|&x| x + 0.5).collect::<Vec<_>>();
+        assert!(ks_two_sample(&one, &other).p > 0.05);
+        assert!(ks_two_sample(&one, &shifted).p < 1e-3);
+    }
+
+    #[test]
+    fn kolmogorov() {
+        assert::close(survival(0.0), 1.0, 0.0);
+        assert::close(survival(0.5), 0.9639452436648751, 1e-12);
+        assert::close(survival(1.0), 0.2699996716773546, 1e-12);
+        assert::close(survival(1.36), 0.04948587675537788, 1e-12);
+        assert::close(survival(2.0), 0.0006709252557796953, 1e-12);
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Distribution, Gaussian};
+use test::Outcome;
+
+const EXACT: usize = 50;
+
+/// Perform the Mann–Whitney U test.
+///
+/// The null hypothesis is that a value drawn from the distribution of the
+/// first sample is equally likely to be smaller or larger than a value drawn
+/// from the distribution of the second one. The statistic is the `U` of the
+/// first sample, that is, the number of pairs in which the value from the
+/// first sample is larger, with ties counted as one half. The two-sided
+/// p-value is computed exactly if there are no ties and both samples have
+/// fewer than 50 values; otherwise, the normal approximation with the tie
+/// and continuity corrections is used. The samples should not be empty.
+pub fn mann_whitney(one: &[f64], other: &[f64]) -> Outcome {
+    should!(!one.is_empty() && !other.is_empty());
+    let (n, m) = (one.len(), other.len());
+    let mut values = one
+        .iter()
+        .map(|&x| (x, true))
+        .chain(other.iter().map(|&x| (x, false)))
+        .collect::<Vec<_>>();
+    values.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let total = n + m;
+    let (mut sum, mut ties) = (0.0, 0.0);
+    let mut i = 0;
+    while i < total {
+        let mut j = i;
+        while j < total && values[j].0 == values[i].0 {
+            j += 1;
+        }
+        let rank = (i + j + 1) as f64 / 2.0;
+        let count = (j - i) as f64;
+        sum += rank * values[i..j].iter().filter(|value| value.1).count() as f64;
+        ties += count * count * count - count;
+        i = j;
+    }
+    let statistic = sum - (n * (n + 1)) as f64 / 2.0;
+    let p = if ties == 0.0 && n < EXACT && m < EXACT {
+        exact(statistic as usize, n, m)
+    } else {
+        let (n, m, total) = (n as f64, m as f64, total as f64);
+        let mean = n * m / 2.0;
+        let variance = n * m / 12.0 * (total + 1.0 - ties / (total * (total - 1.0)));
+        if variance == 0.0 {
+            1.0
+        } else {
+            let z = ((statistic - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+            2.0 * (1.0 - Gaussian::default().distribution(z))
+        }
+    };
+    Outcome {
+        statistic,
+        p: p.min(1.0),
+    }
+}
+
+/// Compute the exact two-sided p-value via the recurrence for the number of
+/// arrangements with a given statistic.
+fn exact(u: usize, n: usize, m: usize) -> f64 {
+    let size = n * m + 1;
+    let mut previous = vec![vec![0.0; size]; m + 1];
+    for counts in previous.iter_mut() {
+        counts[0] = 1.0;
+    }
+    for _ in 1..=n {
+        let mut current = vec![vec![0.0; size]; m + 1];
+        current[0][0] = 1.0;
+        for j in 1..=m {
+            for k in 0..size {
+                let mut count = current[j - 1][k];
+                if k >= j {
+                    count += previous[j][k - j];
+                }
+                current[j][k] = count;
+            }
+        }
+        previous = current;
+    }
+    let counts = &previous[m];
+    let total = counts.iter().sum::<f64>();
+    let lower = counts[..=u].iter().sum::<f64>() / total;
+    let upper = counts[u..].iter().sum::<f64>() / total;
+    2.0 * lower.min(upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::mann_whitney;
+
+    #[test]
+    fn exact() {
+        let outcome = mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+        assert_eq!(outcome.statistic, 0.0);
+        assert::close(outcome.p, 0.1, 1e-15);
+
+        let outcome = mann_whitney(&[1.0, 4.0, 6.0], &[2.0, 3.0, 5.0]);
+        assert_eq!(outcome.statistic, 5.0);
+        assert::close(outcome.p, 1.0, 1e-15);
+    }
+
+    #[test]
+    fn approximate() {
+        let outcome = mann_whitney(&[1.0, 2.0, 2.0, 3.0], &[2.0, 4.0, 5.0]);
+        assert_eq!(outcome.statistic, 2.0);
+        let variance = 12.0f64 / 12.0 * (8.0 - 24.0 / 42.0);
+        let z = (4.0 - 0.5) / variance.sqrt();
+        assert::close(
+            outcome.p,
+            2.0 * (1.0 - Gaussian::default().distribution(z)),
+            1e-12,
+        );
+
+        let mut source = source::default(42);
+        let one = Independent(&Gaussian::new(0.0, 1.0), &mut source)
+            .take(200)
+            .collect::<Vec<_>>();
+        let other = Independent(&Gaussian::new(0.5, 1.0), &mut source)
+            .take(300)
+            .collect::<Vec<_>>();
+        assert!(mann_whitney(&one, &other).p < 1e-3);
+        assert!(mann_whitney(&one, &other[..100]).p < 0.05);
+    }
+}
@@ -0,0 +1,42 @@
+//! Statistical tests.
+//!
+//! ## Example
+//!
+//! ```
+//! use probability::prelude::*;
+//! use probability::test;
+//!
+//! let mut source = source::default(42);
+//! let distribution = Gaussian::new(0.0, 1.0);
+//! let data = Independent(&distribution, &mut source)
+//!     .take(100)
+//!     .collect::<Vec<_>>();
+//! let outcome = test::ks_one_sample(&data, &distribution);
+//! assert!(outcome.p > 0.05);
+//! ```
+
+mod anderson;
+mod chi;
+mod kolmogorov;
+mod mann;
+mod shapiro;
+pub(crate) mod student;
+
+pub use self::anderson::{
+    anderson_darling, anderson_darling_exponential, anderson_darling_gaussian, AndersonDarling,
+};
+pub use self::chi::{chi_square_gof, chi_square_independence, Independence};
+pub use self::kolmogorov::{ks_one_sample, ks_two_sample};
+pub use self::mann::mann_whitney;
+pub use self::shapiro::shapiro_wilk;
+pub use self::student::{t_test_one_sample, t_test_paired, t_test_two_sample, welch_t_test, TTest};
+
+/// The outcome of a test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Outcome {
+    /// The test statistic.
+    pub statistic: f64,
+    /// The p-value, which is the probability of a statistic at least as
+    /// extreme as the observed one under the null hypothesis.
+    pub p: f64,
+}
@@ -0,0 +1,151 @@
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{Distribution, Gaussian, Inverse};
+use math;
+use test::Outcome;
+
+/// Perform the Shapiro–Wilk test for normality.
+///
+/// The null hypothesis is that the sample is drawn from a Gaussian
+/// distribution. The statistic `W` and the p-value are computed using
+/// Royston’s approximations. The sample should contain between 3 and 5000
+/// values, which should not all be equal.
+///
+/// ## References
+///
+/// 1. P. Royston, “Remark AS R94: A remark on algorithm AS 181: The W-test for
+///    normality,” Applied Statistics, 1995.
+pub fn shapiro_wilk(data: &[f64]) -> Outcome {
+    const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+    const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+
+    let n = data.len();
+    should!((3..=5000).contains(&n));
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|one, other| one.total_cmp(other));
+
+    let gaussian = Gaussian::default();
+    let count = n as f64;
+    let m = (1..=n)
+        .map(|i| gaussian.inverse((i as f64 - 0.375) / (count + 0.25)))
+        .collect::<Vec<_>>();
+    let sum = m.iter().fold(0.0, |sum, &m| sum + m * m);
+    let mut a = vec![0.0; n];
+    if n == 3 {
+        a[2] = 0.5f64.sqrt();
+    } else {
+        let u = 1.0 / count.sqrt();
+        let root = sum.sqrt();
+        let last = m[n - 1] / root + poly(&C1, u);
+        let (phi, tail) = if n > 5 {
+            let other = m[n - 2] / root + poly(&C2, u);
+            a[n - 2] = other;
+            (
+                (sum - 2.0 * m[n - 1] * m[n - 1] - 2.0 * m[n - 2] * m[n - 2])
+                    / (1.0 - 2.0 * last * last - 2.0 * other * other),
+                2,
+            )
+        } else {
+            (
+                (sum - 2.0 * m[n - 1] * m[n - 1]) / (1.0 - 2.0 * last * last),
+                1,
+            )
+        };
+        a[n - 1] = last;
+        let phi = phi.sqrt();
+        for i in tail..(n - tail) {
+            a[i] = m[i] / phi;
+        }
+    }
+    for i in 0..(n / 2) {
+        a[i] = -a[n - 1 - i];
+    }
+
+    let mean = sorted.iter().fold(0.0, |sum, &x| sum + x) / count;
+    let numerator = a.iter().zip(&sorted).fold(0.0, |sum, (&a, &x)| sum + a * x);
+    let denominator = sorted
+        .iter()
+        .fold(0.0, |sum, &x| sum + (x - mean) * (x - mean));
+    should!(denominator > 0.0);
+    let statistic = (numerator * numerator / denominator).min(1.0);
+
+    Outcome {
+        statistic,
+        p: p(statistic, n),
+    }
+}
+
+fn p(w: f64, n: usize) -> f64 {
+    use core::f64::consts::PI;
+
+    let count = n as f64;
+    if n == 3 {
+        let p = 6.0 / PI * (libm::asin(w.sqrt()) - PI / 3.0);
+        return p.max(0.0);
+    }
+    let mut y = math::ln_1p(-w);
+    let (mean, deviation) = if n <= 11 {
+        let gamma = -2.273 + 0.459 * count;
+        if y >= gamma {
+            return 0.0;
+        }
+        y = -math::ln(gamma - y);
+        (
+            poly(&[0.544, -0.39978, 0.025054, -6.714e-4], count),
+            math::exp(poly(&[1.3822, -0.77857, 0.062767, -0.0020322], count)),
+        )
+    } else {
+        let x = math::ln(count);
+        (
+            poly(&[-1.5861, -0.31082, -0.083751, 0.0038915], x),
+            math::exp(poly(&[-0.4803, -0.082676, 0.0030302], x)),
+        )
+    };
+    1.0 - Gaussian::default().distribution((y - mean) / deviation)
+}
+
+#[inline]
+fn poly(coefficients: &[f64], x: f64) -> f64 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |sum, &coefficient| sum * x + coefficient)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use assert;
+    use prelude::*;
+
+    use super::shapiro_wilk;
+
+    #[test]
+    fn small() {
+        let outcome = shapiro_wilk(&[1.0, 2.0, 4.0]);
+        assert::close(outcome.statistic, 0.9642857142857143, 1e-12);
+        assert::close(outcome.p, 0.6368868450289714, 1e-9);
+
+        let data = [
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0,
+        ];
+        let outcome = shapiro_wilk(&data);
+        assert::close(outcome.statistic, 0.7888146948353878, 1e-9);
+        assert::close(outcome.p, 0.006703814056502999, 1e-9);
+    }
+
+    #[test]
+    fn large() {
+        let mut source = source::default(42);
+        let data = Independent(&Gaussian::new(1.0, 2.0), &mut source)
+            .take(500)
+            .collect::<Vec<_>>();
+        assert!(shapiro_wilk(&data).p > 0.05);
+        let data = Independent(&Exponential::new(1.0), &mut source)
+            .take(500)
+            .collect::<Vec<_>>();
+        assert!(shapiro_wilk(&data).p < 1e-6);
+    }
+}
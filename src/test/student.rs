@@ -0,0 +1,187 @@
+use alloc::vec::Vec;
+use special::Beta;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use estimate;
+use math;
+
+/// The outcome of a t-test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TTest {
+    /// The test statistic.
+    pub statistic: f64,
+    /// The number of degrees of freedom.
+    pub degrees: f64,
+    /// The two-sided p-value.
+    pub p: f64,
+    /// The estimated difference in means.
+    pub difference: f64,
+    /// The standard error of the difference.
+    pub error: f64,
+}
+
+impl TTest {
+    fn new(difference: f64, error: f64, degrees: f64) -> Self {
+        let statistic = difference / error;
+        TTest {
+            statistic,
+            degrees,
+            p: tail(statistic, degrees),
+            difference,
+            error,
+        }
+    }
+
+    /// Compute a confidence interval for the difference in means with
+    /// confidence level `level`.
+    ///
+    /// It should hold that `level > 0` and `level < 1`.
+    pub fn interval(&self, level: f64) -> (f64, f64) {
+        should!(level > 0.0 && level < 1.0);
+        let width = quantile(1.0 - level, self.degrees) * self.error;
+        (self.difference - width, self.difference + width)
+    }
+}
+
+/// Perform the one-sample t-test.
+///
+/// The null hypothesis is that the mean of the distribution of the sample is
+/// `mu`, and the difference is the one between the sample mean and `mu`. The
+/// sample should contain at least two values, which should not all be equal.
+pub fn t_test_one_sample(data: &[f64], mu: f64) -> TTest {
+    should!(data.len() > 1);
+    let (n, mean, variance) = moments(data);
+    TTest::new(mean - mu, (variance / n).sqrt(), n - 1.0)
+}
+
+/// Perform the paired t-test.
+///
+/// The null hypothesis is that the mean of the pairwise differences between
+/// the two samples is zero. The samples should have the same length.
+pub fn t_test_paired(one: &[f64], other: &[f64]) -> TTest {
+    should!(one.len() == other.len());
+    let differences = one
+        .iter()
+        .zip(other)
+        .map(|(&x, &y)| x - y)
+        .collect::<Vec<_>>();
+    t_test_one_sample(&differences, 0.0)
+}
+
+/// Perform Student’s two-sample t-test assuming equal variances.
+///
+/// The null hypothesis is that the two samples are drawn from distributions
+/// with the same mean. Each sample should contain at least two values.
+pub fn t_test_two_sample(one: &[f64], other: &[f64]) -> TTest {
+    should!(one.len() > 1 && other.len() > 1);
+    let (n, one_mean, one_variance) = moments(one);
+    let (m, other_mean, other_variance) = moments(other);
+    let degrees = n + m - 2.0;
+    let pooled = ((n - 1.0) * one_variance + (m - 1.0) * other_variance) / degrees;
+    TTest::new(
+        one_mean - other_mean,
+        (pooled * (1.0 / n + 1.0 / m)).sqrt(),
+        degrees,
+    )
+}
+
+/// Perform Welch’s two-sample t-test.
+///
+/// The null hypothesis is that the two samples are drawn from distributions
+/// with the same mean, and the variances are not assumed to be equal. The
+/// degrees of freedom are given by the Welch–Satterthwaite equation. Each
+/// sample should contain at least two values.
+pub fn welch_t_test(one: &[f64], other: &[f64]) -> TTest {
+    should!(one.len() > 1 && other.len() > 1);
+    let (n, one_mean, one_variance) = moments(one);
+    let (m, other_mean, other_variance) = moments(other);
+    let (a, b) = (one_variance / n, other_variance / m);
+    let degrees = math::powi(a + b, 2) / (a * a / (n - 1.0) + b * b / (m - 1.0));
+    TTest::new(one_mean - other_mean, (a + b).sqrt(), degrees)
+}
+
+/// Compute the size, mean, and unbiased variance of a sample.
+fn moments(data: &[f64]) -> (f64, f64, f64) {
+    let n = data.len() as f64;
+    let mean = estimate::mean(data);
+    (n, mean, estimate::variance(data, mean) * n / (n - 1.0))
+}
+
+/// Compute the probability of exceeding `|t|` in absolute value under
+/// Student’s t-distribution.
+pub(crate) fn tail(t: f64, degrees: f64) -> f64 {
+    let x = degrees / (degrees + t * t);
+    x.inc_beta(0.5 * degrees, 0.5, (0.5 * degrees).ln_beta(0.5))
+}
+
+/// Compute the value exceeded in absolute value with probability `p` under
+/// Student’s t-distribution.
+pub(crate) fn quantile(p: f64, degrees: f64) -> f64 {
+    let x = p.inv_inc_beta(0.5 * degrees, 0.5, (0.5 * degrees).ln_beta(0.5));
+    (degrees * (1.0 - x) / x).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    use super::{t_test_one_sample, t_test_paired, t_test_two_sample, welch_t_test};
+
+    const ONE: [f64; 6] = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3];
+    const OTHER: [f64; 5] = [4.2, 4.8, 4.5, 5.0, 4.4];
+
+    #[test]
+    fn distribution() {
+        use super::{quantile, tail};
+        assert::close(tail(0.0, 5.0), 1.0, 1e-14);
+        assert::close(tail(2.570581835636314, 5.0), 0.05, 1e-10);
+        assert::close(tail(1.959963984540054, 1e6), 0.05, 1e-6);
+        assert::close(quantile(0.05, 5.0), 2.570581835636314, 1e-8);
+        assert::close(quantile(0.01, 10.0), 3.169272672616951, 1e-8);
+    }
+
+    #[test]
+    fn one_sample() {
+        let outcome = t_test_one_sample(&ONE, 5.0);
+        let error = (0.179f64 / 6.0).sqrt();
+        assert::close(outcome.difference, 0.45, 1e-14);
+        assert::close(outcome.error, error, 1e-14);
+        assert::close(outcome.statistic, 0.45 / error, 1e-12);
+        assert_eq!(outcome.degrees, 5.0);
+        let (lower, upper) = outcome.interval(0.95);
+        assert::close(lower, 0.45 - 2.570581835636314 * error, 1e-8);
+        assert::close(upper, 0.45 + 2.570581835636314 * error, 1e-8);
+        assert::close(outcome.p, super::tail(0.45 / error, 5.0), 1e-15);
+    }
+
+    #[test]
+    fn paired() {
+        let one = [1.0, 2.0, 3.0, 4.0];
+        let other = [0.5, 1.0, 2.5, 3.0];
+        let outcome = t_test_paired(&one, &other);
+        let expected = t_test_one_sample(&[0.5, 1.0, 0.5, 1.0], 0.0);
+        assert_eq!(outcome, expected);
+    }
+
+    #[test]
+    fn two_sample() {
+        let student = t_test_two_sample(&ONE, &OTHER);
+        let welch = welch_t_test(&ONE, &OTHER);
+        assert::close(student.difference, 5.45 - 4.58, 1e-14);
+        assert_eq!(student.degrees, 9.0);
+        assert::close(
+            student.error,
+            ((5.0 * 0.179 + 4.0 * 0.102) / 9.0 * (1.0 / 6.0 + 1.0 / 5.0f64)).sqrt(),
+            1e-14,
+        );
+        let (a, b) = (0.179f64 / 6.0, 0.102 / 5.0);
+        assert::close(welch.error, (a + b).sqrt(), 1e-14);
+        assert::close(
+            welch.degrees,
+            (a + b) * (a + b) / (a * a / 5.0 + b * b / 4.0),
+            1e-12,
+        );
+        assert!(student.p < 0.01 && welch.p < 0.01);
+    }
+}
@@ -0,0 +1,341 @@
+//! Combinations of random variables.
+//!
+//! The types below allow for composing independent random variables
+//! declaratively. For instance, `Scaled(2.0, &gaussian) + Shifted(1.0,
+//! &exponential)` is the sum of a Gaussian random variable multiplied by two
+//! and an exponential random variable shifted by one. The composition can be
+//! sampled and has its mean and variance computed via linearity. The
+//! distribution function and the density of a sum are computed by numerical
+//! convolution.
+//!
+//! ## Example
+//!
+//! ```
+//! use probability::prelude::*;
+//!
+//! let gaussian = Gaussian::new(0.0, 1.0);
+//! let exponential = Exponential::new(1.0);
+//! let variable = Scaled(2.0, &gaussian) + Shifted(1.0, &exponential);
+//! assert_eq!(variable.mean(), 2.0);
+//! assert_eq!(variable.variance(), 5.0);
+//! ```
+
+use core::ops::Add;
+#[allow(unused_imports)]
+use special::Primitive;
+
+use distribution::{self, Continuous, Distribution, Mean, Sample, Support, Variance};
+use quadrature;
+use source::Source;
+
+/// A random variable multiplied by a constant.
+///
+/// The constant should not be zero.
+#[derive(Clone, Copy, Debug)]
+pub struct Scaled<'l, D: 'l>(pub f64, pub &'l D);
+
+/// A random variable with a constant added.
+#[derive(Clone, Copy, Debug)]
+pub struct Shifted<'l, D: 'l>(pub f64, pub &'l D);
+
+/// A sum of two independent random variables.
+#[derive(Clone, Copy, Debug)]
+pub struct Sum<A, B>(pub A, pub B);
+
+impl<'l, D, R> Add<R> for Scaled<'l, D> {
+    type Output = Sum<Self, R>;
+
+    #[inline]
+    fn add(self, other: R) -> Self::Output {
+        Sum(self, other)
+    }
+}
+
+impl<'l, D> distribution::Continuous for Scaled<'l, D>
+where
+    D: Continuous<Value = f64>,
+{
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        let Scaled(a, distribution) = *self;
+        distribution.density(x / a) / a.abs()
+    }
+}
+
+impl<'l, D> distribution::Distribution for Scaled<'l, D>
+where
+    D: Distribution<Value = f64>,
+{
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        let Scaled(a, distribution) = *self;
+        should!(a != 0.0);
+        if a > 0.0 {
+            distribution.distribution(x / a)
+        } else {
+            1.0 - distribution.distribution(x / a)
+        }
+    }
+}
+
+impl<'l, D> distribution::Mean for Scaled<'l, D>
+where
+    D: Mean<Value = f64>,
+{
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.0 * self.1.mean()
+    }
+}
+
+impl<'l, D> distribution::Sample for Scaled<'l, D>
+where
+    D: Sample<Value = f64>,
+{
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        self.0 * self.1.sample(source)
+    }
+}
+
+impl<'l, D> distribution::Support for Scaled<'l, D>
+where
+    D: Support<Value = f64>,
+{
+    fn support(&self) -> (f64, f64) {
+        let Scaled(a, distribution) = *self;
+        let (start, end) = distribution.support();
+        if a > 0.0 {
+            (a * start, a * end)
+        } else {
+            (a * end, a * start)
+        }
+    }
+}
+
+impl<'l, D> distribution::Variance for Scaled<'l, D>
+where
+    D: Variance<Value = f64>,
+{
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.0 * self.0 * self.1.variance()
+    }
+}
+
+impl<'l, D, R> Add<R> for Shifted<'l, D> {
+    type Output = Sum<Self, R>;
+
+    #[inline]
+    fn add(self, other: R) -> Self::Output {
+        Sum(self, other)
+    }
+}
+
+impl<'l, D> distribution::Continuous for Shifted<'l, D>
+where
+    D: Continuous<Value = f64>,
+{
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        self.1.density(x - self.0)
+    }
+}
+
+impl<'l, D> distribution::Distribution for Shifted<'l, D>
+where
+    D: Distribution<Value = f64>,
+{
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        self.1.distribution(x - self.0)
+    }
+}
+
+impl<'l, D> distribution::Mean for Shifted<'l, D>
+where
+    D: Mean<Value = f64>,
+{
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.1.mean() + self.0
+    }
+}
+
+impl<'l, D> distribution::Sample for Shifted<'l, D>
+where
+    D: Sample<Value = f64>,
+{
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        self.1.sample(source) + self.0
+    }
+}
+
+impl<'l, D> distribution::Support for Shifted<'l, D>
+where
+    D: Support<Value = f64>,
+{
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        let (start, end) = self.1.support();
+        (start + self.0, end + self.0)
+    }
+}
+
+impl<'l, D> distribution::Variance for Shifted<'l, D>
+where
+    D: Variance<Value = f64>,
+{
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.1.variance()
+    }
+}
+
+impl<A, B, R> Add<R> for Sum<A, B> {
+    type Output = Sum<Self, R>;
+
+    #[inline]
+    fn add(self, other: R) -> Self::Output {
+        Sum(self, other)
+    }
+}
+
+impl<A, B> distribution::Continuous for Sum<A, B>
+where
+    A: Continuous<Value = f64> + Support,
+    B: Continuous<Value = f64>,
+{
+    fn density(&self, x: f64) -> f64 {
+        let Sum(ref one, ref other) = *self;
+        let (start, end) = one.support();
+        quadrature::integrate(|y| one.density(y) * other.density(x - y), start, end)
+    }
+}
+
+impl<A, B> distribution::Distribution for Sum<A, B>
+where
+    A: Continuous<Value = f64> + Support,
+    B: Distribution<Value = f64>,
+{
+    type Value = f64;
+
+    fn distribution(&self, x: f64) -> f64 {
+        let Sum(ref one, ref other) = *self;
+        let (start, end) = one.support();
+        let value =
+            quadrature::integrate(|y| one.density(y) * other.distribution(x - y), start, end);
+        value.clamp(0.0, 1.0)
+    }
+}
+
+impl<A, B> distribution::Mean for Sum<A, B>
+where
+    A: Continuous<Value = f64> + Mean + Support,
+    B: Mean<Value = f64>,
+{
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.0.mean() + self.1.mean()
+    }
+}
+
+impl<A, B> distribution::Sample for Sum<A, B>
+where
+    A: Continuous<Value = f64> + Sample + Support,
+    B: Sample<Value = f64>,
+{
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        self.0.sample(source) + self.1.sample(source)
+    }
+}
+
+impl<A, B> distribution::Support for Sum<A, B>
+where
+    A: Continuous<Value = f64> + Support,
+    B: Support<Value = f64>,
+{
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        let (one_start, one_end) = self.0.support();
+        let (other_start, other_end) = self.1.support();
+        (one_start + other_start, one_end + other_end)
+    }
+}
+
+impl<A, B> distribution::Variance for Sum<A, B>
+where
+    A: Continuous<Value = f64> + Support + Variance,
+    B: Variance<Value = f64>,
+{
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.0.variance() + self.1.variance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn distribution() {
+        let one = Gaussian::new(1.0, 2.0);
+        let other = Gaussian::new(-3.0, 1.5);
+        let variable = Scaled(-2.0, &one) + Shifted(1.0, &other);
+        let expected = Gaussian::new(-4.0, (16.0f64 + 2.25).sqrt());
+        for &x in [-10.0, -4.0, 0.0, 3.0].iter() {
+            assert::close(variable.distribution(x), expected.distribution(x), 1e-10);
+            assert::close(variable.density(x), expected.density(x), 1e-10);
+        }
+    }
+
+    #[test]
+    fn mean() {
+        let one = Gaussian::new(1.0, 2.0);
+        let other = Exponential::new(2.0);
+        assert_eq!((Scaled(3.0, &one) + Shifted(1.0, &other)).mean(), 4.5);
+    }
+
+    #[test]
+    fn sample() {
+        let one = Uniform::new(0.0, 1.0);
+        let other = Uniform::new(2.0, 3.0);
+        let variable = Scaled(-1.0, &one) + Shifted(1.0, &other);
+        let mut source = source::default(42);
+        for x in Independent(&variable, &mut source).take(100) {
+            assert!((2.0..=4.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn support() {
+        let one = Exponential::new(1.0);
+        let other = Uniform::new(2.0, 3.0);
+        let variable = Scaled(-2.0, &one) + Shifted(1.0, &other);
+        assert_eq!(variable.support(), (f64::NEG_INFINITY, 4.0));
+    }
+
+    #[test]
+    fn variance() {
+        let one = Gaussian::new(1.0, 2.0);
+        let other = Exponential::new(2.0);
+        let variable = Scaled(3.0, &one) + Shifted(1.0, &other) + Shifted(0.0, &one);
+        assert_eq!(variable.variance(), 36.0 + 0.25 + 4.0);
+    }
+}
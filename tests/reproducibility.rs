@@ -0,0 +1,105 @@
+//! The streams of samples are fixed per version of the package and should be
+//! identical on all targets. The elementary functions come from `libm` when
+//! the library is compiled with the `strict` feature or without the standard
+//! library.
+
+#![cfg(any(feature = "strict", not(any(feature = "std", feature = "rayon"))))]
+
+extern crate probability;
+
+use probability::prelude::*;
+
+const SEED: u64 = 0x9e3779b97f4a7c15;
+
+fn stream<D: Sample>(distribution: D) -> Vec<D::Value> {
+    let mut source = source::default(SEED);
+    Independent(&distribution, &mut source).take(4).collect()
+}
+
+fn bits<D: Sample<Value = f64>>(distribution: D) -> Vec<u64> {
+    stream(distribution).into_iter().map(f64::to_bits).collect()
+}
+
+#[test]
+fn beta() {
+    assert_eq!(
+        bits(Beta::new(2.0, 3.0, 0.0, 1.0)),
+        [
+            0x3fd8d00dc5d53f68,
+            0x3fb43a17552f233c,
+            0x3fd6706188804972,
+            0x3fc61abd5214c897
+        ],
+    );
+}
+
+#[test]
+fn binomial() {
+    assert_eq!(stream(Binomial::new(1000, 0.3)), [307, 285, 303, 281]);
+}
+
+#[test]
+fn exponential() {
+    assert_eq!(
+        bits(Exponential::new(2.0)),
+        [
+            0x3fb5e98d7d8bf1b9,
+            0x3fe15635fbe8011a,
+            0x3fa6c4448b81835d,
+            0x3fbf997c4d5defcc
+        ],
+    );
+}
+
+#[test]
+fn gamma() {
+    assert_eq!(
+        bits(Gamma::new(0.5, 2.0)),
+        [
+            0x3f6847201728fc75,
+            0x3fe0f642f6933700,
+            0x3f9b2242366cf651,
+            0x3fe3fd27ee0e5c6d
+        ],
+    );
+    assert_eq!(
+        bits(Gamma::new(9.0, 0.5)),
+        [
+            0x400ef4c84ad36edb,
+            0x4012facc18f549a7,
+            0x4006a6f81aa2bb62,
+            0x40073cd7a45fc66e
+        ],
+    );
+}
+
+#[test]
+fn gaussian() {
+    assert_eq!(
+        bits(Gaussian::new(1.0, 2.0)),
+        [
+            0x3fd622e9c3790024,
+            0x40074c12d3659fe4,
+            0x3ff8adb46ad4b994,
+            0x3fe27011a0b86d73
+        ],
+    );
+}
+
+#[test]
+fn lognormal() {
+    assert_eq!(
+        bits(Lognormal::new(1.0, 0.5)),
+        [
+            0x4002773443508a4b,
+            0x4011898d3a99796f,
+            0x4008e7855c6efac4,
+            0x40038f5c17a3ef27
+        ],
+    );
+}
+
+#[test]
+fn poisson() {
+    assert_eq!(stream(Poisson::new(30.0)), [30, 29, 25, 27]);
+}